@@ -0,0 +1,309 @@
+//! Blind A/B/X comparison of HQPlayer pipelines
+//!
+//! A session takes two or more pipeline presets and hides them behind the
+//! labels A, B, C... in random order. For each trial the bridge secretly picks
+//! one of them as X; the listener switches freely between the labelled options
+//! and X (from the knob or the UI), then guesses which label X is. Finishing
+//! the session reveals the labels, scores the guesses and restores the
+//! pipeline that was live when the session started.
+//!
+//! While a session runs, the instance's pipeline is masked in events and
+//! status reports so nothing on screen gives X away.
+
+use anyhow::{anyhow, bail, Result};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::adapters::hqplayer::{HqpInstanceManager, HqpPipelineSettings, HqpPreset};
+
+const MAX_OPTIONS: usize = 8;
+
+/// Label of the hidden option
+const X: &str = "X";
+
+fn label(index: usize) -> String {
+    char::from(b'A' + index as u8).to_string()
+}
+
+/// One scored guess
+#[derive(Debug, Clone, Serialize)]
+pub struct AbxTrial {
+    /// Label X really was
+    pub x: String,
+    pub guess: String,
+    pub correct: bool,
+}
+
+/// Session state as the listener may see it (nothing that gives X away)
+#[derive(Debug, Clone, Serialize)]
+pub struct AbxStatus {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Labels to choose from, besides X
+    pub labels: Vec<String>,
+    /// What's switched in right now ("A", "B", ... or "X")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+    /// Guesses made so far
+    pub trials: usize,
+}
+
+/// Revealed label and preset
+#[derive(Debug, Clone, Serialize)]
+pub struct AbxOption {
+    pub label: String,
+    pub preset: String,
+}
+
+/// Session results
+#[derive(Debug, Clone, Serialize)]
+pub struct AbxResults {
+    pub instance: String,
+    pub options: Vec<AbxOption>,
+    pub trials: Vec<AbxTrial>,
+    pub correct: usize,
+    /// Chance of scoring at least this well by guessing (one-sided binomial)
+    pub p_value: f64,
+}
+
+struct Session {
+    instance: String,
+    /// Shuffled: options[i] is behind label(i)
+    options: Vec<HqpPreset>,
+    /// Index of X for the current trial
+    x: usize,
+    current: Option<String>,
+    trials: Vec<AbxTrial>,
+    /// Pipeline to restore at the end
+    original: Option<HqpPipelineSettings>,
+}
+
+impl Session {
+    fn status(&self) -> AbxStatus {
+        AbxStatus {
+            active: true,
+            instance: Some(self.instance.clone()),
+            labels: (0..self.options.len()).map(label).collect(),
+            current: self.current.clone(),
+            trials: self.trials.len(),
+        }
+    }
+
+    /// Resolve "A".."H" / "X" (case-insensitive) to an option index
+    fn resolve(&self, name: &str) -> Result<usize> {
+        let name = name.trim().to_ascii_uppercase();
+        if name == X {
+            return Ok(self.x);
+        }
+        (0..self.options.len())
+            .find(|&i| label(i) == name)
+            .ok_or_else(|| anyhow!("Unknown A/B/X option: {}", name))
+    }
+}
+
+/// Probability of at least `correct` right out of `trials` when each guess
+/// has a 1 in `choices` chance
+pub fn binomial_p_value(trials: usize, correct: usize, choices: usize) -> f64 {
+    if correct == 0 {
+        return 1.0;
+    }
+    let p = 1.0 / choices as f64;
+    let mut total = 0.0;
+    let mut combinations = 1.0; // C(trials, k), built up from k = 0
+    for k in 0..=trials {
+        if k > 0 {
+            combinations *= (trials - k + 1) as f64 / k as f64;
+        }
+        if k >= correct {
+            total += combinations * p.powi(k as i32) * (1.0 - p).powi((trials - k) as i32);
+        }
+    }
+    total.min(1.0)
+}
+
+/// The bridge's A/B/X session (one at a time)
+pub struct AbxTester {
+    instances: Arc<HqpInstanceManager>,
+    session: Mutex<Option<Session>>,
+}
+
+impl AbxTester {
+    pub fn new(instances: Arc<HqpInstanceManager>) -> Self {
+        Self {
+            instances,
+            session: Mutex::new(None),
+        }
+    }
+
+    /// Current session state
+    pub async fn status(&self) -> AbxStatus {
+        match self.session.lock().await.as_ref() {
+            Some(session) => session.status(),
+            None => AbxStatus {
+                active: false,
+                instance: None,
+                labels: Vec::new(),
+                current: None,
+                trials: 0,
+            },
+        }
+    }
+
+    /// Start a session comparing presets on an instance
+    ///
+    /// An unfinished session is replaced: its pipeline is restored first.
+    pub async fn start(&self, instance: &str, presets: &[String]) -> Result<AbxStatus> {
+        if presets.len() < 2 || presets.len() > MAX_OPTIONS {
+            bail!("A/B/X needs 2 to {} presets", MAX_OPTIONS);
+        }
+        let adapter = self.instances.require(instance).await?;
+
+        let mut options: Vec<HqpPreset> = Vec::new();
+        for name in presets {
+            let preset = self
+                .instances
+                .get_preset(name)
+                .await
+                .ok_or_else(|| anyhow!("Unknown HQP preset: {}", name))?;
+            if options.iter().any(|o| o.name == preset.name) {
+                bail!("Preset listed twice: {}", preset.name);
+            }
+            options.push(preset);
+        }
+
+        // The old session's pipeline goes back first, so it isn't taken for
+        // this session's original
+        let mut guard = self.session.lock().await;
+        if let Some(replaced) = guard.take() {
+            self.restore(&replaced).await;
+        }
+
+        let original = adapter.snapshot_pipeline().await.ok();
+        adapter.set_blind(true).await;
+        let x = {
+            let mut rng = rand::thread_rng();
+            options.shuffle(&mut rng);
+            rng.gen_range(0..options.len())
+        };
+
+        let session = Session {
+            instance: instance.to_string(),
+            options,
+            x,
+            current: None,
+            trials: Vec::new(),
+            original,
+        };
+        let status = session.status();
+        *guard = Some(session);
+        tracing::info!(
+            "A/B/X session started on {} with {} options",
+            instance,
+            presets.len()
+        );
+        Ok(status)
+    }
+
+    /// Switch the instance to an option ("A", "B", ... or "X")
+    ///
+    /// The pipeline is always re-applied, even when it's already in effect,
+    /// so the switch itself doesn't give X away.
+    pub async fn select(&self, name: &str) -> Result<AbxStatus> {
+        let mut guard = self.session.lock().await;
+        let session = guard
+            .as_mut()
+            .ok_or_else(|| anyhow!("No A/B/X session running"))?;
+        let index = session.resolve(name)?;
+
+        self.instances
+            .require(&session.instance)
+            .await?
+            .apply_pipeline(&session.options[index].settings)
+            .await?;
+        session.current = Some(if name.trim().eq_ignore_ascii_case(X) {
+            X.to_string()
+        } else {
+            label(index)
+        });
+        Ok(session.status())
+    }
+
+    /// Record which label the listener thinks X is, and draw a new X
+    pub async fn guess(&self, name: &str) -> Result<AbxStatus> {
+        let mut guard = self.session.lock().await;
+        let session = guard
+            .as_mut()
+            .ok_or_else(|| anyhow!("No A/B/X session running"))?;
+        if name.trim().eq_ignore_ascii_case(X) {
+            bail!("Guess which label X is, not X itself");
+        }
+        let guessed = session.resolve(name)?;
+
+        session.trials.push(AbxTrial {
+            x: label(session.x),
+            guess: label(guessed),
+            correct: guessed == session.x,
+        });
+        session.x = rand::thread_rng().gen_range(0..session.options.len());
+        session.current = None;
+        Ok(session.status())
+    }
+
+    /// End the session: reveal, score and restore the original pipeline
+    pub async fn finish(&self) -> Result<AbxResults> {
+        let session = self
+            .session
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| anyhow!("No A/B/X session running"))?;
+
+        self.restore(&session).await;
+
+        let correct = session.trials.iter().filter(|t| t.correct).count();
+        let results = AbxResults {
+            p_value: binomial_p_value(session.trials.len(), correct, session.options.len()),
+            instance: session.instance,
+            options: session
+                .options
+                .iter()
+                .enumerate()
+                .map(|(i, preset)| AbxOption {
+                    label: label(i),
+                    preset: preset.name.clone(),
+                })
+                .collect(),
+            trials: session.trials,
+            correct,
+        };
+        tracing::info!(
+            "A/B/X session finished: {}/{} correct (p = {:.3})",
+            results.correct,
+            results.trials.len(),
+            results.p_value
+        );
+        Ok(results)
+    }
+
+    /// Put a session's instance back on its original pipeline and unblind it
+    async fn restore(&self, session: &Session) {
+        match self.instances.get(&session.instance).await {
+            Some(adapter) => {
+                if let Some(original) = &session.original {
+                    if let Err(e) = adapter.apply_pipeline(original).await {
+                        tracing::warn!("A/B/X couldn't restore the pipeline: {}", e);
+                    }
+                }
+                adapter.set_blind(false).await;
+            }
+            None if session.original.is_some() => {
+                tracing::warn!("A/B/X couldn't restore the pipeline: instance removed");
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,1076 @@
+//! AirPlay adapter - exposes shairport-sync receivers as zones
+//!
+//! Reads shairport-sync's metadata pipe (`metadata.pipe_name`) or UDP
+//! metadata stream (`metadata.socket_port`) and turns it into a read-mostly
+//! zone with title, artist, album, cover art and progress.
+//!
+//! Playback commands are sent back through shairport-sync's MQTT remote
+//! (`<topic>/remote`) or D-Bus RemoteControl interface when configured.
+//! Remote control only works while the AirPlay client exposes DACP.
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::net::UdpSocket;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::bus::{BusEvent, PlaybackState, SharedBus, VolumeControl, VolumeScale, Zone};
+use crate::config::get_config_dir;
+
+const AIRPLAY_CONFIG_FILE: &str = "airplay-config.json";
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(5);
+/// RTP timestamps in `prgr` are in frames at 44.1 kHz
+const RTP_RATE: f64 = 44100.0;
+/// Drop buffered pipe data beyond this size (runaway or corrupt stream)
+const MAX_PIPE_BUFFER: usize = 16 * 1024 * 1024;
+/// AirPlay volume range in dB; -144 means muted
+const AIRPLAY_VOLUME_MIN: f32 = -30.0;
+const AIRPLAY_VOLUME_MUTED: f32 = -144.0;
+
+const DBUS_DEST: &str = "org.gnome.ShairportSync";
+const DBUS_PATH: &str = "/org/gnome/ShairportSync";
+const DBUS_REMOTE_INTERFACE: &str = "org.gnome.ShairportSync.RemoteControl";
+
+fn config_path() -> PathBuf {
+    get_config_dir().join(AIRPLAY_CONFIG_FILE)
+}
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// Remote control interface of a shairport-sync instance
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RemoteConfig {
+    /// MQTT remote (`mqtt.enable_remote = "yes"`)
+    Mqtt {
+        host: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+        /// shairport-sync `mqtt.topic`; commands go to `<topic>/remote`
+        topic: String,
+    },
+    /// D-Bus RemoteControl interface (via `dbus-send`)
+    Dbus {
+        /// Use the session bus instead of the system bus
+        #[serde(default)]
+        session: bool,
+    },
+}
+
+/// A shairport-sync receiver
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShairportReceiverConfig {
+    /// Zone name (also used in the zone_id)
+    pub name: String,
+    /// Path of the metadata pipe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipe: Option<String>,
+    /// Local UDP port shairport-sync sends metadata to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub udp_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteConfig>,
+}
+
+/// Saved config for persistence
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedAirPlayConfig {
+    receivers: Vec<ShairportReceiverConfig>,
+}
+
+// =============================================================================
+// Metadata parsing
+// =============================================================================
+
+/// A single shairport-sync metadata item
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataItem {
+    /// Item type: "core" (DMAP) or "ssnc" (shairport-sync)
+    pub item_type: String,
+    /// Four-character code (e.g. "minm", "pbeg")
+    pub code: String,
+    pub data: Vec<u8>,
+}
+
+impl MetadataItem {
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.data).trim().to_string()
+    }
+}
+
+/// Decode a hex-encoded four-character code (e.g. "6d696e6d" -> "minm")
+fn decode_code(hex: &str) -> Option<String> {
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    Some(String::from_utf8_lossy(&value.to_be_bytes()).into_owned())
+}
+
+/// Incremental parser for the metadata pipe's XML-ish item stream
+#[derive(Debug)]
+pub struct PipeParser {
+    buf: String,
+    item_re: Regex,
+}
+
+impl Default for PipeParser {
+    fn default() -> Self {
+        Self {
+            buf: String::new(),
+            item_re: Regex::new(
+                r#"(?s)<item><type>([0-9a-fA-F]{8})</type><code>([0-9a-fA-F]{8})</code><length>(\d+)</length>\s*(?:<data encoding="base64">(.*?)</data>)?\s*</item>"#,
+            )
+            .expect("valid metadata item regex"),
+        }
+    }
+}
+
+impl PipeParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed pipe data and return every complete item
+    ///
+    /// Partial items are kept until the rest arrives.
+    pub fn push(&mut self, chunk: &str) -> Vec<MetadataItem> {
+        self.buf.push_str(chunk);
+
+        let mut items = Vec::new();
+        let mut consumed = 0;
+        for caps in self.item_re.captures_iter(&self.buf) {
+            consumed = caps.get(0).map(|m| m.end()).unwrap_or(consumed);
+            let (Some(item_type), Some(code)) = (decode_code(&caps[1]), decode_code(&caps[2]))
+            else {
+                continue;
+            };
+            let data = match caps.get(4) {
+                Some(encoded) => {
+                    let encoded: String = encoded
+                        .as_str()
+                        .chars()
+                        .filter(|c| !c.is_whitespace())
+                        .collect();
+                    match base64::engine::general_purpose::STANDARD.decode(encoded) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::debug!("Bad base64 in {}/{} item: {}", item_type, code, e);
+                            continue;
+                        }
+                    }
+                }
+                None => Vec::new(),
+            };
+            items.push(MetadataItem {
+                item_type,
+                code,
+                data,
+            });
+        }
+
+        self.buf.drain(..consumed);
+        if self.buf.len() > MAX_PIPE_BUFFER {
+            tracing::warn!("Discarding {} bytes of unparsable metadata", self.buf.len());
+            self.buf.clear();
+        }
+        items
+    }
+}
+
+/// Reassembles shairport-sync UDP metadata packets
+///
+/// Each packet is a 4-byte type, 4-byte code and raw payload. Payloads too
+/// large for one datagram (cover art) arrive as `ssnc`/`chnk` packets:
+/// chunk index, chunk count, type and code, then the chunk data.
+#[derive(Debug, Default)]
+pub struct UdpReassembler {
+    chunks: HashMap<(String, String), Vec<Option<Vec<u8>>>>,
+}
+
+impl UdpReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one datagram; returns an item once it is complete
+    pub fn push(&mut self, packet: &[u8]) -> Option<MetadataItem> {
+        if packet.len() < 8 {
+            return None;
+        }
+        let item_type = String::from_utf8_lossy(&packet[0..4]).into_owned();
+        let code = String::from_utf8_lossy(&packet[4..8]).into_owned();
+
+        if item_type != "ssnc" || code != "chnk" {
+            return Some(MetadataItem {
+                item_type,
+                code,
+                data: packet[8..].to_vec(),
+            });
+        }
+
+        if packet.len() < 24 {
+            return None;
+        }
+        let index = u32::from_be_bytes(packet[8..12].try_into().ok()?) as usize;
+        let total = u32::from_be_bytes(packet[12..16].try_into().ok()?) as usize;
+        let item_type = String::from_utf8_lossy(&packet[16..20]).into_owned();
+        let code = String::from_utf8_lossy(&packet[20..24]).into_owned();
+        if total == 0 || index >= total {
+            return None;
+        }
+
+        let key = (item_type, code);
+        let parts = self
+            .chunks
+            .entry(key.clone())
+            .or_insert_with(|| vec![None; total]);
+        if parts.len() != total || index == 0 {
+            // A new transfer of this item replaces any incomplete one
+            *parts = vec![None; total];
+        }
+        parts[index] = Some(packet[24..].to_vec());
+
+        if parts.iter().all(Option::is_some) {
+            let parts = self.chunks.remove(&key)?;
+            let data = parts.into_iter().flatten().flatten().collect();
+            return Some(MetadataItem {
+                item_type: key.0,
+                code: key.1,
+                data,
+            });
+        }
+        None
+    }
+}
+
+// =============================================================================
+// Receiver state
+// =============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TrackInfo {
+    title: Option<String>,
+    artist: Option<String>,
+    album: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Progress {
+    start: u32,
+    current: u32,
+    end: u32,
+    received: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct Cover {
+    content_type: String,
+    data: Vec<u8>,
+    key: String,
+}
+
+/// What a metadata item changed
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Change {
+    None,
+    State,
+    NowPlaying,
+    Seek,
+    Volume,
+    /// Session ended: state and metadata both changed
+    Stopped,
+}
+
+struct Receiver {
+    config: ShairportReceiverConfig,
+    state: String,
+    client_name: Option<String>,
+    track: TrackInfo,
+    /// Metadata bundle being assembled between `mdst` and `mden`
+    pending: Option<TrackInfo>,
+    cover: Option<Cover>,
+    progress: Option<Progress>,
+    /// AirPlay volume in dB (-30..0, -144 = muted)
+    volume: Option<f32>,
+}
+
+impl Receiver {
+    fn new(config: ShairportReceiverConfig) -> Self {
+        Self {
+            config,
+            state: "stopped".to_string(),
+            client_name: None,
+            track: TrackInfo::default(),
+            pending: None,
+            cover: None,
+            progress: None,
+            volume: None,
+        }
+    }
+
+    fn zone_id(&self) -> String {
+        format!("airplay:{}", self.config.name)
+    }
+
+    /// Apply a metadata item
+    fn apply(&mut self, item: &MetadataItem) -> Change {
+        match (item.item_type.as_str(), item.code.as_str()) {
+            ("core", "minm" | "asar" | "asal") => {
+                let value = Some(item.text()).filter(|s| !s.is_empty());
+                let in_bundle = self.pending.is_some();
+                let track = self.pending.as_mut().unwrap_or(&mut self.track);
+                match item.code.as_str() {
+                    "minm" => track.title = value,
+                    "asar" => track.artist = value,
+                    _ => track.album = value,
+                }
+                // Outside a bundle (older shairport-sync) every field is live
+                if in_bundle {
+                    Change::None
+                } else {
+                    Change::NowPlaying
+                }
+            }
+            ("ssnc", "mdst") => {
+                self.pending = Some(TrackInfo::default());
+                Change::None
+            }
+            ("ssnc", "mden") => match self.pending.take() {
+                Some(track) if track != self.track => {
+                    self.track = track;
+                    Change::NowPlaying
+                }
+                _ => Change::None,
+            },
+            ("ssnc", "pbeg" | "prsm" | "pres") => self.set_state("playing"),
+            ("ssnc", "pfls" | "paus") => self.set_state("paused"),
+            ("ssnc", "pend") => {
+                self.track = TrackInfo::default();
+                self.cover = None;
+                self.progress = None;
+                self.client_name = None;
+                self.set_state("stopped");
+                Change::Stopped
+            }
+            ("ssnc", "snam") => {
+                self.client_name = Some(item.text()).filter(|s| !s.is_empty());
+                Change::None
+            }
+            ("ssnc", "PICT") => {
+                self.cover = image_content_type(&item.data).map(|content_type| {
+                    let mut hasher = DefaultHasher::new();
+                    item.data.hash(&mut hasher);
+                    Cover {
+                        content_type: content_type.to_string(),
+                        data: item.data.clone(),
+                        key: format!("{}:{:016x}", self.zone_id(), hasher.finish()),
+                    }
+                });
+                Change::NowPlaying
+            }
+            ("ssnc", "prgr") => {
+                let text = item.text();
+                let parts: Vec<u32> = text.split('/').filter_map(|p| p.parse().ok()).collect();
+                if let [start, current, end] = parts[..] {
+                    self.progress = Some(Progress {
+                        start,
+                        current,
+                        end,
+                        received: Instant::now(),
+                    });
+                    Change::Seek
+                } else {
+                    Change::None
+                }
+            }
+            ("ssnc", "pvol") => {
+                let volume = item
+                    .text()
+                    .split(',')
+                    .next()
+                    .and_then(|v| v.trim().parse::<f32>().ok());
+                if volume.is_none() || volume == self.volume {
+                    return Change::None;
+                }
+                self.volume = volume;
+                Change::Volume
+            }
+            _ => Change::None,
+        }
+    }
+
+    fn set_state(&mut self, state: &str) -> Change {
+        if self.state == state {
+            return Change::None;
+        }
+        // Freeze progress at the current position when playback stops moving
+        if self.state == "playing" {
+            if let (Some(position), Some(progress)) = (self.position(), self.progress.as_mut()) {
+                progress.current = progress.start.wrapping_add((position * RTP_RATE) as u32);
+                progress.received = Instant::now();
+            }
+        }
+        self.state = state.to_string();
+        if let Some(progress) = self.progress.as_mut() {
+            progress.received = Instant::now();
+        }
+        Change::State
+    }
+
+    /// Track length in seconds
+    fn length(&self) -> Option<f64> {
+        let p = self.progress.as_ref()?;
+        Some(p.end.wrapping_sub(p.start) as f64 / RTP_RATE)
+    }
+
+    /// Current position in seconds, extrapolated while playing
+    fn position(&self) -> Option<f64> {
+        let p = self.progress.as_ref()?;
+        let mut position = p.current.wrapping_sub(p.start) as f64 / RTP_RATE;
+        if self.state == "playing" {
+            position += p.received.elapsed().as_secs_f64();
+        }
+        Some(match self.length() {
+            Some(length) => position.min(length),
+            None => position,
+        })
+    }
+
+    fn is_muted(&self) -> bool {
+        self.volume.is_some_and(|v| v <= AIRPLAY_VOLUME_MUTED)
+    }
+
+    fn to_zone(&self) -> Zone {
+        Zone {
+            zone_id: self.zone_id(),
+            zone_name: self.config.name.clone(),
+            state: PlaybackState::from(self.state.as_str()),
+            volume_control: self.volume.map(|v| VolumeControl {
+                value: v.max(AIRPLAY_VOLUME_MIN),
+                min: AIRPLAY_VOLUME_MIN,
+                max: 0.0,
+                step: 1.0,
+                is_muted: self.is_muted(),
+                scale: VolumeScale::Decibel,
+                output_id: Some(self.zone_id()),
+            }),
+            now_playing: self
+                .track
+                .title
+                .as_ref()
+                .map(|title| crate::bus::NowPlaying {
+                    title: title.clone(),
+                    artist: self.track.artist.clone().unwrap_or_default(),
+                    album: self.track.album.clone().unwrap_or_default(),
+                    image_key: self.cover.as_ref().map(|c| c.key.clone()),
+                    seek_position: self.position(),
+                    duration: self.length(),
+                    metadata: None,
+                }),
+            source: "airplay".to_string(),
+            is_controllable: self.config.remote.is_some(),
+            is_seekable: false,
+            last_updated: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+}
+
+/// Detect cover art format from its magic bytes
+fn image_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(&[0x89, b'P', b'N', b'G']) {
+        Some("image/png")
+    } else {
+        None
+    }
+}
+
+/// Map a surface action to shairport-sync remote commands
+///
+/// Returns the MQTT remote payload and the D-Bus RemoteControl method.
+pub fn remote_command(action: &str) -> Option<(&'static str, &'static str)> {
+    match action {
+        "play" => Some(("play", "Play")),
+        "pause" => Some(("pause", "Pause")),
+        "play_pause" | "playpause" => Some(("playpause", "PlayPause")),
+        "stop" => Some(("stop", "Stop")),
+        "next" => Some(("nextitem", "Next")),
+        "previous" | "prev" => Some(("previtem", "Previous")),
+        "vol_up" | "volume_up" => Some(("volumeup", "VolumeUp")),
+        "vol_down" | "volume_down" => Some(("volumedown", "VolumeDown")),
+        "mute_toggle" | "toggle_mute" => Some(("mutetoggle", "ToggleMute")),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// API types
+// =============================================================================
+
+/// AirPlay adapter status
+#[derive(Debug, Clone, Serialize)]
+pub struct AirPlayStatus {
+    pub running: bool,
+    pub receiver_count: usize,
+    pub receivers: Vec<AirPlayReceiverSummary>,
+}
+
+/// Receiver summary for status response
+#[derive(Debug, Clone, Serialize)]
+pub struct AirPlayReceiverSummary {
+    pub name: String,
+    pub state: String,
+    /// Name of the device currently streaming
+    pub client_name: Option<String>,
+    pub has_remote: bool,
+}
+
+/// Zone info for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct AirPlayZone {
+    pub zone_id: String,
+    pub zone_name: String,
+    pub state: String,
+    pub client_name: Option<String>,
+    pub is_controllable: bool,
+}
+
+/// Now playing info from an AirPlay receiver
+#[derive(Debug, Clone, Serialize)]
+pub struct AirPlayNowPlaying {
+    pub zone_id: String,
+    pub line1: String,
+    pub line2: String,
+    pub line3: String,
+    pub is_playing: bool,
+    pub volume: Option<f32>,
+    pub volume_min: f32,
+    pub volume_max: f32,
+    pub seek_position: Option<i64>,
+    pub length: Option<u32>,
+    pub image_key: Option<String>,
+}
+
+/// Cover art bytes
+#[derive(Debug, Clone)]
+pub struct AirPlayImage {
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+// =============================================================================
+// Adapter
+// =============================================================================
+
+#[derive(Default)]
+struct AirPlayState {
+    receivers: HashMap<String, Receiver>,
+    configs: Vec<ShairportReceiverConfig>,
+    running: bool,
+}
+
+/// AirPlay adapter for shairport-sync receivers
+pub struct AirPlayAdapter {
+    state: Arc<RwLock<AirPlayState>>,
+    bus: SharedBus,
+    /// Wrapped in RwLock to allow creating fresh token on restart
+    shutdown: Arc<RwLock<CancellationToken>>,
+}
+
+impl AirPlayAdapter {
+    pub fn new(bus: SharedBus) -> Self {
+        let adapter = Self {
+            state: Arc::new(RwLock::new(AirPlayState::default())),
+            bus,
+            shutdown: Arc::new(RwLock::new(CancellationToken::new())),
+        };
+        // Load saved config synchronously at startup
+        adapter.load_config_sync();
+        adapter
+    }
+
+    /// Load config from disk (sync, for startup)
+    fn load_config_sync(&self) {
+        let path = config_path();
+        if path.exists() {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => match serde_json::from_str::<SavedAirPlayConfig>(&content) {
+                    Ok(saved) => {
+                        // Use try_write to avoid async in sync context
+                        if let Ok(mut state) = self.state.try_write() {
+                            tracing::info!(
+                                "Loaded AirPlay config from disk ({} receivers)",
+                                saved.receivers.len()
+                            );
+                            state.configs = saved.receivers;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to parse AirPlay config: {}", e),
+                },
+                Err(e) => tracing::warn!("Failed to read AirPlay config: {}", e),
+            }
+        }
+    }
+
+    /// Save config to disk
+    async fn save_config(&self) {
+        let saved = SavedAirPlayConfig {
+            receivers: self.state.read().await.configs.clone(),
+        };
+        let path = config_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(&saved) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save AirPlay config: {}", e);
+                } else {
+                    tracing::info!("Saved AirPlay config to disk");
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize AirPlay config: {}", e),
+        }
+    }
+
+    /// Configure receivers (takes effect on next start)
+    pub async fn configure(&self, receivers: Vec<ShairportReceiverConfig>) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for receiver in &receivers {
+            if receiver.name.trim().is_empty() {
+                return Err(anyhow!("Receiver name must not be empty"));
+            }
+            if !names.insert(receiver.name.as_str()) {
+                return Err(anyhow!("Duplicate receiver name: {}", receiver.name));
+            }
+            if receiver.pipe.is_none() && receiver.udp_port.is_none() {
+                return Err(anyhow!(
+                    "Receiver {} needs a metadata pipe or UDP port",
+                    receiver.name
+                ));
+            }
+        }
+
+        self.state.write().await.configs = receivers;
+        self.save_config().await;
+        Ok(())
+    }
+
+    /// Get configured receivers
+    pub async fn get_config(&self) -> Vec<ShairportReceiverConfig> {
+        self.state.read().await.configs.clone()
+    }
+
+    /// Check if any receiver is configured
+    pub async fn is_configured(&self) -> bool {
+        !self.state.read().await.configs.is_empty()
+    }
+
+    /// Start reading metadata (internal - use Startable trait)
+    async fn start_internal(&self) -> Result<()> {
+        let configs = {
+            let mut state = self.state.write().await;
+            if state.running {
+                return Ok(());
+            }
+            state.running = true;
+            state.receivers = state
+                .configs
+                .iter()
+                .map(|c| (c.name.clone(), Receiver::new(c.clone())))
+                .collect();
+            state.configs.clone()
+        };
+
+        // Create fresh cancellation token for this run (previous token may be cancelled)
+        let shutdown = {
+            let mut token = self.shutdown.write().await;
+            *token = CancellationToken::new();
+            token.clone()
+        };
+
+        for config in configs {
+            {
+                let state = self.state.read().await;
+                if let Some(receiver) = state.receivers.get(&config.name) {
+                    self.bus.publish(BusEvent::ZoneDiscovered {
+                        zone: receiver.to_zone(),
+                    });
+                }
+            }
+
+            if let Some(pipe) = config.pipe.clone() {
+                let state = self.state.clone();
+                let bus = self.bus.clone();
+                let shutdown = shutdown.clone();
+                let name = config.name.clone();
+                tokio::spawn(async move {
+                    Self::pipe_loop(state, bus, name, pipe, shutdown).await;
+                });
+            }
+
+            if let Some(port) = config.udp_port {
+                let state = self.state.clone();
+                let bus = self.bus.clone();
+                let shutdown = shutdown.clone();
+                let name = config.name.clone();
+                tokio::spawn(async move {
+                    Self::udp_loop(state, bus, name, port, shutdown).await;
+                });
+            }
+        }
+
+        tracing::info!("AirPlay adapter started");
+        Ok(())
+    }
+
+    /// Read the metadata pipe, reopening it when the writer goes away
+    async fn pipe_loop(
+        state: Arc<RwLock<AirPlayState>>,
+        bus: SharedBus,
+        name: String,
+        path: String,
+        shutdown: CancellationToken,
+    ) {
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                result = Self::read_pipe(&state, &bus, &name, &path) => {
+                    match result {
+                        Ok(()) => tracing::debug!("AirPlay metadata pipe {} closed", path),
+                        Err(e) => tracing::warn!("AirPlay metadata pipe {}: {}", path, e),
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(RETRY_DELAY) => {}
+            }
+        }
+
+        tracing::info!("AirPlay pipe reader for {} stopped", name);
+    }
+
+    async fn read_pipe(
+        state: &Arc<RwLock<AirPlayState>>,
+        bus: &SharedBus,
+        name: &str,
+        path: &str,
+    ) -> Result<()> {
+        let mut options = tokio::net::unix::pipe::OpenOptions::new();
+        // Keep our own write end open so we don't see EOF between sessions
+        #[cfg(target_os = "linux")]
+        options.read_write(true);
+        let mut pipe = options.open_receiver(path)?;
+
+        let mut parser = PipeParser::new();
+        let mut buf = vec![0u8; 64 * 1024];
+        // Bytes of an incomplete UTF-8 sequence carried over between reads
+        let mut carry = Vec::new();
+        loop {
+            let n = pipe.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            carry.extend_from_slice(&buf[..n]);
+            let valid = match std::str::from_utf8(&carry) {
+                Ok(s) => s.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            let text = String::from_utf8_lossy(&carry[..valid]).into_owned();
+            carry.drain(..valid);
+            if carry.len() > 4 {
+                carry.clear();
+            }
+
+            let items = parser.push(&text);
+            Self::apply_items(state, bus, name, &items).await;
+        }
+    }
+
+    /// Receive the UDP metadata stream
+    async fn udp_loop(
+        state: Arc<RwLock<AirPlayState>>,
+        bus: SharedBus,
+        name: String,
+        port: u16,
+        shutdown: CancellationToken,
+    ) {
+        let socket = match UdpSocket::bind(("0.0.0.0", port)).await {
+            Ok(socket) => socket,
+            Err(e) => {
+                tracing::error!("AirPlay metadata port {} unavailable: {}", port, e);
+                return;
+            }
+        };
+
+        let mut reassembler = UdpReassembler::new();
+        let mut buf = vec![0u8; 65536];
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                result = socket.recv_from(&mut buf) => {
+                    match result {
+                        Ok((n, _)) => {
+                            if let Some(item) = reassembler.push(&buf[..n]) {
+                                Self::apply_items(&state, &bus, &name, &[item]).await;
+                            }
+                        }
+                        Err(e) => tracing::debug!("AirPlay metadata recv failed: {}", e),
+                    }
+                }
+            }
+        }
+
+        tracing::info!("AirPlay UDP reader for {} stopped", name);
+    }
+
+    /// Apply metadata items to a receiver and publish what changed
+    async fn apply_items(
+        state: &Arc<RwLock<AirPlayState>>,
+        bus: &SharedBus,
+        name: &str,
+        items: &[MetadataItem],
+    ) {
+        let mut s = state.write().await;
+        let Some(receiver) = s.receivers.get_mut(name) else {
+            return;
+        };
+
+        for item in items {
+            let change = receiver.apply(item);
+            if matches!(change, Change::State | Change::Stopped) {
+                bus.publish(BusEvent::ZoneUpdated {
+                    zone_id: receiver.zone_id(),
+                    display_name: receiver.config.name.clone(),
+                    state: receiver.state.clone(),
+                });
+            }
+            if matches!(change, Change::NowPlaying | Change::Stopped) {
+                bus.publish(BusEvent::NowPlayingChanged {
+                    zone_id: receiver.zone_id(),
+                    title: receiver.track.title.clone(),
+                    artist: receiver.track.artist.clone(),
+                    album: receiver.track.album.clone(),
+                    image_key: receiver.cover.as_ref().map(|c| c.key.clone()),
+                });
+            }
+            if change == Change::Volume {
+                if let Some(vc) = receiver.to_zone().volume_control {
+                    bus.publish(BusEvent::VolumeChanged {
+                        output_id: receiver.zone_id(),
+                        value: vc.value,
+                        is_muted: vc.is_muted,
+                    });
+                }
+            }
+            if change == Change::Seek {
+                if let Some(position) = receiver.position() {
+                    bus.publish(BusEvent::SeekPositionChanged {
+                        zone_id: receiver.zone_id(),
+                        position: position as i64,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Stop reading metadata (internal - use Startable trait)
+    async fn stop_internal(&self) {
+        // Cancel background tasks first
+        self.shutdown.read().await.cancel();
+
+        let mut state = self.state.write().await;
+        state.running = false;
+        state.receivers.clear();
+        tracing::info!("AirPlay adapter stopped");
+    }
+
+    /// Get adapter status
+    pub async fn get_status(&self) -> AirPlayStatus {
+        let state = self.state.read().await;
+        let mut receivers: Vec<_> = state
+            .receivers
+            .values()
+            .map(|r| AirPlayReceiverSummary {
+                name: r.config.name.clone(),
+                state: r.state.clone(),
+                client_name: r.client_name.clone(),
+                has_remote: r.config.remote.is_some(),
+            })
+            .collect();
+        receivers.sort_by(|a, b| a.name.cmp(&b.name));
+        AirPlayStatus {
+            running: state.running,
+            receiver_count: receivers.len(),
+            receivers,
+        }
+    }
+
+    /// Get all receivers as zones
+    pub async fn get_zones(&self) -> Vec<AirPlayZone> {
+        let state = self.state.read().await;
+        let mut zones: Vec<_> = state
+            .receivers
+            .values()
+            .map(|r| AirPlayZone {
+                zone_id: r.config.name.clone(),
+                zone_name: r.config.name.clone(),
+                state: r.state.clone(),
+                client_name: r.client_name.clone(),
+                is_controllable: r.config.remote.is_some(),
+            })
+            .collect();
+        zones.sort_by(|a, b| a.zone_name.cmp(&b.zone_name));
+        zones
+    }
+
+    /// Get now playing info for a receiver
+    pub async fn get_now_playing(&self, name: &str) -> Option<AirPlayNowPlaying> {
+        let state = self.state.read().await;
+        let receiver = state.receivers.get(name)?;
+
+        // Show the streaming device while metadata hasn't arrived yet
+        let line1 = match (&receiver.track.title, &receiver.client_name) {
+            (Some(title), _) => title.clone(),
+            (None, Some(client)) if receiver.state != "stopped" => client.clone(),
+            _ => receiver.config.name.clone(),
+        };
+
+        Some(AirPlayNowPlaying {
+            zone_id: name.to_string(),
+            line1,
+            line2: receiver.track.artist.clone().unwrap_or_default(),
+            line3: receiver.track.album.clone().unwrap_or_default(),
+            is_playing: receiver.state == "playing",
+            volume: receiver.volume.map(|v| v.max(AIRPLAY_VOLUME_MIN)),
+            volume_min: AIRPLAY_VOLUME_MIN,
+            volume_max: 0.0,
+            seek_position: receiver.position().map(|p| p as i64),
+            length: receiver.length().map(|l| l as u32),
+            image_key: receiver.cover.as_ref().map(|c| c.key.clone()),
+        })
+    }
+
+    /// Get the current cover art of a receiver
+    pub async fn get_image(&self, name: &str) -> Option<AirPlayImage> {
+        let state = self.state.read().await;
+        let cover = state.receivers.get(name)?.cover.as_ref()?;
+        Some(AirPlayImage {
+            content_type: cover.content_type.clone(),
+            data: cover.data.clone(),
+        })
+    }
+
+    /// Send a playback command through the receiver's remote interface
+    pub async fn control(&self, name: &str, action: &str) -> Result<()> {
+        let remote = {
+            let state = self.state.read().await;
+            let receiver = state
+                .receivers
+                .get(name)
+                .ok_or_else(|| anyhow!("AirPlay receiver not found: {}", name))?;
+            receiver
+                .config
+                .remote
+                .clone()
+                .ok_or_else(|| anyhow!("AirPlay receiver {} has no remote control", name))?
+        };
+        let (mqtt_command, dbus_method) =
+            remote_command(action).ok_or_else(|| anyhow!("Unknown action: {}", action))?;
+
+        match remote {
+            RemoteConfig::Mqtt { host, port, topic } => {
+                send_mqtt_command(&host, port.unwrap_or(1883), &topic, mqtt_command).await
+            }
+            RemoteConfig::Dbus { session } => send_dbus_command(session, dbus_method).await,
+        }
+    }
+}
+
+/// Publish a command to shairport-sync's MQTT remote topic
+async fn send_mqtt_command(host: &str, port: u16, topic: &str, command: &str) -> Result<()> {
+    use rumqttc::{AsyncClient, Event, MqttOptions, Outgoing, QoS};
+
+    // Commands can overlap; a broker drops a client whose ID is reused
+    static NEXT_CLIENT: AtomicU64 = AtomicU64::new(0);
+    let client_id = format!(
+        "uhc-airplay-{}-{}",
+        std::process::id(),
+        NEXT_CLIENT.fetch_add(1, Ordering::Relaxed)
+    );
+    let mut options = MqttOptions::new(client_id, host, port);
+    options.set_keep_alive(Duration::from_secs(5));
+    let (client, mut eventloop) = AsyncClient::new(options, 4);
+
+    client
+        .publish(
+            format!("{}/remote", topic.trim_end_matches('/')),
+            QoS::AtLeastOnce,
+            false,
+            command.as_bytes().to_vec(),
+        )
+        .await?;
+
+    // Drive the connection until the broker acknowledges the publish
+    let delivered = tokio::time::timeout(REMOTE_TIMEOUT, async {
+        loop {
+            match eventloop.poll().await? {
+                Event::Incoming(rumqttc::Packet::PubAck(_)) => return Ok::<_, anyhow::Error>(()),
+                Event::Outgoing(Outgoing::Disconnect) => {
+                    return Err(anyhow!("MQTT connection closed"))
+                }
+                _ => {}
+            }
+        }
+    })
+    .await
+    .map_err(|_| anyhow!("MQTT broker {}:{} did not respond", host, port))?;
+
+    let _ = client.disconnect().await;
+    delivered
+}
+
+/// Call a RemoteControl method on shairport-sync's D-Bus interface
+async fn send_dbus_command(session: bool, method: &str) -> Result<()> {
+    let output = tokio::time::timeout(
+        REMOTE_TIMEOUT,
+        tokio::process::Command::new("dbus-send")
+            .arg(if session { "--session" } else { "--system" })
+            .arg("--print-reply")
+            .arg(format!("--dest={}", DBUS_DEST))
+            .arg(DBUS_PATH)
+            .arg(format!("{}.{}", DBUS_REMOTE_INTERFACE, method))
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow!("dbus-send timed out"))??;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "D-Bus {} failed: {}",
+            method,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+// Startable trait implementation via macro
+crate::impl_startable!(AirPlayAdapter, "airplay", is_configured);
@@ -0,0 +1,105 @@
+//! Source-independent browse model
+//!
+//! Mirrors the shape of Roon's browse service so clients can navigate any
+//! source's library the same way: a browse request returns the list being
+//! shown plus a page of its items. Items carry an opaque `item_key` that is
+//! passed back to browse into them or to play them.
+
+use serde::{Deserialize, Serialize};
+
+/// How a client should treat an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowseItemHint {
+    /// Playable leaf (track, station)
+    Action,
+    /// Playable container that can also be browsed into (album, playlist)
+    ActionList,
+    /// Navigable list
+    List,
+    /// Non-selectable section header
+    Header,
+}
+
+/// Prompt for items that need user input (e.g. search)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowseInputPrompt {
+    pub prompt: String,
+    pub action: String,
+}
+
+/// A single entry in a browse list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowseItem {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<BrowseItemHint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_prompt: Option<BrowseInputPrompt>,
+}
+
+impl BrowseItem {
+    pub fn new(
+        title: impl Into<String>,
+        item_key: impl Into<String>,
+        hint: BrowseItemHint,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            image_key: None,
+            item_key: Some(item_key.into()),
+            hint: Some(hint),
+            input_prompt: None,
+        }
+    }
+
+    pub fn with_subtitle(mut self, subtitle: Option<String>) -> Self {
+        self.subtitle = subtitle.filter(|s| !s.is_empty());
+        self
+    }
+
+    pub fn with_image_key(mut self, image_key: Option<String>) -> Self {
+        self.image_key = image_key.filter(|s| !s.is_empty());
+        self
+    }
+}
+
+/// The list being browsed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowseList {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_key: Option<String>,
+    /// Total number of items (items are returned a page at a time)
+    pub count: usize,
+    /// Depth in the hierarchy (0 = root)
+    pub level: u32,
+}
+
+/// A page of a browse list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowseResult {
+    pub list: BrowseList,
+    pub items: Vec<BrowseItem>,
+    pub offset: usize,
+}
+
+/// What to do with a played item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayMode {
+    /// Replace the queue and start playing
+    #[default]
+    Play,
+    /// Append to the queue
+    Add,
+}
@@ -0,0 +1,213 @@
+//! DIDL-Lite metadata (UPnP ContentDirectory / AVTransport)
+//!
+//! Media servers describe browse results as DIDL-Lite documents, and renderers
+//! take the same format as metadata alongside a URI to play. Only the fields
+//! a control point needs are modelled.
+
+use anyhow::{anyhow, Result};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+/// A playable resource of an item (`<res>`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DidlResource {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_info: Option<String>,
+    /// Duration as H:MM:SS(.F)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+}
+
+/// A DIDL-Lite item or container
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DidlObject {
+    pub id: String,
+    pub parent_id: String,
+    pub is_container: bool,
+    pub title: String,
+    /// upnp:class, e.g. `object.item.audioItem.musicTrack`
+    pub class: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album_art_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub child_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<DidlResource>,
+}
+
+impl DidlObject {
+    /// First resource URI (what a renderer is given to play)
+    pub fn uri(&self) -> Option<&str> {
+        self.resources.first().map(|r| r.uri.as_str())
+    }
+
+    /// Whether the object is an audio track (as opposed to e.g. a photo)
+    pub fn is_audio_item(&self) -> bool {
+        !self.is_container && self.class.starts_with("object.item.audioItem")
+    }
+
+    /// Serialize as a single-object DIDL-Lite document (renderer metadata)
+    pub fn to_didl_lite(&self) -> String {
+        let tag = if self.is_container {
+            "container"
+        } else {
+            "item"
+        };
+        let mut xml = String::from(
+            r#"<DIDL-Lite xmlns="urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:upnp="urn:schemas-upnp-org:metadata-1-0/upnp/">"#,
+        );
+        xml.push_str(&format!(
+            r#"<{} id="{}" parentID="{}" restricted="1">"#,
+            tag,
+            escape(&self.id),
+            escape(&self.parent_id)
+        ));
+        xml.push_str(&format!("<dc:title>{}</dc:title>", escape(&self.title)));
+        if let Some(ref artist) = self.artist {
+            xml.push_str(&format!("<upnp:artist>{}</upnp:artist>", escape(artist)));
+        }
+        if let Some(ref album) = self.album {
+            xml.push_str(&format!("<upnp:album>{}</upnp:album>", escape(album)));
+        }
+        if let Some(ref art) = self.album_art_uri {
+            xml.push_str(&format!(
+                "<upnp:albumArtURI>{}</upnp:albumArtURI>",
+                escape(art)
+            ));
+        }
+        xml.push_str(&format!("<upnp:class>{}</upnp:class>", escape(&self.class)));
+        for res in &self.resources {
+            xml.push_str("<res");
+            if let Some(ref info) = res.protocol_info {
+                xml.push_str(&format!(r#" protocolInfo="{}""#, escape(info)));
+            }
+            if let Some(ref duration) = res.duration {
+                xml.push_str(&format!(r#" duration="{}""#, escape(duration)));
+            }
+            xml.push_str(&format!(">{}</res>", escape(&res.uri)));
+        }
+        xml.push_str(&format!("</{}></DIDL-Lite>", tag));
+        xml
+    }
+}
+
+/// Which element's text is being read
+enum Field {
+    Title,
+    Class,
+    Artist,
+    Album,
+    AlbumArt,
+    Res,
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+fn start_object(element: &BytesStart, is_container: bool) -> DidlObject {
+    DidlObject {
+        id: attribute(element, "id").unwrap_or_default(),
+        parent_id: attribute(element, "parentID").unwrap_or_default(),
+        is_container,
+        child_count: attribute(element, "childCount").and_then(|c| c.parse().ok()),
+        ..Default::default()
+    }
+}
+
+/// Parse the items and containers of a DIDL-Lite document
+pub fn parse_didl(xml: &str) -> Result<Vec<DidlObject>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut objects = Vec::new();
+    let mut current: Option<DidlObject> = None;
+    let mut field: Option<Field> = None;
+
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| anyhow!("Invalid DIDL-Lite: {}", e))?
+        {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"item" => current = Some(start_object(&e, false)),
+                b"container" => current = Some(start_object(&e, true)),
+                name => {
+                    let Some(object) = current.as_mut() else {
+                        continue;
+                    };
+                    field = match name {
+                        b"title" => Some(Field::Title),
+                        b"class" => Some(Field::Class),
+                        // upnp:artist and dc:creator; the first one wins
+                        b"artist" | b"creator" if object.artist.is_none() => Some(Field::Artist),
+                        b"album" => Some(Field::Album),
+                        b"albumArtURI" if object.album_art_uri.is_none() => Some(Field::AlbumArt),
+                        b"res" => {
+                            object.resources.push(DidlResource {
+                                uri: String::new(),
+                                protocol_info: attribute(&e, "protocolInfo"),
+                                duration: attribute(&e, "duration"),
+                            });
+                            Some(Field::Res)
+                        }
+                        _ => None,
+                    };
+                }
+            },
+            Event::Empty(e) => match e.local_name().as_ref() {
+                b"item" => objects.push(start_object(&e, false)),
+                b"container" => objects.push(start_object(&e, true)),
+                _ => {}
+            },
+            Event::Text(t) => {
+                if let (Some(object), Some(f)) = (current.as_mut(), field.as_ref()) {
+                    let text = t
+                        .unescape()
+                        .map_err(|e| anyhow!("Invalid DIDL-Lite text: {}", e))?
+                        .into_owned();
+                    match f {
+                        Field::Title => object.title = text,
+                        Field::Class => object.class = text,
+                        Field::Artist => object.artist = Some(text),
+                        Field::Album => object.album = Some(text),
+                        Field::AlbumArt => object.album_art_uri = Some(text),
+                        Field::Res => {
+                            if let Some(res) = object.resources.last_mut() {
+                                res.uri = text;
+                            }
+                        }
+                    }
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"item" | b"container" => {
+                    if let Some(object) = current.take() {
+                        objects.push(object);
+                    }
+                }
+                _ => field = None,
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    // Drop resources that never got a URI
+    for object in &mut objects {
+        object.resources.retain(|r| !r.uri.is_empty());
+    }
+    Ok(objects)
+}
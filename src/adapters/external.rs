@@ -0,0 +1,955 @@
+//! External adapters - audio sources implemented as separate executables
+//!
+//! Each configured adapter is a subprocess that talks newline-delimited JSON
+//! on stdin/stdout, so niche gear can be integrated in any language without
+//! touching this crate. The adapter is registered with the coordinator as
+//! `external:<name>`, supervised (restarted with backoff when it exits) and
+//! its zones are published on the bus as `external:<name>:<zone_id>`.
+//!
+//! Adapter → host (stdout, one object per line):
+//! ```text
+//! {"type":"hello","name":"my-amp","version":"1.0"}
+//! {"type":"zone","zone_id":"k1","zone_name":"Kitchen","state":"stopped"}
+//! {"type":"state","zone_id":"k1","state":"playing"}
+//! {"type":"now_playing","zone_id":"k1","title":"So What","artist":"Miles Davis"}
+//! {"type":"seek","zone_id":"k1","position":42}
+//! {"type":"volume","zone_id":"k1","value":30,"is_muted":false}
+//! {"type":"zone_removed","zone_id":"k1"}
+//! {"type":"command_result","request_id":"1","success":true}
+//! {"type":"log","level":"info","message":"connected"}
+//! ```
+//!
+//! Host → adapter (stdin):
+//! ```text
+//! {"type":"command","request_id":"1","zone_id":"k1","command":{"action":"Play"}}
+//! {"type":"shutdown"}
+//! ```
+//!
+//! `command` uses the bus [`Command`] encoding. Every command must be answered
+//! with a `command_result` carrying the same `request_id`. Anything written to
+//! stderr is logged.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command as ProcessCommand};
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use crate::bus::{
+    BusEvent, Command, CommandResponse, NowPlaying, PlaybackState, SharedBus, VolumeControl, Zone,
+};
+use crate::config::get_config_dir;
+use crate::coordinator::AdapterCoordinator;
+
+const EXTERNAL_CONFIG_FILE: &str = "external-adapters.json";
+
+/// Prefix for external adapter names and zone IDs
+pub const EXTERNAL_PREFIX: &str = "external";
+
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+const RESTART_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(60);
+/// A process that ran this long resets the restart backoff
+const STABLE_RUN: Duration = Duration::from_secs(30);
+
+/// Coordinator name for an external adapter (e.g. "external:my-amp")
+pub fn adapter_prefix(name: &str) -> String {
+    format!("{}:{}", EXTERNAL_PREFIX, name)
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A configured external adapter (persisted)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExternalAdapterConfig {
+    /// Adapter name (used in zone IDs, must not contain ':')
+    pub name: String,
+    /// Executable to run
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment variables for the subprocess
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn config_path() -> PathBuf {
+    get_config_dir().join(EXTERNAL_CONFIG_FILE)
+}
+
+/// Load external adapter configs from disk
+pub fn load_external_adapter_configs() -> Vec<ExternalAdapterConfig> {
+    let path = config_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(configs) => configs,
+            Err(e) => {
+                tracing::warn!("Failed to parse external adapters config: {}", e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            tracing::warn!("Failed to read external adapters config: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Save external adapter configs to disk
+pub fn save_external_adapter_configs(configs: &[ExternalAdapterConfig]) -> bool {
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    match serde_json::to_string_pretty(configs) {
+        Ok(json) => match std::fs::write(&path, json) {
+            Ok(()) => {
+                tracing::info!(
+                    "Saved external adapters config ({} adapters)",
+                    configs.len()
+                );
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to save external adapters config: {}", e);
+                false
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to serialize external adapters config: {}", e);
+            false
+        }
+    }
+}
+
+// =============================================================================
+// Protocol
+// =============================================================================
+
+/// Message written by an external adapter to stdout
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdapterMessage {
+    /// Optional greeting, logged on startup
+    Hello {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        version: Option<String>,
+    },
+    /// Announce a zone (or replace its name, state, capabilities and volume)
+    Zone {
+        zone_id: String,
+        zone_name: String,
+        #[serde(default)]
+        state: PlaybackState,
+        #[serde(default = "default_enabled")]
+        is_controllable: bool,
+        #[serde(default)]
+        is_seekable: bool,
+        #[serde(default)]
+        volume: Option<VolumeControl>,
+    },
+    /// A zone went away
+    ZoneRemoved { zone_id: String },
+    /// Playback state changed
+    State {
+        zone_id: String,
+        state: PlaybackState,
+    },
+    /// Track changed
+    NowPlaying {
+        zone_id: String,
+        #[serde(default)]
+        title: String,
+        #[serde(default)]
+        artist: String,
+        #[serde(default)]
+        album: String,
+        #[serde(default)]
+        image_key: Option<String>,
+        /// Position in seconds
+        #[serde(default)]
+        seek_position: Option<f64>,
+        /// Duration in seconds
+        #[serde(default)]
+        duration: Option<f64>,
+    },
+    /// Playback position in seconds
+    Seek { zone_id: String, position: f64 },
+    /// Volume changed (in the scale announced with the zone)
+    Volume {
+        zone_id: String,
+        value: f32,
+        #[serde(default)]
+        is_muted: bool,
+    },
+    /// Answer to a host command
+    CommandResult {
+        request_id: String,
+        success: bool,
+        #[serde(default)]
+        error: Option<String>,
+    },
+    /// Log line forwarded to the host log
+    Log {
+        #[serde(default)]
+        level: Option<String>,
+        message: String,
+    },
+}
+
+/// Message written by the host to an external adapter's stdin
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    /// Execute a command for one of the adapter's zones
+    Command {
+        request_id: String,
+        zone_id: String,
+        command: Command,
+    },
+    /// The adapter is being stopped; exit promptly
+    Shutdown,
+}
+
+// =============================================================================
+// Runtime state
+// =============================================================================
+
+/// Where to deliver a command result
+enum Reply {
+    /// Waiting API caller
+    Caller(oneshot::Sender<Result<()>>),
+    /// Command that arrived on the bus (answered with CommandResult)
+    Bus {
+        zone_id: String,
+        command: Command,
+        request_id: Option<String>,
+    },
+}
+
+/// Command queued for the running subprocess
+struct QueuedCommand {
+    zone_id: String,
+    command: Command,
+    reply: oneshot::Sender<Result<()>>,
+}
+
+struct Instance {
+    config: ExternalAdapterConfig,
+    /// Zones by adapter-local zone_id
+    zones: HashMap<String, Zone>,
+    pid: Option<u32>,
+    restarts: u32,
+    last_error: Option<String>,
+    commands: Option<mpsc::Sender<QueuedCommand>>,
+}
+
+impl Instance {
+    fn new(config: ExternalAdapterConfig) -> Self {
+        Self {
+            config,
+            zones: HashMap::new(),
+            pid: None,
+            restarts: 0,
+            last_error: None,
+            commands: None,
+        }
+    }
+}
+
+type SharedInstances = Arc<RwLock<HashMap<String, Instance>>>;
+
+/// External adapter status for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalAdapterStatus {
+    #[serde(flatten)]
+    pub config: ExternalAdapterConfig,
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    pub zone_count: usize,
+}
+
+/// Split "external:<name>:<zone>" into (name, zone)
+fn split_zone_id(zone_id: &str) -> Option<(&str, &str)> {
+    zone_id
+        .strip_prefix(EXTERNAL_PREFIX)?
+        .strip_prefix(':')?
+        .split_once(':')
+}
+
+// =============================================================================
+// Manager
+// =============================================================================
+
+/// Manager for configured external adapters
+pub struct ExternalAdapterManager {
+    instances: SharedInstances,
+    /// Spawns and tracks the supervisor tasks (which get the bus from it)
+    coordinator: Arc<AdapterCoordinator>,
+}
+
+impl ExternalAdapterManager {
+    pub fn new(coordinator: Arc<AdapterCoordinator>) -> Self {
+        Self {
+            instances: Arc::new(RwLock::new(HashMap::new())),
+            coordinator,
+        }
+    }
+
+    /// Load adapters from config file (does not start them)
+    pub async fn load_from_config(&self) {
+        let mut instances = self.instances.write().await;
+        for config in load_external_adapter_configs() {
+            instances.insert(config.name.clone(), Instance::new(config));
+        }
+    }
+
+    async fn save_to_config(&self) {
+        let configs: Vec<_> = self
+            .list_adapters()
+            .await
+            .into_iter()
+            .map(|s| s.config)
+            .collect();
+        save_external_adapter_configs(&configs);
+    }
+
+    /// Register all adapters with the coordinator and start the enabled ones
+    pub async fn start_all(&self) {
+        let names: Vec<String> = self.instances.read().await.keys().cloned().collect();
+        for name in names {
+            if let Err(e) = self.start_adapter(&name).await {
+                tracing::warn!("Failed to start external adapter {}: {}", name, e);
+            }
+        }
+    }
+
+    async fn start_adapter(&self, name: &str) -> Result<()> {
+        let enabled = self
+            .instances
+            .read()
+            .await
+            .get(name)
+            .map(|i| i.config.enabled)
+            .ok_or_else(|| anyhow!("External adapter not found: {}", name))?;
+
+        let prefix = adapter_prefix(name);
+        self.coordinator.register(&prefix, enabled).await;
+
+        let instances = self.instances.clone();
+        let name = name.to_string();
+        self.coordinator
+            .start_adapter(&prefix, move |bus, cancel| {
+                supervise(name, instances, bus, cancel)
+            })
+            .await
+    }
+
+    /// Add or replace an adapter, persist it and (re)start it
+    pub async fn add_adapter(&self, config: ExternalAdapterConfig) -> Result<()> {
+        if config.name.trim().is_empty() {
+            return Err(anyhow!("Adapter name is required"));
+        }
+        if config.name.contains(':') {
+            return Err(anyhow!("Adapter name must not contain ':'"));
+        }
+        if config.command.trim().is_empty() {
+            return Err(anyhow!("Adapter command is required"));
+        }
+
+        let name = config.name.clone();
+        let prefix = adapter_prefix(&name);
+        if self.instances.read().await.contains_key(&name) {
+            self.coordinator.stop_adapter(&prefix).await?;
+        }
+        self.instances
+            .write()
+            .await
+            .insert(name.clone(), Instance::new(config));
+        self.save_to_config().await;
+        self.start_adapter(&name).await
+    }
+
+    /// Stop and remove an adapter
+    pub async fn remove_adapter(&self, name: &str) -> bool {
+        if !self.instances.read().await.contains_key(name) {
+            return false;
+        }
+        let prefix = adapter_prefix(name);
+        if let Err(e) = self.coordinator.stop_adapter(&prefix).await {
+            tracing::warn!("Failed to stop external adapter {}: {}", name, e);
+        }
+        self.coordinator.unregister(&prefix).await;
+        self.instances.write().await.remove(name);
+        self.save_to_config().await;
+        true
+    }
+
+    /// Stop all running adapters
+    pub async fn stop_all(&self) {
+        let names: Vec<String> = self.instances.read().await.keys().cloned().collect();
+        for name in names {
+            if let Err(e) = self.coordinator.stop_adapter(&adapter_prefix(&name)).await {
+                tracing::warn!("Failed to stop external adapter {}: {}", name, e);
+            }
+        }
+    }
+
+    /// List adapters with their runtime status (sorted by name)
+    pub async fn list_adapters(&self) -> Vec<ExternalAdapterStatus> {
+        let snapshot: Vec<_> = {
+            let instances = self.instances.read().await;
+            instances
+                .values()
+                .map(|i| {
+                    (
+                        i.config.clone(),
+                        i.pid,
+                        i.restarts,
+                        i.last_error.clone(),
+                        i.zones.len(),
+                    )
+                })
+                .collect()
+        };
+
+        let mut adapters = Vec::with_capacity(snapshot.len());
+        for (config, pid, restarts, last_error, zone_count) in snapshot {
+            let running = self
+                .coordinator
+                .is_running(&adapter_prefix(&config.name))
+                .await;
+            adapters.push(ExternalAdapterStatus {
+                config,
+                running,
+                pid,
+                restarts,
+                last_error,
+                zone_count,
+            });
+        }
+        adapters.sort_by(|a, b| a.config.name.cmp(&b.config.name));
+        adapters
+    }
+
+    /// Get all zones announced by external adapters (sorted by zone_id)
+    pub async fn get_zones(&self) -> Vec<Zone> {
+        let instances = self.instances.read().await;
+        let mut zones: Vec<_> = instances
+            .values()
+            .flat_map(|i| i.zones.values().cloned())
+            .collect();
+        zones.sort_by(|a, b| a.zone_id.cmp(&b.zone_id));
+        zones
+    }
+
+    /// Get a zone by its full zone_id ("external:<name>:<zone>")
+    pub async fn get_zone(&self, zone_id: &str) -> Option<Zone> {
+        let (name, local_id) = split_zone_id(zone_id)?;
+        let instances = self.instances.read().await;
+        instances.get(name)?.zones.get(local_id).cloned()
+    }
+
+    /// Send a command to the adapter owning `zone_id` and wait for its result
+    pub async fn send_command(&self, zone_id: &str, command: Command) -> Result<()> {
+        let (name, local_id) =
+            split_zone_id(zone_id).ok_or_else(|| anyhow!("Invalid zone_id: {}", zone_id))?;
+        let sender = {
+            let instances = self.instances.read().await;
+            let instance = instances
+                .get(name)
+                .ok_or_else(|| anyhow!("External adapter not found: {}", name))?;
+            if !instance.zones.contains_key(local_id) {
+                return Err(anyhow!("Zone not found: {}", zone_id));
+            }
+            instance
+                .commands
+                .clone()
+                .ok_or_else(|| anyhow!("External adapter {} is not running", name))?
+        };
+
+        let (reply, result) = oneshot::channel();
+        sender
+            .send(QueuedCommand {
+                zone_id: local_id.to_string(),
+                command,
+                reply,
+            })
+            .await
+            .map_err(|_| anyhow!("External adapter {} is not running", name))?;
+
+        match tokio::time::timeout(COMMAND_TIMEOUT, result).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(anyhow!("External adapter {} exited", name)),
+            Err(_) => Err(anyhow!("External adapter {} did not answer", name)),
+        }
+    }
+}
+
+// =============================================================================
+// Supervision
+// =============================================================================
+
+/// Run an adapter's subprocess until cancelled, restarting it when it exits
+async fn supervise(
+    name: String,
+    instances: SharedInstances,
+    bus: SharedBus,
+    cancel: CancellationToken,
+) {
+    let prefix = adapter_prefix(&name);
+    let mut bus_rx = bus.subscribe();
+    let mut backoff = RESTART_BACKOFF_MIN;
+
+    loop {
+        let Some(config) = instances.read().await.get(&name).map(|i| i.config.clone()) else {
+            break;
+        };
+
+        let started = Instant::now();
+        let result = run_process(&name, &config, &instances, &bus, &mut bus_rx, &cancel).await;
+
+        // Zones disappear with the process
+        let zone_ids: Vec<String> = {
+            let mut instances = instances.write().await;
+            match instances.get_mut(&name) {
+                Some(instance) => {
+                    instance.pid = None;
+                    instance.commands = None;
+                    if let Err(e) = &result {
+                        instance.restarts += 1;
+                        instance.last_error = Some(e.to_string());
+                    }
+                    instance.zones.drain().map(|(_, z)| z.zone_id).collect()
+                }
+                None => Vec::new(),
+            }
+        };
+        for zone_id in zone_ids {
+            bus.publish(BusEvent::ZoneRemoved { zone_id });
+        }
+
+        match result {
+            Ok(()) => break,
+            Err(e) => tracing::warn!(
+                "External adapter {} stopped: {} (restarting in {:?})",
+                name,
+                e,
+                backoff
+            ),
+        }
+
+        if started.elapsed() >= STABLE_RUN {
+            backoff = RESTART_BACKOFF_MIN;
+        }
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = wait_for_shutdown(&mut bus_rx) => break,
+            _ = tokio::time::sleep(backoff) => {}
+        }
+        backoff = (backoff * 2).min(RESTART_BACKOFF_MAX);
+    }
+
+    bus.publish(BusEvent::AdapterStopped { adapter: prefix });
+    tracing::info!("External adapter {} stopped", name);
+}
+
+/// Resolve when the bus announces shutdown (or closes)
+async fn wait_for_shutdown(bus_rx: &mut broadcast::Receiver<BusEvent>) {
+    loop {
+        match bus_rx.recv().await {
+            Ok(BusEvent::ShuttingDown { .. }) | Err(broadcast::error::RecvError::Closed) => return,
+            _ => {}
+        }
+    }
+}
+
+async fn send_message(stdin: &mut ChildStdin, message: &HostMessage) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    stdin.write_all(line.as_bytes()).await?;
+    stdin.flush().await?;
+    Ok(())
+}
+
+/// Run the subprocess once
+///
+/// Returns Ok when asked to stop, Err when the process exited or failed.
+async fn run_process(
+    name: &str,
+    config: &ExternalAdapterConfig,
+    instances: &SharedInstances,
+    bus: &SharedBus,
+    bus_rx: &mut broadcast::Receiver<BusEvent>,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let mut child = ProcessCommand::new(&config.command)
+        .args(&config.args)
+        .envs(&config.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run {}: {}", config.command, e))?;
+
+    let mut stdin = child.stdin.take().ok_or_else(|| anyhow!("No stdin"))?;
+    let mut stdout =
+        BufReader::new(child.stdout.take().ok_or_else(|| anyhow!("No stdout"))?).lines();
+    let mut stderr =
+        BufReader::new(child.stderr.take().ok_or_else(|| anyhow!("No stderr"))?).lines();
+    let mut stderr_open = true;
+
+    let (command_tx, mut command_rx) = mpsc::channel::<QueuedCommand>(16);
+    {
+        let mut instances = instances.write().await;
+        if let Some(instance) = instances.get_mut(name) {
+            instance.pid = child.id();
+            instance.commands = Some(command_tx);
+        }
+    }
+    tracing::info!(
+        "External adapter {} started (pid {})",
+        name,
+        child.id().unwrap_or_default()
+    );
+
+    let zone_prefix = format!("{}:", adapter_prefix(name));
+    let mut pending: HashMap<String, Reply> = HashMap::new();
+    let mut next_request_id: u64 = 0;
+
+    let result = loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break Ok(()),
+            line = stdout.next_line() => match line {
+                Ok(Some(line)) => {
+                    handle_line(name, &line, instances, bus, &mut pending).await;
+                }
+                Ok(None) => match tokio::time::timeout(SHUTDOWN_GRACE, child.wait()).await {
+                    Ok(Ok(status)) => break Err(anyhow!("process exited ({})", status)),
+                    _ => break Err(anyhow!("process closed stdout")),
+                },
+                Err(e) => break Err(anyhow!("failed to read stdout: {}", e)),
+            },
+            line = stderr.next_line(), if stderr_open => match line {
+                Ok(Some(line)) => tracing::info!("[{}] {}", name, line),
+                _ => stderr_open = false,
+            },
+            Some(queued) = command_rx.recv() => {
+                next_request_id += 1;
+                let request_id = next_request_id.to_string();
+                let message = HostMessage::Command {
+                    request_id: request_id.clone(),
+                    zone_id: queued.zone_id,
+                    command: queued.command,
+                };
+                if let Err(e) = send_message(&mut stdin, &message).await {
+                    let _ = queued.reply.send(Err(anyhow!("write failed: {}", e)));
+                    break Err(anyhow!("failed to write stdin: {}", e));
+                }
+                pending.insert(request_id, Reply::Caller(queued.reply));
+            }
+            event = bus_rx.recv() => match event {
+                Ok(BusEvent::CommandReceived { zone_id, command, request_id: bus_request_id }) => {
+                    let Some(local_id) = zone_id.strip_prefix(&zone_prefix) else {
+                        continue;
+                    };
+                    next_request_id += 1;
+                    let request_id = next_request_id.to_string();
+                    let message = HostMessage::Command {
+                        request_id: request_id.clone(),
+                        zone_id: local_id.to_string(),
+                        command: command.clone(),
+                    };
+                    if let Err(e) = send_message(&mut stdin, &message).await {
+                        break Err(anyhow!("failed to write stdin: {}", e));
+                    }
+                    pending.insert(
+                        request_id,
+                        Reply::Bus { zone_id, command, request_id: bus_request_id },
+                    );
+                }
+                Ok(BusEvent::ShuttingDown { .. }) | Err(broadcast::error::RecvError::Closed) => {
+                    break Ok(());
+                }
+                _ => {}
+            },
+        }
+    };
+
+    // Pending callers are answered by dropping their senders
+    drop(pending);
+    if result.is_ok() {
+        let _ = send_message(&mut stdin, &HostMessage::Shutdown).await;
+        drop(stdin);
+        stop_child(name, &mut child).await;
+    } else {
+        let _ = child.kill().await;
+    }
+    result
+}
+
+/// Give the process a moment to exit on its own, then kill it
+async fn stop_child(name: &str, child: &mut Child) {
+    match tokio::time::timeout(SHUTDOWN_GRACE, child.wait()).await {
+        Ok(_) => tracing::debug!("External adapter {} exited", name),
+        Err(_) => {
+            tracing::warn!("External adapter {} did not exit, killing", name);
+            let _ = child.kill().await;
+        }
+    }
+}
+
+/// Apply one line of adapter output
+async fn handle_line(
+    name: &str,
+    line: &str,
+    instances: &SharedInstances,
+    bus: &SharedBus,
+    pending: &mut HashMap<String, Reply>,
+) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    let message: AdapterMessage = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => {
+            tracing::warn!("External adapter {} sent invalid message: {}", name, e);
+            return;
+        }
+    };
+
+    let zone_id = |local_id: &str| format!("{}:{}", adapter_prefix(name), local_id);
+
+    match message {
+        AdapterMessage::Hello {
+            name: hello,
+            version,
+        } => {
+            tracing::info!(
+                "External adapter {} connected: {} {}",
+                name,
+                hello.unwrap_or_default(),
+                version.unwrap_or_default()
+            );
+        }
+        AdapterMessage::Log { level, message } => match level.as_deref() {
+            Some("error") => tracing::error!("[{}] {}", name, message),
+            Some("warn") | Some("warning") => tracing::warn!("[{}] {}", name, message),
+            Some("debug") => tracing::debug!("[{}] {}", name, message),
+            _ => tracing::info!("[{}] {}", name, message),
+        },
+        AdapterMessage::CommandResult {
+            request_id,
+            success,
+            error,
+        } => match pending.remove(&request_id) {
+            Some(Reply::Caller(reply)) => {
+                let _ = reply.send(if success {
+                    Ok(())
+                } else {
+                    Err(anyhow!(
+                        error.unwrap_or_else(|| "command failed".to_string())
+                    ))
+                });
+            }
+            Some(Reply::Bus {
+                zone_id,
+                command,
+                request_id,
+            }) => {
+                bus.publish(BusEvent::CommandResult {
+                    response: CommandResponse {
+                        zone_id,
+                        command,
+                        success,
+                        error,
+                        timestamp: now_millis(),
+                    },
+                    request_id,
+                });
+            }
+            None => tracing::debug!(
+                "External adapter {} answered unknown request {}",
+                name,
+                request_id
+            ),
+        },
+        AdapterMessage::Zone {
+            zone_id: local_id,
+            zone_name,
+            state,
+            is_controllable,
+            is_seekable,
+            volume,
+        } => {
+            let mut instances = instances.write().await;
+            let Some(instance) = instances.get_mut(name) else {
+                return;
+            };
+            let existing = instance.zones.get(&local_id);
+            let is_new = existing.is_none();
+            let zone = Zone {
+                zone_id: zone_id(&local_id),
+                zone_name,
+                state,
+                volume_control: volume,
+                now_playing: existing.and_then(|z| z.now_playing.clone()),
+                source: EXTERNAL_PREFIX.to_string(),
+                is_controllable,
+                is_seekable,
+                last_updated: now_millis(),
+            };
+            if is_new {
+                bus.publish(BusEvent::ZoneDiscovered { zone: zone.clone() });
+            } else {
+                bus.publish(BusEvent::ZoneUpdated {
+                    zone_id: zone.zone_id.clone(),
+                    display_name: zone.zone_name.clone(),
+                    state: zone.state.to_string(),
+                });
+            }
+            instance.zones.insert(local_id, zone);
+        }
+        AdapterMessage::ZoneRemoved { zone_id: local_id } => {
+            let removed = instances
+                .write()
+                .await
+                .get_mut(name)
+                .and_then(|i| i.zones.remove(&local_id));
+            if let Some(zone) = removed {
+                bus.publish(BusEvent::ZoneRemoved {
+                    zone_id: zone.zone_id,
+                });
+            }
+        }
+        AdapterMessage::State {
+            zone_id: local_id,
+            state,
+        } => {
+            let mut instances = instances.write().await;
+            let Some(zone) = instances
+                .get_mut(name)
+                .and_then(|i| i.zones.get_mut(&local_id))
+            else {
+                tracing::debug!("External adapter {}: unknown zone {}", name, local_id);
+                return;
+            };
+            zone.state = state;
+            zone.last_updated = now_millis();
+            bus.publish(BusEvent::ZoneUpdated {
+                zone_id: zone.zone_id.clone(),
+                display_name: zone.zone_name.clone(),
+                state: state.to_string(),
+            });
+        }
+        AdapterMessage::NowPlaying {
+            zone_id: local_id,
+            title,
+            artist,
+            album,
+            image_key,
+            seek_position,
+            duration,
+        } => {
+            let mut instances = instances.write().await;
+            let Some(zone) = instances
+                .get_mut(name)
+                .and_then(|i| i.zones.get_mut(&local_id))
+            else {
+                tracing::debug!("External adapter {}: unknown zone {}", name, local_id);
+                return;
+            };
+            zone.now_playing = Some(NowPlaying {
+                title: title.clone(),
+                artist: artist.clone(),
+                album: album.clone(),
+                image_key: image_key.clone(),
+                seek_position,
+                duration,
+                metadata: None,
+            });
+            zone.last_updated = now_millis();
+            bus.publish(BusEvent::NowPlayingChanged {
+                zone_id: zone.zone_id.clone(),
+                title: Some(title),
+                artist: Some(artist),
+                album: Some(album),
+                image_key,
+            });
+        }
+        AdapterMessage::Seek {
+            zone_id: local_id,
+            position,
+        } => {
+            let mut instances = instances.write().await;
+            let Some(zone) = instances
+                .get_mut(name)
+                .and_then(|i| i.zones.get_mut(&local_id))
+            else {
+                return;
+            };
+            if let Some(np) = zone.now_playing.as_mut() {
+                np.seek_position = Some(position);
+            }
+            bus.publish(BusEvent::SeekPositionChanged {
+                zone_id: zone.zone_id.clone(),
+                position: position as i64,
+            });
+        }
+        AdapterMessage::Volume {
+            zone_id: local_id,
+            value,
+            is_muted,
+        } => {
+            let mut instances = instances.write().await;
+            let Some(zone) = instances
+                .get_mut(name)
+                .and_then(|i| i.zones.get_mut(&local_id))
+            else {
+                return;
+            };
+            if let Some(vc) = zone.volume_control.as_mut() {
+                vc.value = value;
+                vc.is_muted = is_muted;
+            }
+            bus.publish(BusEvent::VolumeChanged {
+                output_id: zone.zone_id.clone(),
+                value,
+                is_muted,
+            });
+        }
+    }
+}
@@ -10,7 +10,7 @@ use quick_xml::Writer;
 use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
 use std::net::{Ipv4Addr, SocketAddrV4};
 use std::path::PathBuf;
@@ -20,12 +20,14 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{TcpStream, UdpSocket};
 use tokio::sync::{Mutex, RwLock};
 use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
 
 use crate::bus::{
     BusEvent, NowPlaying as BusNowPlaying, PlaybackState, SharedBus, TrackMetadata,
     VolumeControl as BusVolumeControl, VolumeScale, Zone as BusZone,
 };
 use crate::config::get_config_dir;
+use crate::volume::delegation::delegated_action;
 
 const HQP_CONFIG_FILE: &str = "hqp-config.json";
 
@@ -138,6 +140,8 @@ const PROFILE_PATH: &str = "/config/profile/load";
 const MAX_RECONNECT_ATTEMPTS: u32 = 3;
 /// Delay between reconnection attempts
 const RECONNECT_DELAY: Duration = Duration::from_millis(200);
+/// How often configured instances are refreshed in the background
+const MONITOR_INTERVAL: Duration = Duration::from_secs(2);
 
 /// HQPlayer state information
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -188,6 +192,13 @@ pub struct HqpStatus {
     pub active_channels: u32,
     pub samplerate: u32,
     pub bitrate: u32,
+    /// Track metadata from the `<metadata>` element (empty when absent)
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub artist: String,
+    #[serde(default)]
+    pub album: String,
 }
 
 /// Volume range info
@@ -251,6 +262,39 @@ pub struct PipelineStatus {
     pub settings: PipelineSettings,
 }
 
+/// Shown instead of the pipeline in effect during a blind comparison
+const BLIND_LABEL: &str = "Hidden (A/B/X)";
+
+impl PipelineStatus {
+    /// Hide what's in effect (the options stay listed)
+    fn mask(&mut self) {
+        let status = &mut self.status;
+        for field in [
+            &mut status.mode,
+            &mut status.active_mode,
+            &mut status.active_filter,
+            &mut status.active_shaper,
+        ] {
+            *field = BLIND_LABEL.to_string();
+        }
+        status.active_rate = 0;
+
+        let settings = &mut self.settings;
+        for setting in [
+            &mut settings.mode,
+            &mut settings.filter1x,
+            &mut settings.filter_nx,
+            &mut settings.shaper,
+            &mut settings.samplerate,
+        ] {
+            setting.selected = SelectedOption {
+                value: String::new(),
+                label: BLIND_LABEL.to_string(),
+            };
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineState {
     pub state: String,
@@ -312,6 +356,51 @@ pub struct MatrixProfile {
     pub name: String,
 }
 
+/// Pipeline settings by name, portable between instances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HqpPipelineSettings {
+    pub mode: String,
+    pub filter1x: String,
+    pub filter_nx: String,
+    pub shaper: String,
+    /// Output rate in Hz (0 = auto)
+    pub rate: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix_profile: Option<String>,
+}
+
+/// Playlist entry (from PlaylistGet)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HqpPlaylistItem {
+    pub index: u32,
+    pub uri: String,
+    /// Track length in seconds
+    pub length: u32,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+}
+
+/// Library directory, usually one album (from LibraryGet)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HqpLibraryDirectory {
+    pub path: String,
+    pub artist: String,
+    pub album: String,
+    pub genre: String,
+    pub date: String,
+    pub files: Vec<HqpLibraryFile>,
+}
+
+/// Track in a library directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HqpLibraryFile {
+    pub name: String,
+    pub title: String,
+    /// Track length in seconds
+    pub length: u32,
+}
+
 /// Internal adapter state
 #[allow(dead_code)]
 struct HqpAdapterState {
@@ -324,6 +413,15 @@ struct HqpAdapterState {
     connected: bool,
     info: Option<HqpInfo>,
     last_state: Option<HqpState>,
+    volume_range: Option<VolumeRange>,
+    /// Mute as set by our own mute commands (HQPlayer only toggles mute and
+    /// doesn't report it). A mute toggled from HQPlayer's own UI isn't seen,
+    /// so the flag is inverted from then on until toggled back.
+    muted: bool,
+    /// Pipeline hidden from events and status during an A/B/X session
+    blind: bool,
+    /// Standalone zone as last published (None while linked or disconnected)
+    zone: Option<BusZone>,
     modes: Vec<ListItem>,
     filters: Vec<FilterItem>,
     shapers: Vec<ListItem>,
@@ -358,6 +456,10 @@ impl Default for HqpAdapterState {
             connected: false,
             info: None,
             last_state: None,
+            volume_range: None,
+            muted: false,
+            blind: false,
+            zone: None,
             modes: Vec::new(),
             filters: Vec::new(),
             shapers: Vec::new(),
@@ -562,33 +664,23 @@ impl HqpAdapter {
         }
 
         tracing::info!("HQPlayer connected: {} v{}", info.name, info.version);
-        self.bus
-            .publish(BusEvent::HqpConnected { host: host.clone() });
-
-        // Get status and volume range for ZoneDiscovered (using inner methods to avoid recursion)
-        let status = self.get_playback_status_inner().await.unwrap_or_default();
-        let vol_range = self.get_volume_range_inner().await.unwrap_or_default();
-
-        // Get instance name for zone ID
-        let instance_name = {
-            let state = self.state.read().await;
-            state.instance_name.clone()
-        };
+        self.bus.publish(BusEvent::HqpConnected { host });
 
-        // Emit ZoneDiscovered for this HQPlayer instance
-        let zone =
-            Self::hqp_status_to_zone(&host, instance_name.as_deref(), &info, &status, &vol_range);
-        self.bus.publish(BusEvent::ZoneDiscovered { zone });
+        // Volume range for the standalone zone (using inner method to avoid recursion).
+        // The zone itself is published by refresh(), which knows whether the
+        // instance is linked.
+        let vol_range = self.get_volume_range_inner().await.ok();
+        self.state.write().await.volume_range = vol_range;
 
         Ok(())
     }
 
     /// Disconnect
     pub async fn disconnect(&self) {
-        let (host, instance_name) = {
+        let (host, zone) = {
             let mut state = self.state.write().await;
             state.connected = false;
-            (state.host.clone(), state.instance_name.clone())
+            (state.host.clone(), state.zone.take())
         };
 
         {
@@ -596,13 +688,13 @@ impl HqpAdapter {
             *conn = None;
         }
 
-        if let Some(ref h) = host {
-            // Emit ZoneRemoved for this HQPlayer instance
-            let zone_id = format!("hqplayer:{}", instance_name.as_deref().unwrap_or(h));
-            self.bus.publish(BusEvent::ZoneRemoved { zone_id });
-
-            self.bus
-                .publish(BusEvent::HqpDisconnected { host: h.clone() });
+        if let Some(zone) = zone {
+            self.bus.publish(BusEvent::ZoneRemoved {
+                zone_id: zone.zone_id,
+            });
+        }
+        if let Some(h) = host {
+            self.bus.publish(BusEvent::HqpDisconnected { host: h });
         }
     }
 
@@ -622,10 +714,10 @@ impl HqpAdapter {
 
     /// Mark connection as broken (called on communication errors)
     async fn mark_disconnected(&self) {
-        let (host, instance_name) = {
+        let (host, zone) = {
             let mut state = self.state.write().await;
             state.connected = false;
-            (state.host.clone(), state.instance_name.clone())
+            (state.host.clone(), state.zone.take())
         };
 
         {
@@ -635,9 +727,11 @@ impl HqpAdapter {
 
         if let Some(ref h) = host {
             tracing::warn!("HQPlayer connection lost to {}", h);
-            // Emit ZoneRemoved for this HQPlayer instance
-            let zone_id = format!("hqplayer:{}", instance_name.as_deref().unwrap_or(h));
-            self.bus.publish(BusEvent::ZoneRemoved { zone_id });
+        }
+        if let Some(zone) = zone {
+            self.bus.publish(BusEvent::ZoneRemoved {
+                zone_id: zone.zone_id,
+            });
         }
     }
 
@@ -791,29 +885,6 @@ impl HqpAdapter {
         }))
     }
 
-    /// Get playback status (no reconnection)
-    async fn get_playback_status_inner(&self) -> Result<HqpStatus> {
-        let xml = Self::build_request("Status", &[("subscribe", "0")]);
-        let response = self.send_command_inner(&xml).await?;
-
-        Ok(HqpStatus {
-            state: Self::parse_attr_u32(&response, "state") as u8,
-            track: Self::parse_attr_u32(&response, "track"),
-            track_id: Self::parse_attr(&response, "track_id").unwrap_or_default(),
-            position: Self::parse_attr_u32(&response, "position"),
-            length: Self::parse_attr_u32(&response, "length"),
-            volume: Self::parse_attr_i32(&response, "volume"),
-            active_mode: Self::parse_attr(&response, "active_mode").unwrap_or_default(),
-            active_filter: Self::parse_attr(&response, "active_filter").unwrap_or_default(),
-            active_shaper: Self::parse_attr(&response, "active_shaper").unwrap_or_default(),
-            active_rate: Self::parse_attr_u32(&response, "active_rate"),
-            active_bits: Self::parse_attr_u32(&response, "active_bits"),
-            active_channels: Self::parse_attr_u32(&response, "active_channels"),
-            samplerate: Self::parse_attr_u32(&response, "samplerate"),
-            bitrate: Self::parse_attr_u32(&response, "bitrate"),
-        })
-    }
-
     /// Get volume range (no reconnection)
     async fn get_volume_range_inner(&self) -> Result<VolumeRange> {
         let xml = Self::build_request("VolumeRange", &[]);
@@ -845,6 +916,49 @@ impl HqpAdapter {
         )
     }
 
+    /// Parse a Status response
+    fn parse_status(response: &str) -> HqpStatus {
+        HqpStatus {
+            state: Self::parse_attr_u32(response, "state") as u8,
+            track: Self::parse_attr_u32(response, "track"),
+            track_id: Self::parse_attr(response, "track_id").unwrap_or_default(),
+            position: Self::parse_attr_u32(response, "position"),
+            length: Self::parse_attr_u32(response, "length"),
+            volume: Self::parse_attr_i32(response, "volume"),
+            active_mode: Self::parse_attr(response, "active_mode").unwrap_or_default(),
+            active_filter: Self::parse_attr(response, "active_filter").unwrap_or_default(),
+            active_shaper: Self::parse_attr(response, "active_shaper").unwrap_or_default(),
+            active_rate: Self::parse_attr_u32(response, "active_rate"),
+            active_bits: Self::parse_attr_u32(response, "active_bits"),
+            active_channels: Self::parse_attr_u32(response, "active_channels"),
+            samplerate: Self::parse_attr_u32(response, "samplerate"),
+            bitrate: Self::parse_attr_u32(response, "bitrate"),
+            title: Self::parse_metadata_attr(response, "title"),
+            artist: Self::parse_metadata_attr(response, "artist"),
+            album: Self::parse_metadata_attr(response, "album"),
+        }
+    }
+
+    /// Parse an attribute of the `<metadata>` element in a Status response
+    fn parse_metadata_attr(xml: &str, attr: &str) -> String {
+        xml.find("<metadata")
+            .map(|start| Self::parse_text_attr(&xml[start..], attr))
+            .unwrap_or_default()
+    }
+
+    /// Parse a free-text attribute (names, paths, tags), unescaped
+    ///
+    /// Matched with a leading space so "artist" doesn't pick up "albumartist".
+    fn parse_text_attr(xml: &str, attr: &str) -> String {
+        Self::parse_attr(xml, &format!(" {}", attr))
+            .map(|value| {
+                quick_xml::escape::unescape(&value)
+                    .map(|unescaped| unescaped.into_owned())
+                    .unwrap_or(value)
+            })
+            .unwrap_or_default()
+    }
+
     /// Parse XML attribute
     fn parse_attr(xml: &str, attr: &str) -> Option<String> {
         let pattern = format!("{}=\"", attr);
@@ -920,22 +1034,7 @@ impl HqpAdapter {
         let xml = Self::build_request("Status", &[("subscribe", "0")]);
         let response = self.send_command(&xml).await?;
 
-        Ok(HqpStatus {
-            state: Self::parse_attr_u32(&response, "state") as u8,
-            track: Self::parse_attr_u32(&response, "track"),
-            track_id: Self::parse_attr(&response, "track_id").unwrap_or_default(),
-            position: Self::parse_attr_u32(&response, "position"),
-            length: Self::parse_attr_u32(&response, "length"),
-            volume: Self::parse_attr_i32(&response, "volume"),
-            active_mode: Self::parse_attr(&response, "active_mode").unwrap_or_default(),
-            active_filter: Self::parse_attr(&response, "active_filter").unwrap_or_default(),
-            active_shaper: Self::parse_attr(&response, "active_shaper").unwrap_or_default(),
-            active_rate: Self::parse_attr_u32(&response, "active_rate"),
-            active_bits: Self::parse_attr_u32(&response, "active_bits"),
-            active_channels: Self::parse_attr_u32(&response, "active_channels"),
-            samplerate: Self::parse_attr_u32(&response, "samplerate"),
-            bitrate: Self::parse_attr_u32(&response, "bitrate"),
-        })
+        Ok(Self::parse_status(&response))
     }
 
     /// Get volume range
@@ -1075,6 +1174,153 @@ impl HqpAdapter {
         Ok(())
     }
 
+    /// Set mode, filter (1x and Nx) and/or shaper by name, as listed by
+    /// HQPlayer (case-insensitive)
+    pub async fn set_pipeline_by_name(
+        &self,
+        mode: Option<&str>,
+        filter: Option<&str>,
+        shaper: Option<&str>,
+    ) -> Result<()> {
+        self.ensure_connected().await?;
+
+        let (mode, filter, shaper) = {
+            let state = self.state.read().await;
+            (
+                mode.map(|name| value_by_name("mode", name, list_items(&state.modes)))
+                    .transpose()?,
+                filter
+                    .map(|name| value_by_name("filter", name, filter_items(&state.filters)))
+                    .transpose()?,
+                shaper
+                    .map(|name| value_by_name("shaper", name, list_items(&state.shapers)))
+                    .transpose()?,
+            )
+        };
+
+        if let Some(value) = mode {
+            self.set_list_value("SetMode", &[("value", value)]).await?;
+        }
+        if let Some(value) = filter {
+            self.set_list_value("SetFilter", &[("value", value), ("value1x", value)])
+                .await?;
+        }
+        if let Some(value) = shaper {
+            self.set_list_value("SetShaping", &[("value", value)])
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Send a setter with list values, which can be negative (e.g. -1 for
+    /// the "[source]" mode)
+    async fn set_list_value(&self, element: &str, values: &[(&str, i32)]) -> Result<()> {
+        let values: Vec<(&str, String)> = values
+            .iter()
+            .map(|(key, value)| (*key, value.to_string()))
+            .collect();
+        let attrs: Vec<(&str, &str)> = values
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        let xml = Self::build_request(element, &attrs);
+        self.send_command(&xml).await?;
+        Ok(())
+    }
+
+    /// Snapshot the live pipeline by name, so it can be re-applied here or
+    /// on another instance
+    pub async fn snapshot_pipeline(&self) -> Result<HqpPipelineSettings> {
+        let live = self.get_state().await?;
+        // Matrix is optional (not every HQPlayer has profiles)
+        let matrix_profile = self
+            .get_matrix_profile()
+            .await
+            .ok()
+            .flatten()
+            .map(|p| p.name);
+
+        let state = self.state.read().await;
+        let filter_name = |index: u32| {
+            state
+                .filters
+                .get(index as usize)
+                .map(|f| f.name.clone())
+                .ok_or_else(|| anyhow!("HQPlayer reported unknown filter {}", index))
+        };
+        Ok(HqpPipelineSettings {
+            mode: state
+                .modes
+                .iter()
+                .find(|m| m.index == live.mode as u32)
+                .map(|m| m.name.clone())
+                .ok_or_else(|| anyhow!("HQPlayer reported unknown mode {}", live.mode))?,
+            filter1x: filter_name(live.filter1x.unwrap_or(live.filter))?,
+            filter_nx: filter_name(live.filter_nx.unwrap_or(live.filter))?,
+            shaper: state
+                .shapers
+                .get(live.shaper as usize)
+                .map(|s| s.name.clone())
+                .ok_or_else(|| anyhow!("HQPlayer reported unknown shaper {}", live.shaper))?,
+            rate: state
+                .rates
+                .iter()
+                .find(|r| r.index == live.rate)
+                .map(|r| r.rate)
+                .unwrap_or(0),
+            matrix_profile,
+        })
+    }
+
+    /// Apply a pipeline snapshot through SetMode/SetFilter/SetShaping/SetRate,
+    /// which doesn't restart HQPlayer the way loading a profile does
+    ///
+    /// Every name is resolved before anything is changed, so a snapshot from
+    /// an instance with different filters fails without half-applying.
+    pub async fn apply_pipeline(&self, settings: &HqpPipelineSettings) -> Result<()> {
+        self.ensure_connected().await?;
+        let matrix = match &settings.matrix_profile {
+            Some(name) => Some(
+                self.get_matrix_profiles()
+                    .await?
+                    .into_iter()
+                    .find(|p| p.name.eq_ignore_ascii_case(name))
+                    .map(|p| p.index)
+                    .ok_or_else(|| anyhow!("Unknown HQPlayer matrix profile: {}", name))?,
+            ),
+            None => None,
+        };
+
+        let (mode, filter1x, filter_nx, shaper, rate) = {
+            let state = self.state.read().await;
+            (
+                value_by_name("mode", &settings.mode, list_items(&state.modes))?,
+                value_by_name("filter", &settings.filter1x, filter_items(&state.filters))?,
+                value_by_name("filter", &settings.filter_nx, filter_items(&state.filters))?,
+                value_by_name("shaper", &settings.shaper, list_items(&state.shapers))?,
+                match state.rates.iter().find(|r| r.rate == settings.rate) {
+                    Some(r) => Some(r.index),
+                    // Auto rate, unless HQPlayer lists it: keep the output rate
+                    None if settings.rate == 0 => None,
+                    None => return Err(anyhow!("Unsupported HQPlayer rate: {}", settings.rate)),
+                },
+            )
+        };
+
+        self.set_list_value("SetMode", &[("value", mode)]).await?;
+        self.set_list_value("SetFilter", &[("value", filter_nx), ("value1x", filter1x)])
+            .await?;
+        self.set_list_value("SetShaping", &[("value", shaper)])
+            .await?;
+        if let Some(rate) = rate {
+            self.set_rate(rate).await?;
+        }
+        if let Some(index) = matrix {
+            self.set_matrix_profile(index).await?;
+        }
+        Ok(())
+    }
+
     /// Set volume
     pub async fn set_volume(&self, value: i32) -> Result<()> {
         let xml = Self::build_request("Volume", &[("value", &value.to_string())]);
@@ -1097,12 +1343,65 @@ impl HqpAdapter {
     }
 
     /// Mute toggle
+    ///
+    /// `refresh` can't see mute, so the new flag is published here.
     pub async fn volume_mute(&self) -> Result<()> {
         let xml = Self::build_request("VolumeMute", &[]);
         self.send_command(&xml).await?;
+        let (cached, is_muted) = {
+            let mut state = self.state.write().await;
+            state.muted = !state.muted;
+            (state.last_state.as_ref().map(|s| s.volume), state.muted)
+        };
+        let volume = match cached {
+            Some(volume) => volume,
+            None => self.get_state().await?.volume,
+        };
+        self.bus.publish(BusEvent::VolumeChanged {
+            output_id: self.volume_output_id().await,
+            value: volume as f32,
+            is_muted,
+        });
         Ok(())
     }
 
+    /// Mute or unmute (toggles only if HQPlayer isn't that way already)
+    pub async fn set_mute(&self, muted: bool) -> Result<()> {
+        if self.state.read().await.muted == muted {
+            return Ok(());
+        }
+        self.volume_mute().await
+    }
+
+    /// Output ID volume is reported under (the instance's standalone zone ID)
+    pub async fn volume_output_id(&self) -> String {
+        let state = self.state.read().await;
+        let name = state.instance_name.as_deref().or(state.host.as_deref());
+        format!("hqplayer:{}", name.unwrap_or_default())
+    }
+
+    /// Current volume in dB, with HQPlayer's volume range
+    pub async fn get_volume_control(&self) -> Result<BusVolumeControl> {
+        let status = self.get_playback_status().await?;
+        let (cached, is_muted) = {
+            let state = self.state.read().await;
+            (state.volume_range.clone(), state.muted)
+        };
+        let range = match cached {
+            Some(range) => range,
+            None => self.get_volume_range().await?,
+        };
+        Ok(BusVolumeControl {
+            value: status.volume as f32,
+            min: range.min as f32,
+            max: range.max as f32,
+            step: range.step as f32,
+            is_muted,
+            scale: VolumeScale::Decibel,
+            output_id: Some(self.volume_output_id().await),
+        })
+    }
+
     /// Play
     pub async fn play(&self) -> Result<()> {
         let xml = Self::build_request("Play", &[("last", "0")]);
@@ -1157,7 +1456,223 @@ impl HqpAdapter {
         }
     }
 
-    /// Get full pipeline status
+    // =========================================================================
+    // Playlist and library (HQPlayer Desktop's own transport)
+    // =========================================================================
+
+    /// Get the playlist
+    pub async fn get_playlist(&self) -> Result<Vec<HqpPlaylistItem>> {
+        let xml = Self::build_request("PlaylistGet", &[("picture", "0")]);
+        let response = self.send_command(&xml).await?;
+
+        // Items may carry a <metadata> child, so each one runs to the next item
+        Ok(response
+            .split("<PlaylistItem")
+            .skip(1)
+            .map(|item| {
+                let tag = item.split('>').next().unwrap_or_default();
+                HqpPlaylistItem {
+                    index: Self::parse_attr_u32(tag, "index"),
+                    uri: Self::parse_text_attr(tag, "uri"),
+                    length: Self::parse_attr_u32(tag, "length"),
+                    title: Self::parse_metadata_attr(item, "title"),
+                    artist: Self::parse_metadata_attr(item, "artist"),
+                    album: Self::parse_metadata_attr(item, "album"),
+                }
+            })
+            .collect())
+    }
+
+    /// Add a file or URL to the playlist, after the playing track if `queued`
+    /// or at the end otherwise; `clear` empties the playlist first
+    pub async fn playlist_add(&self, uri: &str, queued: bool, clear: bool) -> Result<()> {
+        let xml = Self::build_request(
+            "PlaylistAdd",
+            &[
+                ("uri", uri),
+                ("queued", if queued { "1" } else { "0" }),
+                ("clear", if clear { "1" } else { "0" }),
+            ],
+        );
+        self.send_command(&xml).await?;
+        Ok(())
+    }
+
+    /// Empty the playlist
+    pub async fn playlist_clear(&self) -> Result<()> {
+        let xml = Self::build_request("PlaylistClear", &[]);
+        self.send_command(&xml).await?;
+        Ok(())
+    }
+
+    /// Remove a playlist entry
+    pub async fn playlist_remove(&self, index: u32) -> Result<()> {
+        let xml = Self::build_request("PlaylistRemove", &[("index", &index.to_string())]);
+        self.send_command(&xml).await?;
+        Ok(())
+    }
+
+    /// Start playing a playlist entry
+    pub async fn playlist_play(&self, index: u32) -> Result<()> {
+        let xml = Self::build_request("SelectTrack", &[("index", &index.to_string())]);
+        self.send_command(&xml).await?;
+        self.play().await
+    }
+
+    /// Get the library, one entry per directory
+    pub async fn get_library(&self) -> Result<Vec<HqpLibraryDirectory>> {
+        let xml = Self::build_request("LibraryGet", &[("pictures", "0")]);
+        let response = self.send_command(&xml).await?;
+
+        Ok(response
+            .split("<LibraryDirectory")
+            .skip(1)
+            .map(|directory| {
+                let tag = directory.split('>').next().unwrap_or_default();
+                HqpLibraryDirectory {
+                    path: Self::parse_text_attr(tag, "path"),
+                    artist: Self::parse_text_attr(tag, "artist"),
+                    album: Self::parse_text_attr(tag, "album"),
+                    genre: Self::parse_text_attr(tag, "genre"),
+                    date: Self::parse_text_attr(tag, "date"),
+                    files: Self::parse_items(directory, "LibraryFile", |file| HqpLibraryFile {
+                        name: Self::parse_text_attr(file, "name"),
+                        title: Self::parse_text_attr(file, "song"),
+                        length: Self::parse_attr_u32(file, "length"),
+                    }),
+                }
+            })
+            .collect())
+    }
+
+    /// Load a library directory into the playlist, after the playing track if
+    /// `queued` or replacing the playlist otherwise
+    pub async fn library_load(&self, path: &str, queued: bool) -> Result<()> {
+        let xml = Self::build_request(
+            "LibraryLoad",
+            &[("path", path), ("queued", if queued { "1" } else { "0" })],
+        );
+        self.send_command(&xml).await?;
+        Ok(())
+    }
+
+    /// Refresh state from HQPlayer, publishing state and pipeline changes
+    ///
+    /// Run periodically by the instance monitor, which also keeps the control
+    /// connection alive: a dropped connection is re-established on the next
+    /// refresh. Changes made in HQPlayer's own UI are picked up here.
+    ///
+    /// An instance that isn't `linked` to a Roon/LMS zone is playing on its
+    /// own (from its library or playlist), so it's published as a
+    /// `hqplayer:<instance>` zone; a linked one is shown through its zone.
+    pub async fn refresh(&self, linked: bool) -> Result<()> {
+        let state = self.get_state().await?;
+        let status = self.get_playback_status().await?;
+        self.update_zone(&status, linked).await;
+
+        let (host, previous, is_muted) = {
+            let mut s = self.state.write().await;
+            (
+                s.host.clone().unwrap_or_default(),
+                s.last_state.replace(state.clone()),
+                s.muted,
+            )
+        };
+        // First refresh after startup: nothing to compare against
+        let Some(previous) = previous else {
+            return Ok(());
+        };
+
+        if previous.volume != state.volume {
+            // Keeps zones whose volume is routed to this instance in step
+            self.bus.publish(BusEvent::VolumeChanged {
+                output_id: self.volume_output_id().await,
+                value: state.volume as f32,
+                is_muted,
+            });
+        }
+        if previous.state != state.state {
+            self.bus.publish(BusEvent::HqpStateChanged {
+                host: host.clone(),
+                state: playback_state(state.state).to_string(),
+            });
+        }
+        // A blind comparison mustn't give the pipeline away
+        let blind = self.state.read().await.blind;
+        if !blind && pipeline_key(&previous) != pipeline_key(&state) {
+            let non_empty = |s: String| Some(s).filter(|s| !s.is_empty());
+            let rate = if status.samplerate > 0 {
+                format!("{}->{}", status.samplerate, state.active_rate)
+            } else {
+                state.active_rate.to_string()
+            };
+            tracing::debug!("HQPlayer pipeline changed on {}", host);
+            self.bus.publish(BusEvent::HqpPipelineChanged {
+                host,
+                filter: non_empty(status.active_filter),
+                shaper: non_empty(status.active_shaper),
+                rate: Some(rate),
+            });
+        }
+        Ok(())
+    }
+
+    /// Publish, update or withdraw the standalone zone
+    async fn update_zone(&self, status: &HqpStatus, linked: bool) {
+        if linked {
+            let removed = self.state.write().await.zone.take();
+            if let Some(zone) = removed {
+                tracing::debug!("HQPlayer {} is linked, hiding its zone", zone.zone_id);
+                self.bus.publish(BusEvent::ZoneRemoved {
+                    zone_id: zone.zone_id,
+                });
+            }
+            return;
+        }
+
+        let vol_range = match self.state.read().await.volume_range.clone() {
+            Some(range) => range,
+            None => self.get_volume_range().await.unwrap_or_default(),
+        };
+
+        let mut state = self.state.write().await;
+        state.volume_range = Some(vol_range.clone());
+        let Some(host) = state.host.clone() else {
+            return;
+        };
+        let info = state.info.clone().unwrap_or_default();
+        let zone = Self::hqp_status_to_zone(
+            &host,
+            state.instance_name.as_deref(),
+            &info,
+            status,
+            &vol_range,
+            state.muted,
+        );
+        let changed = state
+            .zone
+            .as_ref()
+            .is_none_or(|previous| zone_changed(previous, &zone));
+        state.zone = Some(zone.clone());
+        drop(state);
+
+        if changed {
+            self.bus.publish(BusEvent::ZoneDiscovered { zone });
+        }
+    }
+
+    /// Hide (or reveal) the pipeline in events and status while an A/B/X
+    /// session runs on this instance
+    pub async fn set_blind(&self, blind: bool) {
+        self.state.write().await.blind = blind;
+    }
+
+    /// The standalone `hqplayer:<instance>` zone, if published
+    pub async fn get_zone(&self) -> Option<BusZone> {
+        self.state.read().await.zone.clone()
+    }
+
+    /// Get full pipeline status (masked during an A/B/X session)
     pub async fn get_pipeline_status(&self) -> Result<PipelineStatus> {
         let state = self.get_state().await?;
         let vol_range = self.get_volume_range().await?;
@@ -1199,7 +1714,7 @@ impl HqpAdapter {
             _ => "Unknown",
         };
 
-        Ok(PipelineStatus {
+        let mut pipeline = PipelineStatus {
             status: PipelineState {
                 state: state_str.to_string(),
                 mode: get_mode_by_index(state.mode),
@@ -1305,7 +1820,11 @@ impl HqpAdapter {
                         .collect(),
                 },
             },
-        })
+        };
+        if cached.blind {
+            pipeline.mask();
+        }
+        Ok(pipeline)
     }
 
     // =========================================================================
@@ -1755,6 +2274,7 @@ impl HqpAdapter {
         info: &HqpInfo,
         status: &HqpStatus,
         vol_range: &VolumeRange,
+        is_muted: bool,
     ) -> BusZone {
         use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -1765,12 +2285,7 @@ impl HqpAdapter {
             info.name.clone()
         };
 
-        let state = match status.state {
-            0 => PlaybackState::Stopped,
-            1 => PlaybackState::Paused,
-            2 => PlaybackState::Playing,
-            _ => PlaybackState::Unknown,
-        };
+        let state = playback_state(status.state);
 
         let volume_control = if vol_range.enabled {
             Some(BusVolumeControl {
@@ -1778,7 +2293,7 @@ impl HqpAdapter {
                 min: vol_range.min as f32,
                 max: vol_range.max as f32,
                 step: vol_range.step as f32,
-                is_muted: false, // HQPlayer doesn't report mute separately
+                is_muted,
                 scale: VolumeScale::Decibel,
                 output_id: Some(zone_id.clone()),
             })
@@ -1787,28 +2302,29 @@ impl HqpAdapter {
         };
 
         // Build now_playing if we have track info
-        let now_playing = if !status.track_id.is_empty() || status.length > 0 {
-            Some(BusNowPlaying {
-                title: String::new(), // HQPlayer status doesn't include title
-                artist: String::new(),
-                album: String::new(),
-                image_key: None,
-                seek_position: Some(status.position as f64),
-                duration: Some(status.length as f64),
-                metadata: Some(TrackMetadata {
-                    format: Some(status.active_mode.clone()),
-                    sample_rate: Some(status.samplerate),
-                    bit_depth: Some(status.active_bits as u8),
-                    bitrate: Some(status.bitrate),
-                    genre: None,
-                    composer: None,
-                    track_number: Some(status.track),
-                    disc_number: None,
-                }),
-            })
-        } else {
-            None
-        };
+        let now_playing =
+            if !status.track_id.is_empty() || status.length > 0 || !status.title.is_empty() {
+                Some(BusNowPlaying {
+                    title: status.title.clone(),
+                    artist: status.artist.clone(),
+                    album: status.album.clone(),
+                    image_key: None,
+                    seek_position: Some(status.position as f64),
+                    duration: Some(status.length as f64),
+                    metadata: Some(TrackMetadata {
+                        format: Some(status.active_mode.clone()),
+                        sample_rate: Some(status.samplerate),
+                        bit_depth: Some(status.active_bits as u8),
+                        bitrate: Some(status.bitrate),
+                        genre: None,
+                        composer: None,
+                        track_number: Some(status.track),
+                        disc_number: None,
+                    }),
+                })
+            } else {
+                None
+            };
 
         let last_updated = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -1829,10 +2345,122 @@ impl HqpAdapter {
     }
 }
 
+/// (name, value) pairs of a mode or shaper list
+fn list_items(items: &[ListItem]) -> impl Iterator<Item = (&str, i32)> {
+    items.iter().map(|i| (i.name.as_str(), i.value))
+}
+
+/// (name, value) pairs of the filter list
+fn filter_items(items: &[FilterItem]) -> impl Iterator<Item = (&str, i32)> {
+    items.iter().map(|i| (i.name.as_str(), i.value))
+}
+
+/// Find a list entry's value by name (case-insensitive)
+fn value_by_name<'a>(
+    kind: &str,
+    name: &str,
+    mut items: impl Iterator<Item = (&'a str, i32)>,
+) -> Result<i32> {
+    items
+        .find(|(item, _)| item.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+        .ok_or_else(|| anyhow!("Unknown HQPlayer {}: {}", kind, name))
+}
+
+/// Map HQPlayer's numeric state (0=stopped, 1=paused, 2=playing)
+fn playback_state(state: u8) -> PlaybackState {
+    match state {
+        0 => PlaybackState::Stopped,
+        1 => PlaybackState::Paused,
+        2 => PlaybackState::Playing,
+        _ => PlaybackState::Unknown,
+    }
+}
+
+/// The State fields that make up the pipeline (mode, filters, shaper, rates)
+fn pipeline_key(state: &HqpState) -> (u8, u32, Option<u32>, Option<u32>, u32, u32, u8, u32) {
+    (
+        state.mode,
+        state.filter,
+        state.filter1x,
+        state.filter_nx,
+        state.shaper,
+        state.rate,
+        state.active_mode,
+        state.active_rate,
+    )
+}
+
+/// Whether a standalone zone shows something different (seek position is
+/// ignored, it moves on every refresh while playing)
+fn zone_changed(previous: &BusZone, zone: &BusZone) -> bool {
+    let track = |z: &BusZone| {
+        z.now_playing
+            .as_ref()
+            .map(|np| (np.title.clone(), np.artist.clone(), np.album.clone()))
+    };
+    previous.state != zone.state
+        || previous.volume_control.as_ref().map(|v| v.value)
+            != zone.volume_control.as_ref().map(|v| v.value)
+        || track(previous) != track(zone)
+}
+
 // =============================================================================
 // Multi-instance manager
 // =============================================================================
 
+const PRESETS_FILE: &str = "hqp-presets.json";
+
+fn presets_path() -> PathBuf {
+    get_config_dir().join(PRESETS_FILE)
+}
+
+/// Named pipeline snapshot, applicable to any instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HqpPreset {
+    pub name: String,
+    /// Instance the snapshot was taken from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(flatten)]
+    pub settings: HqpPipelineSettings,
+}
+
+fn load_presets() -> Vec<HqpPreset> {
+    let path = presets_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(presets) => presets,
+            Err(e) => {
+                tracing::warn!("Failed to parse HQP presets: {}", e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            tracing::warn!("Failed to read HQP presets: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn save_presets(presets: &[HqpPreset]) {
+    let path = presets_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(presets) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                tracing::error!("Failed to save HQP presets: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("Failed to serialize HQP presets: {}", e),
+    }
+}
+
 /// Instance info for API responses
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HqpInstanceInfo {
@@ -1847,6 +2475,16 @@ pub struct HqpInstanceInfo {
 pub struct HqpInstanceManager {
     instances: Arc<RwLock<HashMap<String, Arc<HqpAdapter>>>>,
     bus: SharedBus,
+    /// Stops the background monitor
+    shutdown: CancellationToken,
+    monitoring: std::sync::atomic::AtomicBool,
+    /// Instances some zone is linked to (kept current by HqpZoneLinkService)
+    linked: Arc<RwLock<HashSet<String>>>,
+    presets: RwLock<Vec<HqpPreset>>,
+    discovery: RwLock<HqpDiscoverySettings>,
+    /// Instances answering background discovery (by address), with the
+    /// number of probes each has missed since
+    discovered: RwLock<HashMap<String, (DiscoveredHqp, u32)>>,
 }
 
 impl HqpInstanceManager {
@@ -1855,7 +2493,89 @@ impl HqpInstanceManager {
         Self {
             instances: Arc::new(RwLock::new(HashMap::new())),
             bus,
+            shutdown: CancellationToken::new(),
+            monitoring: std::sync::atomic::AtomicBool::new(false),
+            linked: Arc::new(RwLock::new(HashSet::new())),
+            presets: RwLock::new(load_presets()),
+            discovery: RwLock::new(load_discovery_settings()),
+            discovered: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Start refreshing every configured instance in the background
+    ///
+    /// Keeps each control connection alive and publishes `HqpStateChanged` /
+    /// `HqpPipelineChanged` when an instance changes, so linked-zone displays
+    /// update without polling HQPlayer themselves. Unlinked instances are
+    /// kept published as `hqplayer:<instance>` zones.
+    pub fn start_monitor(&self) {
+        use std::sync::atomic::Ordering;
+
+        if self.monitoring.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let instances = self.instances.clone();
+        let linked = self.linked.clone();
+        let shutdown = self.shutdown.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(MONITOR_INTERVAL);
+            // An unreachable instance can hold a round up past the interval
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => {
+                        tracing::info!("HQPlayer monitor shutting down");
+                        break;
+                    }
+                    _ = tick.tick() => {
+                        let adapters: Vec<_> = {
+                            let linked = linked.read().await;
+                            instances
+                                .read()
+                                .await
+                                .iter()
+                                .map(|(name, adapter)| (adapter.clone(), linked.contains(name)))
+                                .collect()
+                        };
+                        let refreshes = adapters.iter().map(|(adapter, linked)| async move {
+                            if !adapter.is_configured().await {
+                                return;
+                            }
+                            if let Err(e) = adapter.refresh(*linked).await {
+                                tracing::debug!("HQPlayer refresh failed: {}", e);
+                            }
+                        });
+                        futures::future::join_all(refreshes).await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Stop the background monitor
+    pub fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// Set which instances are linked to a zone; the rest are published as
+    /// standalone zones on the next refresh
+    pub async fn set_linked_instances(&self, names: HashSet<String>) {
+        *self.linked.write().await = names;
+    }
+
+    /// Standalone `hqplayer:<instance>` zones, sorted by zone ID
+    pub async fn get_zones(&self) -> Vec<BusZone> {
+        let adapters: Vec<_> = self.instances.read().await.values().cloned().collect();
+        let mut zones = Vec::new();
+        for adapter in adapters {
+            if let Some(zone) = adapter.get_zone().await {
+                zones.push(zone);
+            }
         }
+        zones.sort_by(|a, b| a.zone_id.cmp(&b.zone_id));
+        zones
     }
 
     /// Load instances from config file
@@ -1970,14 +2690,16 @@ impl HqpInstanceManager {
     }
 
     /// Remove an instance by name
+    ///
+    /// The instance is disconnected, which withdraws its standalone zone.
     pub async fn remove_instance(&self, name: &str) -> bool {
-        let mut instances = self.instances.write().await;
-        let removed = instances.remove(name).is_some();
-        if removed {
-            drop(instances);
-            self.save_to_config().await;
-        }
-        removed
+        let removed = self.instances.write().await.remove(name);
+        let Some(adapter) = removed else {
+            return false;
+        };
+        adapter.disconnect().await;
+        self.save_to_config().await;
+        true
     }
 
     /// Check if any instance is configured
@@ -1991,6 +2713,316 @@ impl HqpInstanceManager {
         let instances = self.instances.read().await;
         instances.len()
     }
+
+    /// Look up an instance, failing with a readable error
+    pub async fn require(&self, name: &str) -> Result<Arc<HqpAdapter>> {
+        self.get(name)
+            .await
+            .ok_or_else(|| anyhow!("Unknown HQP instance: {}", name))
+    }
+
+    /// List saved pipeline presets
+    pub async fn list_presets(&self) -> Vec<HqpPreset> {
+        self.presets.read().await.clone()
+    }
+
+    /// Snapshot an instance's live pipeline as a preset (replacing any
+    /// preset of the same name)
+    pub async fn save_preset(&self, name: &str, instance: &str) -> Result<HqpPreset> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Preset name is required"));
+        }
+        let settings = self.require(instance).await?.snapshot_pipeline().await?;
+        let preset = HqpPreset {
+            name: name.to_string(),
+            source: Some(instance.to_string()),
+            settings,
+        };
+
+        let mut presets = self.presets.write().await;
+        match presets
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => *existing = preset.clone(),
+            None => presets.push(preset.clone()),
+        }
+        save_presets(&presets);
+        tracing::info!("Saved HQP preset {} from {}", name, instance);
+        Ok(preset)
+    }
+
+    /// Delete a preset, returning whether it existed
+    pub async fn delete_preset(&self, name: &str) -> bool {
+        let mut presets = self.presets.write().await;
+        let before = presets.len();
+        presets.retain(|p| !p.name.eq_ignore_ascii_case(name));
+        let removed = presets.len() != before;
+        if removed {
+            save_presets(&presets);
+        }
+        removed
+    }
+
+    /// Find a preset by name (case-insensitive)
+    pub async fn get_preset(&self, name: &str) -> Option<HqpPreset> {
+        self.presets
+            .read()
+            .await
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// Apply a saved preset to an instance
+    pub async fn apply_preset(&self, name: &str, instance: &str) -> Result<HqpPreset> {
+        let preset = self
+            .get_preset(name)
+            .await
+            .ok_or_else(|| anyhow!("Unknown HQP preset: {}", name))?;
+        self.require(instance)
+            .await?
+            .apply_pipeline(&preset.settings)
+            .await?;
+        tracing::info!("Applied HQP preset {} to {}", preset.name, instance);
+        Ok(preset)
+    }
+
+    /// Copy one instance's live pipeline to another
+    pub async fn copy_pipeline(&self, from: &str, to: &str) -> Result<HqpPipelineSettings> {
+        let target = self.require(to).await?;
+        let settings = self.require(from).await?.snapshot_pipeline().await?;
+        target.apply_pipeline(&settings).await?;
+        tracing::info!("Copied HQP pipeline from {} to {}", from, to);
+        Ok(settings)
+    }
+
+    /// Probe the network for HQPlayer instances in the background
+    ///
+    /// Runs until the manager is stopped; see `update_discovered`.
+    pub fn start_discovery(self: Arc<Self>) {
+        let shutdown = self.shutdown.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(DISCOVERY_INTERVAL);
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tick.tick() => match discover_hqplayers(None).await {
+                        Ok(found) => self.update_discovered(found).await,
+                        Err(e) => tracing::debug!("HQPlayer discovery failed: {}", e),
+                    },
+                }
+            }
+        });
+    }
+
+    /// Background discovery settings and the instances currently answering
+    pub async fn discovery_status(&self) -> HqpDiscoveryStatus {
+        let mut instances: Vec<DiscoveredHqp> = self
+            .discovered
+            .read()
+            .await
+            .values()
+            .map(|(hqp, _)| hqp.clone())
+            .collect();
+        instances.sort_by(|a, b| (&a.name, &a.host).cmp(&(&b.name, &b.host)));
+        HqpDiscoveryStatus {
+            auto_register: self.discovery.read().await.auto_register,
+            instances,
+        }
+    }
+
+    /// Turn automatic registration of discovered instances on or off
+    pub async fn set_auto_register(&self, enabled: bool) {
+        let mut settings = self.discovery.write().await;
+        settings.auto_register = enabled;
+        save_discovery_settings(&settings);
+    }
+
+    /// Take in the result of a discovery probe
+    ///
+    /// Publishes `HqpDiscovered` for instances that weren't answering before
+    /// and `HqpLost` for ones that have missed `DISCOVERY_LOST_AFTER` probes.
+    /// When an HQPlayer answers from a new address, instances saved with the
+    /// old one are moved over; with auto-registration on, an HQPlayer no
+    /// instance points at is added under a name derived from its own.
+    ///
+    /// HQPlayers are told apart by announced name and port. Once several
+    /// answer with the same ones, that name is never followed to a new
+    /// address, since a different machine answering would look the same.
+    pub async fn update_discovered(&self, found: Vec<DiscoveredHqp>) {
+        let mut answering: HashMap<String, usize> = HashMap::new();
+        for hqp in &found {
+            *answering.entry(hqp.key()).or_default() += 1;
+        }
+
+        for hqp in &found {
+            let key = hqp.key();
+            let moved_from = {
+                let mut settings = self.discovery.write().await;
+                if answering[&key] > 1 && settings.ambiguous.insert(key.clone()) {
+                    tracing::warn!(
+                        "Several HQPlayers announce themselves as {}, not following their addresses",
+                        hqp.name
+                    );
+                    settings.hosts.remove(&key);
+                    save_discovery_settings(&settings);
+                }
+                if settings.ambiguous.contains(&key) {
+                    None
+                } else {
+                    let previous = settings.hosts.insert(key, hqp.host.clone());
+                    if previous.as_ref() != Some(&hqp.host) {
+                        save_discovery_settings(&settings);
+                    }
+                    // An old address still answering is another HQPlayer
+                    previous.filter(|host| {
+                        *host != hqp.host && !found.iter().any(|other| other.host == *host)
+                    })
+                }
+            };
+            if let Some(old_host) = &moved_from {
+                self.move_host(old_host, &hqp.host).await;
+            }
+
+            let is_new = {
+                let mut discovered = self.discovered.write().await;
+                if let Some(old_host) = &moved_from {
+                    discovered.remove(old_host);
+                }
+                discovered
+                    .insert(hqp.host.clone(), (hqp.clone(), 0))
+                    .is_none()
+            };
+            if is_new {
+                tracing::info!("HQPlayer discovered: {} at {}", hqp.name, hqp.host);
+                self.bus.publish(BusEvent::HqpDiscovered {
+                    name: hqp.name.clone(),
+                    host: hqp.host.clone(),
+                    version: hqp.version.clone(),
+                });
+                if self.discovery.read().await.auto_register {
+                    self.register_discovered(hqp).await;
+                }
+            }
+        }
+
+        let lost: Vec<DiscoveredHqp> = {
+            let mut discovered = self.discovered.write().await;
+            for (host, (_, misses)) in discovered.iter_mut() {
+                if !found.iter().any(|hqp| hqp.host == *host) {
+                    *misses += 1;
+                }
+            }
+            let lost = discovered
+                .values()
+                .filter(|(_, misses)| *misses >= DISCOVERY_LOST_AFTER)
+                .map(|(hqp, _)| hqp.clone())
+                .collect();
+            discovered.retain(|_, (_, misses)| *misses < DISCOVERY_LOST_AFTER);
+            lost
+        };
+        for hqp in lost {
+            tracing::info!("HQPlayer lost: {} at {}", hqp.name, hqp.host);
+            self.bus.publish(BusEvent::HqpLost {
+                name: hqp.name,
+                host: hqp.host,
+            });
+        }
+    }
+
+    /// Point every instance saved with `old_host` at `new_host`
+    async fn move_host(&self, old_host: &str, new_host: &str) {
+        let adapters: Vec<(String, Arc<HqpAdapter>)> = self
+            .instances
+            .read()
+            .await
+            .iter()
+            .map(|(name, adapter)| (name.clone(), adapter.clone()))
+            .collect();
+        let mut moved = false;
+        for (name, adapter) in adapters {
+            let (port, web_port, username, password) = {
+                let state = adapter.state.read().await;
+                if state.host.as_deref() != Some(old_host) {
+                    continue;
+                }
+                (
+                    state.port,
+                    state.web_port,
+                    state.web_username.clone(),
+                    state.web_password.clone(),
+                )
+            };
+            tracing::info!(
+                "HQPlayer instance {} moved from {} to {}",
+                name,
+                old_host,
+                new_host
+            );
+            adapter
+                .configure(
+                    new_host.to_string(),
+                    Some(port),
+                    Some(web_port),
+                    username,
+                    password,
+                )
+                .await;
+            moved = true;
+        }
+        if moved {
+            self.save_to_config().await;
+        }
+    }
+
+    /// Add a discovered HQPlayer unless an instance already points at it
+    ///
+    /// An unconfigured default instance is used first, so a fresh install
+    /// picks up its HQPlayer without any setup.
+    async fn register_discovered(&self, hqp: &DiscoveredHqp) {
+        let (taken, default_free) = {
+            let instances = self.instances.read().await;
+            let mut taken = false;
+            for adapter in instances.values() {
+                if adapter.state.read().await.host.as_deref() == Some(hqp.host.as_str()) {
+                    taken = true;
+                }
+            }
+            let default_free = match instances.get("default") {
+                Some(adapter) => !adapter.is_configured().await,
+                None => false,
+            };
+            (taken, default_free)
+        };
+        if taken {
+            return;
+        }
+
+        let name = if default_free {
+            "default".to_string()
+        } else {
+            let base = instance_name_for(&hqp.name);
+            let mut name = base.clone();
+            let mut n = 2;
+            while self.get(&name).await.is_some() {
+                name = format!("{}-{}", base, n);
+                n += 1;
+            }
+            name
+        };
+        tracing::info!(
+            "Registering discovered HQPlayer {} at {} as instance {}",
+            hqp.name,
+            hqp.host,
+            name
+        );
+        self.add_instance(name, hqp.host.clone(), Some(hqp.port), None, None, None)
+            .await;
+    }
 }
 
 // =============================================================================
@@ -1998,22 +3030,139 @@ impl HqpInstanceManager {
 // =============================================================================
 
 const ZONE_LINKS_FILE: &str = "hqp-zone-links.json";
+const FORMAT_RULES_FILE: &str = "hqp-format-rules.json";
+const ZONE_FALLBACKS_FILE: &str = "hqp-zone-fallbacks.json";
+const ZONE_VOLUME_FILE: &str = "hqp-zone-volume.json";
+
+/// How often linked instances with fallbacks are health-checked
+const FAILOVER_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long HQPlayer gets to pick up a new track before its source rate is read
+const FORMAT_RULE_SETTLE: Duration = Duration::from_millis(1000);
+/// Minimum time between automatic switches on one link (each one restarts playback)
+const FORMAT_RULE_COOLDOWN: Duration = Duration::from_secs(30);
 
 fn zone_links_path() -> PathBuf {
     get_config_dir().join(ZONE_LINKS_FILE)
 }
 
+fn format_rules_path() -> PathBuf {
+    get_config_dir().join(FORMAT_RULES_FILE)
+}
+
+fn zone_fallbacks_path() -> PathBuf {
+    get_config_dir().join(ZONE_FALLBACKS_FILE)
+}
+
+fn zone_volume_path() -> PathBuf {
+    get_config_dir().join(ZONE_VOLUME_FILE)
+}
+
 /// Zone link info for API responses
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZoneLink {
     pub zone_id: String,
     pub instance: String,
+    /// Instances to fail over to, in order of preference
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
+    /// Fallback in use while the primary instance is unreachable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    /// Volume commands for the zone go to HQPlayer
+    #[serde(default)]
+    pub volume: bool,
+}
+
+/// Source format of the track HQPlayer is playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Pcm,
+    Dsd,
+}
+
+impl SourceFormat {
+    /// DSD sources arrive at DSD64 (2.8224 MHz) or above
+    pub fn from_rate(rate: u32) -> Self {
+        if rate >= 2_822_400 {
+            Self::Dsd
+        } else {
+            Self::Pcm
+        }
+    }
+}
+
+/// Automatic pipeline switch for a linked zone, e.g. "DSD -> SDM, ASDM7EC"
+/// or "44.1k PCM -> poly-sinc-gauss-long"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HqpFormatRule {
+    /// Source format to match (any if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<SourceFormat>,
+    /// Source sample rate in Hz to match (any if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<u32>,
+    /// Configuration profile to load first (needs web credentials)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Mode, filter and shaper names as listed by HQPlayer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shaper: Option<String>,
+}
+
+impl HqpFormatRule {
+    /// Whether the rule applies to a source
+    pub fn matches(&self, format: SourceFormat, rate: u32) -> bool {
+        self.format.is_none_or(|f| f == format) && self.rate.is_none_or(|r| r == rate)
+    }
+
+    fn has_action(&self) -> bool {
+        self.profile.is_some()
+            || self.mode.is_some()
+            || self.filter.is_some()
+            || self.shaper.is_some()
+    }
+}
+
+/// Rule engine state for one linked zone
+#[derive(Default)]
+struct FormatRuleState {
+    /// Last (title, album) seen; Roon republishes now playing on every zone change
+    track: Option<(String, String)>,
+    /// Bumped on every new track so superseded evaluations can be dropped
+    generation: u64,
+    /// Rule last applied, the album it was applied for, and when
+    applied: Option<(HqpFormatRule, String, std::time::Instant)>,
+}
+
+/// A new track on a linked zone with rules, waiting to be evaluated
+struct PendingFormatRule {
+    link_zone: String,
+    instance: String,
+    album: String,
+    generation: u64,
+    /// Time left in the cooldown of the previous switch
+    cooldown: Option<Duration>,
 }
 
 /// Service for managing zone-to-HQPlayer-instance links
 pub struct HqpZoneLinkService {
     links: Arc<RwLock<HashMap<String, String>>>, // zone_id -> instance_name
     instances: Arc<HqpInstanceManager>,
+    /// Format rules per linked zone, first match wins
+    rules: Arc<RwLock<HashMap<String, Vec<HqpFormatRule>>>>,
+    rule_state: Mutex<HashMap<String, FormatRuleState>>,
+    /// Fallback instances per linked zone, in order of preference
+    fallbacks: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    /// Fallback currently standing in for an unreachable primary, per zone
+    failed_over: RwLock<HashMap<String, String>>,
+    /// Linked zones whose volume is routed to HQPlayer
+    volume: Arc<RwLock<HashSet<String>>>,
 }
 
 impl HqpZoneLinkService {
@@ -2022,8 +3171,16 @@ impl HqpZoneLinkService {
         let service = Self {
             links: Arc::new(RwLock::new(HashMap::new())),
             instances,
+            rules: Arc::new(RwLock::new(HashMap::new())),
+            rule_state: Mutex::new(HashMap::new()),
+            fallbacks: Arc::new(RwLock::new(HashMap::new())),
+            failed_over: RwLock::new(HashMap::new()),
+            volume: Arc::new(RwLock::new(HashSet::new())),
         };
         service.load_links_sync();
+        service.load_rules_sync();
+        service.load_fallbacks_sync();
+        service.load_volume_sync();
         service
     }
 
@@ -2037,6 +3194,9 @@ impl HqpZoneLinkService {
         match std::fs::read_to_string(&path) {
             Ok(content) => match serde_json::from_str::<HashMap<String, String>>(&content) {
                 Ok(saved_links) => {
+                    if let Ok(mut linked) = self.instances.linked.try_write() {
+                        *linked = saved_links.values().cloned().collect();
+                    }
                     if let Ok(mut links) = self.links.try_write() {
                         *links = saved_links;
                         tracing::info!("Loaded {} HQP zone links from disk", links.len());
@@ -2048,8 +3208,10 @@ impl HqpZoneLinkService {
         }
     }
 
-    /// Save links to disk
+    /// Save links to disk (and tell the instance manager which instances
+    /// are now linked)
     async fn save_links(&self) {
+        self.update_linked_instances().await;
         let links = self.links.read().await;
         let path = zone_links_path();
 
@@ -2069,6 +3231,134 @@ impl HqpZoneLinkService {
         }
     }
 
+    /// Load format rules from disk synchronously (at startup)
+    fn load_rules_sync(&self) {
+        let path = format_rules_path();
+        if !path.exists() {
+            return;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                match serde_json::from_str::<HashMap<String, Vec<HqpFormatRule>>>(&content) {
+                    Ok(saved_rules) => {
+                        if let Ok(mut rules) = self.rules.try_write() {
+                            *rules = saved_rules;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to parse HQP format rules: {}", e),
+                }
+            }
+            Err(e) => tracing::warn!("Failed to read HQP format rules: {}", e),
+        }
+    }
+
+    /// Save format rules to disk
+    async fn save_rules(&self) {
+        let rules = self.rules.read().await;
+        let path = format_rules_path();
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        match serde_json::to_string_pretty(&*rules) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save HQP format rules: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize HQP format rules: {}", e),
+        }
+    }
+
+    /// Tell the instance manager which instances are serving a zone (primaries,
+    /// or the fallbacks standing in for them)
+    async fn update_linked_instances(&self) {
+        let mut names: HashSet<String> = self.links.read().await.values().cloned().collect();
+        names.extend(self.failed_over.read().await.values().cloned());
+        self.instances.set_linked_instances(names).await;
+    }
+
+    /// Load fallbacks from disk synchronously (at startup)
+    fn load_fallbacks_sync(&self) {
+        let path = zone_fallbacks_path();
+        if !path.exists() {
+            return;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<HashMap<String, Vec<String>>>(&content) {
+                Ok(saved) => {
+                    if let Ok(mut fallbacks) = self.fallbacks.try_write() {
+                        *fallbacks = saved;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to parse HQP zone fallbacks: {}", e),
+            },
+            Err(e) => tracing::warn!("Failed to read HQP zone fallbacks: {}", e),
+        }
+    }
+
+    /// Save fallbacks to disk
+    async fn save_fallbacks(&self) {
+        let fallbacks = self.fallbacks.read().await;
+        let path = zone_fallbacks_path();
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        match serde_json::to_string_pretty(&*fallbacks) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save HQP zone fallbacks: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize HQP zone fallbacks: {}", e),
+        }
+    }
+
+    /// Load volume routing from disk synchronously (at startup)
+    fn load_volume_sync(&self) {
+        let path = zone_volume_path();
+        if !path.exists() {
+            return;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<HashSet<String>>(&content) {
+                Ok(saved) => {
+                    if let Ok(mut volume) = self.volume.try_write() {
+                        *volume = saved;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to parse HQP zone volume routing: {}", e),
+            },
+            Err(e) => tracing::warn!("Failed to read HQP zone volume routing: {}", e),
+        }
+    }
+
+    /// Save volume routing to disk
+    async fn save_volume(&self) {
+        let mut zone_ids: Vec<String> = self.volume.read().await.iter().cloned().collect();
+        zone_ids.sort();
+        let path = zone_volume_path();
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        match serde_json::to_string_pretty(&zone_ids) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save HQP zone volume routing: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize HQP zone volume routing: {}", e),
+        }
+    }
+
     /// Link a zone to an HQP instance
     pub async fn link_zone(&self, zone_id: String, instance_name: String) -> Result<()> {
         // Verify instance exists
@@ -2080,6 +3370,7 @@ impl HqpZoneLinkService {
             let mut links = self.links.write().await;
             links.insert(zone_id.clone(), instance_name.clone());
         }
+        self.failed_over.write().await.remove(&zone_id);
 
         self.save_links().await;
         tracing::info!("Zone {} linked to HQP instance {}", zone_id, instance_name);
@@ -2094,31 +3385,262 @@ impl HqpZoneLinkService {
         };
 
         if was_linked {
+            self.failed_over.write().await.remove(zone_id);
             self.save_links().await;
+            self.drop_rules(&[zone_id.to_string()]).await;
+            self.drop_fallbacks(&[zone_id.to_string()], None).await;
+            self.drop_volume(&[zone_id.to_string()]).await;
             tracing::info!("Zone {} unlinked from HQP", zone_id);
         }
 
         was_linked
     }
 
-    /// Get the HQP instance name for a zone
+    /// Get the HQP instance serving a zone: its linked instance, or the
+    /// fallback standing in while that one is unreachable
     pub async fn get_instance_for_zone(&self, zone_id: &str) -> Option<String> {
-        let links = self.links.read().await;
-        links.get(zone_id).cloned()
+        let primary = self.links.read().await.get(zone_id).cloned()?;
+        match self.failed_over.read().await.get(zone_id) {
+            Some(fallback) => Some(fallback.clone()),
+            None => Some(primary),
+        }
     }
 
     /// Get all zone links
     pub async fn get_links(&self) -> Vec<ZoneLink> {
         let links = self.links.read().await;
+        let fallbacks = self.fallbacks.read().await;
+        let failed_over = self.failed_over.read().await;
+        let volume = self.volume.read().await;
         links
             .iter()
             .map(|(zone_id, instance)| ZoneLink {
                 zone_id: zone_id.clone(),
                 instance: instance.clone(),
+                fallbacks: fallbacks.get(zone_id).cloned().unwrap_or_default(),
+                active: failed_over.get(zone_id).cloned(),
+                volume: volume.contains(zone_id),
             })
             .collect()
     }
 
+    /// Route a linked zone's volume to HQPlayer, or give it back to the
+    /// zone's own source
+    ///
+    /// While routed, the zone reports HQPlayer's volume and range (in dB)
+    /// through `ZoneVolumeDelegated`, like a zone with a volume output.
+    pub async fn set_volume_routing(&self, zone_id: &str, enabled: bool) -> Result<()> {
+        if self.get_instance_for_zone(zone_id).await.is_none() {
+            return Err(anyhow!("Zone is not linked to HQPlayer: {}", zone_id));
+        }
+
+        let changed = {
+            let mut volume = self.volume.write().await;
+            if enabled {
+                volume.insert(zone_id.to_string())
+            } else {
+                volume.remove(zone_id)
+            }
+        };
+        if !changed {
+            return Ok(());
+        }
+        self.save_volume().await;
+
+        if enabled {
+            tracing::info!("Zone {} volume routed to HQPlayer", zone_id);
+            self.publish_volume_control(zone_id).await;
+        } else {
+            tracing::info!("Zone {} volume back with its source", zone_id);
+            self.instances.bus.publish(BusEvent::ZoneVolumeDelegated {
+                zone_id: zone_id.to_string(),
+                volume_control: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether a zone's volume is routed to HQPlayer
+    pub async fn routes_volume(&self, zone_id: &str) -> bool {
+        self.volume.read().await.contains(zone_id)
+    }
+
+    /// HQPlayer's volume control for a zone whose volume is routed to it
+    ///
+    /// Returns `None` if the zone's volume isn't routed or HQPlayer is unreachable.
+    pub async fn volume_control_for_zone(&self, zone_id: &str) -> Option<BusVolumeControl> {
+        if !self.routes_volume(zone_id).await {
+            return None;
+        }
+        let adapter = self
+            .instances
+            .get(&self.get_instance_for_zone(zone_id).await?)
+            .await?;
+        match adapter.get_volume_control().await {
+            Ok(vc) => Some(vc),
+            Err(e) => {
+                tracing::debug!("Failed to query HQPlayer volume for {}: {}", zone_id, e);
+                None
+            }
+        }
+    }
+
+    /// Send a volume/mute action for a routed zone to HQPlayer
+    ///
+    /// `action` is a surface action (see `delegated_action`); absolute values
+    /// are in dB and clamped to HQPlayer's range.
+    pub async fn control_volume(
+        &self,
+        zone_id: &str,
+        action: &str,
+        value: Option<f32>,
+    ) -> Result<()> {
+        if !self.routes_volume(zone_id).await {
+            return Err(anyhow!(
+                "Zone volume is not routed to HQPlayer: {}",
+                zone_id
+            ));
+        }
+        let instance = self
+            .get_instance_for_zone(zone_id)
+            .await
+            .ok_or_else(|| anyhow!("Zone is not linked to HQPlayer: {}", zone_id))?;
+        let adapter = self.instances.require(&instance).await?;
+
+        match delegated_action(action) {
+            Some("vol_up") => adapter.volume_up().await?,
+            Some("vol_down") => adapter.volume_down().await?,
+            Some("vol_abs") => {
+                let value = value.ok_or_else(|| anyhow!("vol_abs needs a value"))?;
+                let range = adapter.get_volume_control().await?;
+                adapter
+                    .set_volume(value.clamp(range.min, range.max).round() as i32)
+                    .await?
+            }
+            Some("mute") => adapter.set_mute(true).await?,
+            Some("unmute") => adapter.set_mute(false).await?,
+            Some(_) => adapter.volume_mute().await?,
+            None => return Err(anyhow!("Not a volume action: {}", action)),
+        }
+
+        self.publish_volume_control(zone_id).await;
+        Ok(())
+    }
+
+    /// Publish HQPlayer's volume control for every routed zone
+    ///
+    /// Called at startup so zone state reflects HQPlayer's volume before the
+    /// first volume change.
+    pub async fn refresh_volume(&self) {
+        let zone_ids: Vec<String> = self.volume.read().await.iter().cloned().collect();
+        for zone_id in zone_ids {
+            self.publish_volume_control(&zone_id).await;
+        }
+    }
+
+    async fn publish_volume_control(&self, zone_id: &str) {
+        if let Some(vc) = self.volume_control_for_zone(zone_id).await {
+            self.instances.bus.publish(BusEvent::ZoneVolumeDelegated {
+                zone_id: zone_id.to_string(),
+                volume_control: Some(vc),
+            });
+        }
+    }
+
+    /// Give unlinked zones' volume back to their source
+    async fn drop_volume(&self, zone_ids: &[String]) {
+        let removed: Vec<&String> = {
+            let mut volume = self.volume.write().await;
+            zone_ids
+                .iter()
+                .filter(|zone_id| volume.remove(zone_id.as_str()))
+                .collect()
+        };
+        if removed.is_empty() {
+            return;
+        }
+        self.save_volume().await;
+        for zone_id in removed {
+            self.instances.bus.publish(BusEvent::ZoneVolumeDelegated {
+                zone_id: zone_id.clone(),
+                volume_control: None,
+            });
+        }
+    }
+
+    /// Set the instances a linked zone fails over to, in order of preference
+    /// (an empty list removes them)
+    pub async fn set_fallbacks(&self, zone_id: &str, fallbacks: Vec<String>) -> Result<()> {
+        let primary = self
+            .links
+            .read()
+            .await
+            .get(zone_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Zone is not linked to HQPlayer: {}", zone_id))?;
+        for (i, name) in fallbacks.iter().enumerate() {
+            if self.instances.get(name).await.is_none() {
+                return Err(anyhow!("Unknown HQP instance: {}", name));
+            }
+            if *name == primary || fallbacks[..i].contains(name) {
+                return Err(anyhow!("Fallback listed twice: {}", name));
+            }
+        }
+
+        {
+            let mut all = self.fallbacks.write().await;
+            if fallbacks.is_empty() {
+                all.remove(zone_id);
+            } else {
+                all.insert(zone_id.to_string(), fallbacks.clone());
+            }
+        }
+        // A fallback that's no longer listed stops standing in; the next
+        // check picks a new one if the primary is still down
+        let dropped = {
+            let mut failed_over = self.failed_over.write().await;
+            match failed_over.get(zone_id) {
+                Some(active) if !fallbacks.contains(active) => failed_over.remove(zone_id),
+                _ => None,
+            }
+        };
+        if let Some(from) = dropped {
+            self.update_linked_instances().await;
+            self.instances.bus.publish(BusEvent::HqpLinkFailover {
+                zone_id: zone_id.to_string(),
+                from,
+                to: primary,
+            });
+        }
+        self.save_fallbacks().await;
+        Ok(())
+    }
+
+    /// Forget the fallbacks of unlinked zones, or (with `instance`) remove a
+    /// deleted instance from every zone's fallbacks
+    async fn drop_fallbacks(&self, zone_ids: &[String], instance: Option<&str>) {
+        let changed = {
+            let mut fallbacks = self.fallbacks.write().await;
+            let mut changed = zone_ids
+                .iter()
+                .filter(|zone_id| fallbacks.remove(zone_id.as_str()).is_some())
+                .count()
+                > 0;
+            if let Some(instance) = instance {
+                for list in fallbacks.values_mut() {
+                    let before = list.len();
+                    list.retain(|name| name != instance);
+                    changed |= list.len() != before;
+                }
+                fallbacks.retain(|_, list| !list.is_empty());
+            }
+            changed
+        };
+        if changed {
+            self.save_fallbacks().await;
+        }
+    }
+
     /// Get HQP pipeline data for a linked zone
     pub async fn get_pipeline_for_zone(&self, zone_id: &str) -> Option<PipelineStatus> {
         let instance_name = self.get_instance_for_zone(zone_id).await?;
@@ -2147,14 +3669,29 @@ impl HqpZoneLinkService {
             .collect();
 
         let count = zones_to_remove.len();
-        for zone_id in zones_to_remove {
-            links.remove(&zone_id);
+        for zone_id in &zones_to_remove {
+            links.remove(zone_id);
         }
 
         drop(links);
 
-        if count > 0 {
+        let failed_over_to_instance = {
+            let mut failed_over = self.failed_over.write().await;
+            let before = failed_over.len();
+            failed_over.retain(|zone_id, active| {
+                active != instance_name && !zones_to_remove.contains(zone_id)
+            });
+            failed_over.len() != before
+        };
+        self.drop_fallbacks(&zones_to_remove, Some(instance_name))
+            .await;
+
+        if count > 0 || failed_over_to_instance {
             self.save_links().await;
+        }
+        if count > 0 {
+            self.drop_rules(&zones_to_remove).await;
+            self.drop_volume(&zones_to_remove).await;
             tracing::info!(
                 "Removed {} zone links for deleted instance {}",
                 count,
@@ -2166,19 +3703,19 @@ impl HqpZoneLinkService {
     }
 
     /// Auto-correct links when instances are renamed (called after loading)
+    ///
+    /// Links to an unknown instance are moved to the only instance, if there
+    /// is just one. Fallbacks get the same correction: a fallback that's
+    /// unknown (or ends up the link's own instance) is dropped, so the link
+    /// never fails over to an instance that doesn't exist.
     pub async fn auto_correct_links(&self) -> bool {
         let instances = self.instances.list_instances().await;
-        if instances.len() != 1 {
-            return false; // Can only auto-correct with single instance
-        }
-
-        let single_instance = &instances[0].name;
+        let instance_names: Vec<String> = instances.iter().map(|i| i.name.clone()).collect();
         let mut corrected = false;
 
-        {
+        if let [single] = instances.as_slice() {
+            let single_instance = &single.name;
             let mut links = self.links.write().await;
-            let instance_names: Vec<String> = instances.iter().map(|i| i.name.clone()).collect();
-
             for (zone_id, instance_name) in links.iter_mut() {
                 if !instance_names.contains(instance_name) {
                     tracing::warn!(
@@ -2192,12 +3729,400 @@ impl HqpZoneLinkService {
                 }
             }
         }
-
         if corrected {
             self.save_links().await;
         }
 
-        corrected
+        let fallbacks_corrected = {
+            let links = self.links.read().await;
+            let mut fallbacks = self.fallbacks.write().await;
+            let mut changed = false;
+            for (zone_id, list) in fallbacks.iter_mut() {
+                let before = list.len();
+                list.retain(|name| {
+                    instance_names.contains(name) && links.get(zone_id) != Some(name)
+                });
+                if list.len() != before {
+                    tracing::warn!(
+                        "Auto-correcting fallbacks of zone link {} to {:?}",
+                        zone_id,
+                        list
+                    );
+                    changed = true;
+                }
+            }
+            fallbacks.retain(|_, list| !list.is_empty());
+            changed
+        };
+        if fallbacks_corrected {
+            self.save_fallbacks().await;
+        }
+
+        // Links standing in on an instance that's gone return to their primary
+        let reset = {
+            let links = self.links.read().await;
+            let mut failed_over = self.failed_over.write().await;
+            let before = failed_over.len();
+            failed_over.retain(|zone_id, active| {
+                instance_names.contains(active) && links.get(zone_id) != Some(active)
+            });
+            failed_over.len() != before
+        };
+        if reset {
+            self.update_linked_instances().await;
+        }
+
+        corrected || fallbacks_corrected
+    }
+
+    /// Health-check linked instances that have fallbacks in the background
+    ///
+    /// Runs until the instance manager is stopped.
+    pub fn start_failover(self: Arc<Self>) {
+        let shutdown = self.instances.shutdown.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(FAILOVER_INTERVAL);
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tick.tick() => self.check_failover().await,
+                }
+            }
+        });
+    }
+
+    /// Move links whose primary instance is unreachable to the first healthy
+    /// fallback, and back once the primary answers again
+    ///
+    /// Each move is announced with `HqpLinkFailover`. A link with no healthy
+    /// instance at all stays where it is.
+    pub async fn check_failover(&self) {
+        let candidates: Vec<(String, String, Vec<String>)> = {
+            let links = self.links.read().await;
+            let fallbacks = self.fallbacks.read().await;
+            let failed_over = self.failed_over.read().await;
+            links
+                .iter()
+                .filter_map(|(zone_id, primary)| {
+                    let mut order = vec![primary.clone()];
+                    order.extend(fallbacks.get(zone_id).cloned().unwrap_or_default());
+                    // Failed-over links are checked even if their fallbacks
+                    // were just cleared, so they can move back
+                    (order.len() > 1 || failed_over.contains_key(zone_id))
+                        .then(|| (zone_id.clone(), primary.clone(), order))
+                })
+                .collect()
+        };
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut health: HashMap<String, bool> = HashMap::new();
+        let mut moved = false;
+        for (zone_id, primary, order) in candidates {
+            let current = self
+                .failed_over
+                .read()
+                .await
+                .get(&zone_id)
+                .cloned()
+                .unwrap_or_else(|| primary.clone());
+
+            let mut target = None;
+            for name in &order {
+                if self.is_healthy(name, &mut health).await {
+                    target = Some(name.clone());
+                    break;
+                }
+            }
+            let Some(target) = target else {
+                tracing::debug!("No reachable HQP instance for zone {}", zone_id);
+                continue;
+            };
+            if target == current {
+                continue;
+            }
+
+            {
+                let mut failed_over = self.failed_over.write().await;
+                if target == primary {
+                    failed_over.remove(&zone_id);
+                } else {
+                    failed_over.insert(zone_id.clone(), target.clone());
+                }
+            }
+            if target == primary {
+                tracing::info!("Zone {} back on HQP instance {}", zone_id, target);
+            } else {
+                tracing::warn!(
+                    "HQP instance {} unreachable, zone {} failed over to {}",
+                    current,
+                    zone_id,
+                    target
+                );
+            }
+            self.instances.bus.publish(BusEvent::HqpLinkFailover {
+                zone_id: zone_id.clone(),
+                from: current,
+                to: target,
+            });
+            // Routed volume now comes from the other instance
+            self.publish_volume_control(&zone_id).await;
+            moved = true;
+        }
+
+        if moved {
+            self.update_linked_instances().await;
+        }
+    }
+
+    /// Whether an instance answers (one round trip per check, cached in `health`)
+    async fn is_healthy(&self, name: &str, health: &mut HashMap<String, bool>) -> bool {
+        if let Some(&healthy) = health.get(name) {
+            return healthy;
+        }
+        let healthy = match self.instances.get(name).await {
+            Some(adapter) => adapter.is_configured().await && adapter.get_state().await.is_ok(),
+            None => false,
+        };
+        health.insert(name.to_string(), healthy);
+        healthy
+    }
+
+    /// Format rules for every linked zone that has some
+    pub async fn get_all_rules(&self) -> HashMap<String, Vec<HqpFormatRule>> {
+        self.rules.read().await.clone()
+    }
+
+    /// Replace a linked zone's format rules (an empty list removes them)
+    pub async fn set_rules(&self, zone_id: &str, rules: Vec<HqpFormatRule>) -> Result<()> {
+        if self.get_instance_for_zone(zone_id).await.is_none() {
+            return Err(anyhow!("Zone is not linked to HQPlayer: {}", zone_id));
+        }
+        if let Some(i) = rules.iter().position(|r| !r.has_action()) {
+            return Err(anyhow!(
+                "Rule {} needs a profile, mode, filter or shaper",
+                i + 1
+            ));
+        }
+
+        {
+            let mut all = self.rules.write().await;
+            if rules.is_empty() {
+                all.remove(zone_id);
+            } else {
+                all.insert(zone_id.to_string(), rules);
+            }
+        }
+        self.rule_state.lock().await.remove(zone_id);
+        self.save_rules().await;
+        Ok(())
+    }
+
+    /// Forget the rules of unlinked zones
+    async fn drop_rules(&self, zone_ids: &[String]) {
+        let removed = {
+            let mut rules = self.rules.write().await;
+            zone_ids
+                .iter()
+                .filter(|zone_id| rules.remove(zone_id.as_str()).is_some())
+                .count()
+        };
+        if removed > 0 {
+            self.save_rules().await;
+        }
+    }
+
+    /// Evaluate format rules whenever a linked zone starts a new track
+    ///
+    /// Runs until the instance manager is stopped. Evaluations run in their
+    /// own tasks so settling and pipeline switches don't hold up the bus.
+    pub fn start_format_rules(self: Arc<Self>) {
+        let mut rx = self.instances.bus.subscribe();
+        let shutdown = self.instances.shutdown.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    event = rx.recv() => match event {
+                        Ok(BusEvent::NowPlayingChanged {
+                            zone_id,
+                            title,
+                            album,
+                            ..
+                        }) => {
+                            let title = title.unwrap_or_default();
+                            let album = album.unwrap_or_default();
+                            if let Some(pending) =
+                                self.track_started(&zone_id, &title, &album).await
+                            {
+                                self.clone().spawn_format_rules(pending);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }
+        });
+    }
+
+    /// Apply the first rule matching the source HQPlayer is now playing,
+    /// returning it if the pipeline was switched
+    ///
+    /// Nothing is switched while the same album keeps playing or if the
+    /// matching rule is the one already in effect. A new track within the
+    /// cooldown of the previous switch is evaluated once the cooldown ends
+    /// (unless another track starts first), so this returns `None` for it.
+    pub async fn apply_format_rules(
+        self: &Arc<Self>,
+        zone_id: &str,
+        title: &str,
+        album: &str,
+    ) -> Result<Option<HqpFormatRule>> {
+        let Some(pending) = self.track_started(zone_id, title, album).await else {
+            return Ok(None);
+        };
+        if pending.cooldown.is_some() {
+            self.clone().spawn_format_rules(pending);
+            return Ok(None);
+        }
+        self.run_format_rules(pending).await
+    }
+
+    /// Record a now-playing change, returning the evaluation it needs (if any)
+    async fn track_started(
+        &self,
+        zone_id: &str,
+        title: &str,
+        album: &str,
+    ) -> Option<PendingFormatRule> {
+        // Roon announces bare zone IDs; links are usually made with the prefix
+        let (link_zone, instance) = match self.get_instance_for_zone(zone_id).await {
+            Some(instance) => (zone_id.to_string(), instance),
+            None => {
+                let prefixed = format!("roon:{}", zone_id);
+                let instance = self.get_instance_for_zone(&prefixed).await?;
+                (prefixed, instance)
+            }
+        };
+        if !self.rules.read().await.contains_key(&link_zone) {
+            return None;
+        }
+
+        let mut states = self.rule_state.lock().await;
+        let state = states.entry(link_zone.clone()).or_default();
+        let track = (title.to_string(), album.to_string());
+        if state.track.as_ref() == Some(&track) {
+            return None;
+        }
+        state.track = Some(track);
+        state.generation += 1;
+        let mut cooldown = None;
+        if let Some((_, applied_album, at)) = &state.applied {
+            if !album.is_empty() && applied_album == album {
+                return None;
+            }
+            cooldown = FORMAT_RULE_COOLDOWN.checked_sub(at.elapsed());
+        }
+        Some(PendingFormatRule {
+            link_zone,
+            instance,
+            album: album.to_string(),
+            generation: state.generation,
+            cooldown,
+        })
+    }
+
+    /// Evaluate a pending track in the background
+    fn spawn_format_rules(self: Arc<Self>, pending: PendingFormatRule) {
+        let shutdown = self.instances.shutdown.clone();
+        let link_zone = pending.link_zone.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                result = self.run_format_rules(pending) => {
+                    if let Err(e) = result {
+                        tracing::warn!("HQP format rule failed for {}: {}", link_zone, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Wait for the track to settle (or the cooldown to end), then switch to
+    /// the first matching rule unless a newer track has superseded this one
+    async fn run_format_rules(&self, pending: PendingFormatRule) -> Result<Option<HqpFormatRule>> {
+        let PendingFormatRule {
+            link_zone,
+            instance,
+            album,
+            generation,
+            cooldown,
+        } = pending;
+        tokio::time::sleep(cooldown.unwrap_or_default().max(FORMAT_RULE_SETTLE)).await;
+
+        let Some(rules) = self.rules.read().await.get(&link_zone).cloned() else {
+            return Ok(None);
+        };
+        let adapter = self
+            .instances
+            .get(&instance)
+            .await
+            .ok_or_else(|| anyhow!("Unknown HQP instance: {}", instance))?;
+        let status = adapter.get_playback_status().await?;
+        if status.samplerate == 0 {
+            return Ok(None);
+        }
+        let format = SourceFormat::from_rate(status.samplerate);
+        let Some(rule) = rules
+            .into_iter()
+            .find(|r| r.matches(format, status.samplerate))
+        else {
+            return Ok(None);
+        };
+
+        {
+            let mut states = self.rule_state.lock().await;
+            let state = states.entry(link_zone.clone()).or_default();
+            if state.generation != generation {
+                return Ok(None);
+            }
+            if let Some((applied, applied_album, _)) = &mut state.applied {
+                if *applied == rule {
+                    // Already in effect, no need to restart HQPlayer
+                    *applied_album = album;
+                    return Ok(None);
+                }
+            }
+        }
+
+        if let Some(profile) = &rule.profile {
+            adapter.load_profile(profile).await?;
+        }
+        adapter
+            .set_pipeline_by_name(
+                rule.mode.as_deref(),
+                rule.filter.as_deref(),
+                rule.shaper.as_deref(),
+            )
+            .await?;
+        tracing::info!(
+            "Applied HQP format rule on {} for {:?} {} Hz",
+            link_zone,
+            format,
+            status.samplerate
+        );
+        self.rule_state
+            .lock()
+            .await
+            .entry(link_zone)
+            .or_default()
+            .applied = Some((rule.clone(), album, std::time::Instant::now()));
+        Ok(Some(rule))
     }
 }
 
@@ -2208,6 +4133,12 @@ impl HqpZoneLinkService {
 const HQP_MULTICAST_ADDR: Ipv4Addr = Ipv4Addr::new(239, 192, 0, 199);
 const HQP_DISCOVERY_PORT: u16 = 4321;
 const HQP_DISCOVERY_TIMEOUT_MS: u64 = 3000;
+const DISCOVERY_FILE: &str = "hqp-discovery.json";
+
+/// How often the background discovery probes the network
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(60);
+/// Probes an instance may miss before it's reported lost
+const DISCOVERY_LOST_AFTER: u32 = 2;
 
 /// Discovered HQPlayer instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2219,6 +4150,86 @@ pub struct DiscoveredHqp {
     pub product: Option<String>,
 }
 
+impl DiscoveredHqp {
+    /// What tells HQPlayers apart across address changes
+    fn key(&self) -> String {
+        format!("{}:{}", self.name, self.port)
+    }
+}
+
+/// Background discovery settings (persisted)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HqpDiscoverySettings {
+    /// Add newly discovered instances to the instance manager
+    #[serde(default)]
+    auto_register: bool,
+    /// Last address each HQPlayer (by announced name and port) answered from
+    #[serde(default)]
+    hosts: HashMap<String, String>,
+    /// Names and ports several HQPlayers have answered with at once
+    #[serde(default)]
+    ambiguous: HashSet<String>,
+}
+
+fn discovery_path() -> PathBuf {
+    get_config_dir().join(DISCOVERY_FILE)
+}
+
+fn load_discovery_settings() -> HqpDiscoverySettings {
+    let path = discovery_path();
+    if !path.exists() {
+        return HqpDiscoverySettings::default();
+    }
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Failed to parse HQP discovery settings: {}", e);
+            HqpDiscoverySettings::default()
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to read HQP discovery settings: {}", e);
+            HqpDiscoverySettings::default()
+        }
+    }
+}
+
+fn save_discovery_settings(settings: &HqpDiscoverySettings) {
+    let path = discovery_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(settings) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                tracing::error!("Failed to save HQP discovery settings: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("Failed to serialize HQP discovery settings: {}", e),
+    }
+}
+
+/// Background discovery state for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct HqpDiscoveryStatus {
+    pub auto_register: bool,
+    /// Instances currently answering, sorted by name
+    pub instances: Vec<DiscoveredHqp>,
+}
+
+/// Instance name for a discovered HQPlayer ("Living Room PC" -> "living-room-pc")
+fn instance_name_for(announced: &str) -> String {
+    let slug = announced
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "hqplayer".to_string()
+    } else {
+        slug
+    }
+}
+
 /// Discover HQPlayer instances on the network via UDP multicast
 pub async fn discover_hqplayers(timeout_ms: Option<u64>) -> Result<Vec<DiscoveredHqp>> {
     let timeout_duration = Duration::from_millis(timeout_ms.unwrap_or(HQP_DISCOVERY_TIMEOUT_MS));
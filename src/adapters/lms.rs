@@ -8,35 +8,147 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::net::UdpSocket;
 use tokio::sync::RwLock;
-use tokio::time::interval;
+use tokio::time::{interval, timeout};
 use tokio_util::sync::CancellationToken;
 
+use crate::adapters::browse::{
+    BrowseInputPrompt, BrowseItem, BrowseItemHint, BrowseList, BrowseResult, PlayMode,
+};
 use crate::bus::{BusEvent, PlaybackState, SharedBus, VolumeControl, Zone};
 use crate::config::get_config_dir;
 
 const LMS_CONFIG_FILE: &str = "lms-config.json";
 
-/// Saved config for persistence
+/// Name of the instance configured by the single-server settings
+pub const DEFAULT_INSTANCE: &str = "default";
+
+fn default_instance_name() -> String {
+    DEFAULT_INSTANCE.to_string()
+}
+
+/// Saved config for one LMS server
+///
+/// The config file holds an array of these. The legacy single-object format
+/// (no `name`) loads as the default instance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct SavedLmsConfig {
-    host: String,
-    port: u16,
+pub struct LmsInstanceConfig {
+    #[serde(default = "default_instance_name")]
+    pub name: String,
+    pub host: String,
+    pub port: u16,
     #[serde(skip_serializing_if = "Option::is_none")]
-    username: Option<String>,
+    pub username: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    password: Option<String>,
+    pub password: Option<String>,
 }
 
 fn config_path() -> PathBuf {
     get_config_dir().join(LMS_CONFIG_FILE)
 }
 
+/// Load all LMS instance configs from disk
+pub fn load_lms_configs() -> Vec<LmsInstanceConfig> {
+    let path = config_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            // Try parsing as array first
+            if let Ok(configs) = serde_json::from_str::<Vec<LmsInstanceConfig>>(&content) {
+                return configs;
+            }
+
+            // Fall back to single-object format (legacy)
+            match serde_json::from_str::<LmsInstanceConfig>(&content) {
+                Ok(single) => vec![single],
+                Err(e) => {
+                    tracing::warn!("Failed to parse LMS config: {}", e);
+                    Vec::new()
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to read LMS config: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Save all LMS instance configs to disk
+pub fn save_lms_configs(configs: &[LmsInstanceConfig]) -> bool {
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    match serde_json::to_string_pretty(configs) {
+        Ok(json) => match std::fs::write(&path, json) {
+            Ok(()) => {
+                tracing::info!("Saved LMS config ({} instances)", configs.len());
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to save LMS config: {}", e);
+                false
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to serialize LMS config: {}", e);
+            false
+        }
+    }
+}
+
+/// Zone ID prefix for an instance's players
+///
+/// The default instance keeps the plain `lms:` prefix so existing zone IDs
+/// (knob bindings, HQPlayer links) stay valid; named instances use
+/// `lms:<instance>:`.
+fn zone_prefix(instance: &str) -> String {
+    if instance == DEFAULT_INSTANCE {
+        "lms:".to_string()
+    } else {
+        format!("lms:{}:", instance)
+    }
+}
+
+/// Check an instance name can be embedded in zone IDs
+///
+/// Names must not be mistaken for the first octet of a player MAC address,
+/// which is how default-instance zone IDs (`lms:00:04:20:...`) begin.
+pub fn validate_instance_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(anyhow!("Instance name is required"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow!(
+            "Instance name may only contain letters, digits, '-' and '_'"
+        ));
+    }
+    if name.len() == 2 && name.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!(
+            "Instance name '{}' is ambiguous with player MAC addresses",
+            name
+        ));
+    }
+    Ok(())
+}
+
 const DEFAULT_PORT: u16 = 9000;
 const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Default page size for browse requests
+pub const BROWSE_PAGE_SIZE: usize = 100;
 
 /// Shared JSON-RPC client operations for LMS
 /// Extracted to avoid code duplication between LmsAdapter and the polling task
@@ -150,6 +262,24 @@ impl LmsRpc {
                     .or_else(|| v.as_i64().map(|n| n.to_string()))
             });
 
+        // Synced players report their group's master and the other members
+        let sync_master = result
+            .get("sync_master")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+        let sync_slaves = result
+            .get("sync_slaves")
+            .and_then(|v| v.as_str())
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| id.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Ok(LmsPlayer {
             playerid: player_id.to_string(),
             state: state.to_string(),
@@ -190,6 +320,8 @@ impl LmsRpc {
             artwork_track_id: artwork_id.clone(),
             coverid: artwork_id,
             artwork_url,
+            sync_master,
+            sync_slaves,
             ..Default::default()
         })
     }
@@ -255,6 +387,11 @@ pub struct LmsPlayer {
     pub artwork_track_id: Option<String>,
     pub coverid: Option<String>,
     pub artwork_url: Option<String>,
+    // Sync group fields
+    #[serde(default)]
+    pub sync_master: Option<String>,
+    #[serde(default)]
+    pub sync_slaves: Vec<String>,
 }
 
 impl Default for LmsPlayer {
@@ -279,13 +416,90 @@ impl Default for LmsPlayer {
             artwork_track_id: None,
             coverid: None,
             artwork_url: None,
+            sync_master: None,
+            sync_slaves: Vec::new(),
         }
     }
 }
 
+/// A group of synchronized LMS players
+///
+/// The master's player ID identifies the group; it is also the zone ID
+/// (`lms:<master>`, or `lms:<instance>:<master>` on a named instance) the
+/// group is published under.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LmsSyncGroup {
+    pub group_id: String,
+    pub name: String,
+    /// Group volume (average of the members)
+    pub volume: i32,
+    /// Master first, then the other members
+    pub members: Vec<LmsSyncMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LmsSyncMember {
+    pub playerid: String,
+    pub name: String,
+    pub volume: i32,
+}
+
+/// Derive sync groups from cached player status
+fn sync_groups(players: &HashMap<String, LmsPlayer>) -> Vec<LmsSyncGroup> {
+    let mut member_ids: HashMap<&str, Vec<&str>> = HashMap::new();
+    for player in players.values() {
+        let Some(master) = player.sync_master.as_deref() else {
+            continue;
+        };
+        let members = member_ids.entry(master).or_default();
+        for id in std::iter::once(player.playerid.as_str())
+            .chain(player.sync_slaves.iter().map(String::as_str))
+        {
+            if id != master && !members.contains(&id) {
+                members.push(id);
+            }
+        }
+    }
+
+    let mut groups: Vec<_> = member_ids
+        .into_iter()
+        .filter_map(|(master, slaves)| {
+            let master = players.get(master)?;
+            let mut slaves: Vec<_> = slaves.iter().filter_map(|id| players.get(*id)).collect();
+            if slaves.is_empty() {
+                return None;
+            }
+            slaves.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let members: Vec<_> = std::iter::once(master)
+                .chain(slaves)
+                .map(|p| LmsSyncMember {
+                    playerid: p.playerid.clone(),
+                    name: p.name.clone(),
+                    volume: p.volume,
+                })
+                .collect();
+            let total: i32 = members.iter().map(|m| m.volume).sum();
+            Some(LmsSyncGroup {
+                group_id: master.playerid.clone(),
+                name: members
+                    .iter()
+                    .map(|m| m.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" + "),
+                volume: (total as f32 / members.len() as f32).round() as i32,
+                members,
+            })
+        })
+        .collect();
+    groups.sort_by(|a, b| a.name.cmp(&b.name));
+    groups
+}
+
 /// LMS connection status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LmsStatus {
+    pub instance: String,
     pub connected: bool,
     pub host: Option<String>,
     pub port: u16,
@@ -303,6 +517,7 @@ pub struct LmsPlayerInfo {
 
 /// Internal state
 struct LmsState {
+    instance_name: String,
     host: Option<String>,
     port: u16,
     username: Option<String>,
@@ -310,11 +525,14 @@ struct LmsState {
     connected: bool,
     running: bool,
     players: HashMap<String, LmsPlayer>,
+    /// Zones last published on the bus: zone_id -> (name, state)
+    published_zones: HashMap<String, (String, String)>,
 }
 
 impl Default for LmsState {
     fn default() -> Self {
         Self {
+            instance_name: default_instance_name(),
             host: None,
             port: DEFAULT_PORT,
             username: None,
@@ -322,6 +540,7 @@ impl Default for LmsState {
             connected: false,
             running: false,
             players: HashMap::new(),
+            published_zones: HashMap::new(),
         }
     }
 }
@@ -336,8 +555,17 @@ pub struct LmsAdapter {
 }
 
 impl LmsAdapter {
+    /// Create the default instance (the single-server configuration)
     pub fn new(bus: SharedBus) -> Self {
-        let state = Arc::new(RwLock::new(LmsState::default()));
+        Self::new_instance(bus, DEFAULT_INSTANCE)
+    }
+
+    /// Create a named instance, loading its saved config if any
+    pub fn new_instance(bus: SharedBus, name: &str) -> Self {
+        let state = Arc::new(RwLock::new(LmsState {
+            instance_name: name.to_string(),
+            ..Default::default()
+        }));
         let client = Client::builder()
             .timeout(Duration::from_secs(10))
             .build()
@@ -350,65 +578,55 @@ impl LmsAdapter {
             shutdown: Arc::new(RwLock::new(CancellationToken::new())),
         };
         // Load saved config synchronously at startup
-        adapter.load_config_sync();
+        adapter.load_config_sync(name);
         adapter
     }
 
-    /// Load config from disk (sync, for startup)
-    fn load_config_sync(&self) {
-        let path = config_path();
-        if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(content) => match serde_json::from_str::<SavedLmsConfig>(&content) {
-                    Ok(saved) => {
-                        // Use try_write to avoid async in sync context
-                        if let Ok(mut state) = self.state.try_write() {
-                            state.host = Some(saved.host.clone());
-                            state.port = saved.port;
-                            state.username = saved.username;
-                            state.password = saved.password;
-                            tracing::info!(
-                                "Loaded LMS config from disk: {}:{}",
-                                saved.host,
-                                saved.port
-                            );
-                        }
-                    }
-                    Err(e) => tracing::warn!("Failed to parse LMS config: {}", e),
-                },
-                Err(e) => tracing::warn!("Failed to read LMS config: {}", e),
-            }
+    /// Load this instance's config from disk (sync, for startup)
+    fn load_config_sync(&self, name: &str) {
+        let Some(saved) = load_lms_configs().into_iter().find(|c| c.name == name) else {
+            return;
+        };
+        // Use try_write to avoid async in sync context
+        if let Ok(mut state) = self.state.try_write() {
+            state.host = Some(saved.host.clone());
+            state.port = saved.port;
+            state.username = saved.username;
+            state.password = saved.password;
+            tracing::info!(
+                "Loaded LMS config for {} from disk: {}:{}",
+                name,
+                saved.host,
+                saved.port
+            );
         }
     }
 
-    /// Save config to disk
+    /// Save this instance's config to disk, keeping other instances' entries
     async fn save_config(&self) {
         let state = self.state.read().await;
         if let Some(ref host) = state.host {
-            let saved = SavedLmsConfig {
+            let saved = LmsInstanceConfig {
+                name: state.instance_name.clone(),
                 host: host.clone(),
                 port: state.port,
                 username: state.username.clone(),
                 password: state.password.clone(),
             };
-            let path = config_path();
-            // Ensure config directory exists
-            if let Some(parent) = path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-            match serde_json::to_string_pretty(&saved) {
-                Ok(json) => {
-                    if let Err(e) = std::fs::write(&path, json) {
-                        tracing::error!("Failed to save LMS config: {}", e);
-                    } else {
-                        tracing::info!("Saved LMS config to disk");
-                    }
-                }
-                Err(e) => tracing::error!("Failed to serialize LMS config: {}", e),
+            let mut configs = load_lms_configs();
+            match configs.iter_mut().find(|c| c.name == saved.name) {
+                Some(existing) => *existing = saved,
+                None => configs.push(saved),
             }
+            save_lms_configs(&configs);
         }
     }
 
+    /// Name of this instance
+    pub async fn instance_name(&self) -> String {
+        self.state.read().await.instance_name.clone()
+    }
+
     /// Configure the LMS connection
     pub async fn configure(
         &self,
@@ -438,6 +656,7 @@ impl LmsAdapter {
     pub async fn get_status(&self) -> LmsStatus {
         let state = self.state.read().await;
         LmsStatus {
+            instance: state.instance_name.clone(),
             connected: state.connected,
             host: state.host.clone(),
             port: state.port,
@@ -546,13 +765,18 @@ impl LmsAdapter {
         // Cancel background tasks first
         self.shutdown.read().await.cancel();
 
-        let host = {
+        let (host, removed) = {
             let mut state = self.state.write().await;
             state.connected = false;
             state.running = false;
-            state.host.clone()
+            let removed: Vec<String> = state.published_zones.drain().map(|(id, _)| id).collect();
+            (state.host.clone(), removed)
         };
 
+        for zone_id in removed {
+            self.bus.publish(BusEvent::ZoneRemoved { zone_id });
+        }
+
         if let Some(host) = host {
             self.bus.publish(BusEvent::LmsDisconnected { host });
         }
@@ -703,12 +927,531 @@ impl LmsAdapter {
         let command = if relative { "vol_rel" } else { "vol_abs" };
         self.control(player_id, command, Some(value)).await
     }
+
+    /// Get sync groups (from cached player status)
+    pub async fn get_sync_groups(&self) -> Vec<LmsSyncGroup> {
+        sync_groups(&self.state.read().await.players)
+    }
+
+    /// Get the sync group a player belongs to, if any
+    pub async fn get_sync_group(&self, player_id: &str) -> Option<LmsSyncGroup> {
+        self.get_sync_groups()
+            .await
+            .into_iter()
+            .find(|g| g.members.iter().any(|m| m.playerid == player_id))
+    }
+
+    /// Zone ID a player is published under (its group master's if synced)
+    pub async fn zone_id_for_player(&self, player_id: &str) -> String {
+        let state = self.state.read().await;
+        let group_id = sync_groups(&state.players)
+            .into_iter()
+            .find(|g| g.members.iter().any(|m| m.playerid == player_id))
+            .map(|g| g.group_id);
+        format!(
+            "{}{}",
+            zone_prefix(&state.instance_name),
+            group_id.as_deref().unwrap_or(player_id)
+        )
+    }
+
+    /// Get zones as published on the bus (synced players appear as one zone)
+    pub async fn get_cached_zones(&self) -> Vec<Zone> {
+        let state = self.state.read().await;
+        lms_zones(&state.instance_name, &state.players)
+    }
+
+    /// Sync a player to another player's group
+    pub async fn sync(&self, player_id: &str, target_id: &str) -> Result<()> {
+        if player_id == target_id {
+            return Err(anyhow!("Cannot sync a player to itself"));
+        }
+        // "<target> sync <player>" adds player to target's group
+        self.rpc
+            .execute(Some(target_id), vec![json!("sync"), json!(player_id)])
+            .await?;
+        self.update_players().await
+    }
+
+    /// Remove a player from its sync group
+    pub async fn unsync(&self, player_id: &str) -> Result<()> {
+        self.rpc
+            .execute(Some(player_id), vec![json!("sync"), json!("-")])
+            .await?;
+        self.update_players().await
+    }
+
+    /// Change volume for a zone
+    ///
+    /// For a synced player the whole group moves together, keeping the
+    /// members' relative levels: relative changes apply to every member,
+    /// absolute values set the group (average) volume.
+    pub async fn change_zone_volume(
+        &self,
+        player_id: &str,
+        value: i32,
+        relative: bool,
+    ) -> Result<()> {
+        let Some(group) = self.get_sync_group(player_id).await else {
+            return self.change_volume(player_id, value, relative).await;
+        };
+
+        let delta = if relative {
+            value
+        } else {
+            value - group.volume
+        };
+        for member in &group.members {
+            let volume = (member.volume + delta).clamp(0, 100);
+            self.rpc
+                .execute(
+                    Some(&member.playerid),
+                    vec![json!("mixer"), json!("volume"), json!(volume)],
+                )
+                .await?;
+            if let Some(player) = self.state.write().await.players.get_mut(&member.playerid) {
+                player.volume = volume;
+            }
+        }
+        Ok(())
+    }
+
+    /// Browse the library, favorites, playlists and radio apps
+    ///
+    /// `item_key` comes from a previous browse (None for the root menu);
+    /// `query` is required for the search item.
+    pub async fn browse(
+        &self,
+        item_key: Option<&str>,
+        query: Option<&str>,
+        offset: usize,
+        count: usize,
+    ) -> Result<BrowseResult> {
+        let key = LmsBrowseKey::parse(item_key.unwrap_or(""))?;
+        let base_url = self.rpc.base_url().await?;
+        let range = || vec![json!(offset), json!(count)];
+
+        let (title, total, items) = match key {
+            LmsBrowseKey::Root => {
+                let items = root_menu();
+                let total = items.len();
+                let page = items.into_iter().skip(offset).take(count).collect();
+                ("Library".to_string(), total, page)
+            }
+            LmsBrowseKey::Xml { cmd, item_id } => {
+                let mut params = vec![json!(cmd), json!("items")];
+                params.extend(range());
+                params.push(json!("want_url:1"));
+                if let Some(id) = item_id {
+                    params.push(json!(format!("item_id:{}", id)));
+                }
+                let result = self.rpc.execute(None, params).await?;
+                let items = result_loop(&result, "loop_loop")
+                    .iter()
+                    .map(|item| xml_item(&base_url, cmd, item))
+                    .collect();
+                let title = str_field(&result, "title").unwrap_or_else(|| cmd.to_string());
+                (title, count_field(&result), items)
+            }
+            LmsBrowseKey::Artists => {
+                let mut params = vec![json!("artists")];
+                params.extend(range());
+                let result = self.rpc.execute(None, params).await?;
+                let items = result_loop(&result, "artists_loop")
+                    .iter()
+                    .filter_map(|a| {
+                        Some(BrowseItem::new(
+                            str_field(a, "artist")?,
+                            format!("artist:{}", id_field(a, "id")?),
+                            BrowseItemHint::ActionList,
+                        ))
+                    })
+                    .collect();
+                ("Artists".to_string(), count_field(&result), items)
+            }
+            LmsBrowseKey::Albums | LmsBrowseKey::Artist(_) => {
+                let mut params = vec![json!("albums")];
+                params.extend(range());
+                params.push(json!("tags:laj"));
+                if let LmsBrowseKey::Artist(id) = key {
+                    params.push(json!(format!("artist_id:{}", id)));
+                }
+                let result = self.rpc.execute(None, params).await?;
+                let albums = result_loop(&result, "albums_loop");
+                let title = match key {
+                    LmsBrowseKey::Artist(_) => albums
+                        .first()
+                        .and_then(|a| str_field(a, "artist"))
+                        .unwrap_or_else(|| "Artist".to_string()),
+                    _ => "Albums".to_string(),
+                };
+                let items = albums
+                    .iter()
+                    .filter_map(|a| {
+                        Some(
+                            BrowseItem::new(
+                                str_field(a, "album")?,
+                                format!("album:{}", id_field(a, "id")?),
+                                BrowseItemHint::ActionList,
+                            )
+                            .with_subtitle(str_field(a, "artist"))
+                            .with_image_key(id_field(a, "artwork_track_id")),
+                        )
+                    })
+                    .collect();
+                (title, count_field(&result), items)
+            }
+            LmsBrowseKey::Album(id) => {
+                let mut params = vec![json!("titles")];
+                params.extend(range());
+                params.push(json!(format!("album_id:{}", id)));
+                params.push(json!("tags:alu"));
+                params.push(json!("sort:tracknum"));
+                let result = self.rpc.execute(None, params).await?;
+                let tracks = result_loop(&result, "titles_loop");
+                let title = tracks
+                    .first()
+                    .and_then(|t| str_field(t, "album"))
+                    .unwrap_or_else(|| "Album".to_string());
+                let items = tracks.iter().filter_map(track_item).collect();
+                (title, count_field(&result), items)
+            }
+            LmsBrowseKey::Playlists => {
+                let mut params = vec![json!("playlists")];
+                params.extend(range());
+                let result = self.rpc.execute(None, params).await?;
+                let items = result_loop(&result, "playlists_loop")
+                    .iter()
+                    .filter_map(|p| {
+                        Some(BrowseItem::new(
+                            str_field(p, "playlist")?,
+                            format!("playlist:{}", id_field(p, "id")?),
+                            BrowseItemHint::ActionList,
+                        ))
+                    })
+                    .collect();
+                ("Playlists".to_string(), count_field(&result), items)
+            }
+            LmsBrowseKey::Playlist(id) => {
+                let mut params = vec![json!("playlists"), json!("tracks")];
+                params.extend(range());
+                params.push(json!(format!("playlist_id:{}", id)));
+                params.push(json!("tags:au"));
+                let result = self.rpc.execute(None, params).await?;
+                let items = result_loop(&result, "playlisttracks_loop")
+                    .iter()
+                    .filter_map(track_item)
+                    .collect();
+                ("Playlist".to_string(), count_field(&result), items)
+            }
+            LmsBrowseKey::Radios => {
+                let mut params = vec![json!("radios")];
+                params.extend(range());
+                let result = self.rpc.execute(None, params).await?;
+                let items = result_loop(&result, "radioss_loop")
+                    .iter()
+                    .filter_map(|r| {
+                        Some(
+                            BrowseItem::new(
+                                str_field(r, "name")?,
+                                format!("xml:{}", str_field(r, "cmd")?),
+                                BrowseItemHint::List,
+                            )
+                            .with_image_key(absolute_image_url(&base_url, str_field(r, "icon"))),
+                        )
+                    })
+                    .collect();
+                ("Radio".to_string(), count_field(&result), items)
+            }
+            LmsBrowseKey::Search => {
+                let term = query
+                    .filter(|q| !q.trim().is_empty())
+                    .ok_or_else(|| anyhow!("Search requires a query"))?;
+                let result = self
+                    .rpc
+                    .execute(
+                        None,
+                        vec![
+                            json!("search"),
+                            json!(0),
+                            json!(count),
+                            json!(format!("term:{}", term)),
+                        ],
+                    )
+                    .await?;
+                let items = search_items(&result);
+                let total = items.len();
+                let page = items.into_iter().skip(offset).take(count).collect();
+                (format!("Search: {}", term), total, page)
+            }
+            LmsBrowseKey::Track(_) | LmsBrowseKey::Url(_) => {
+                return Err(anyhow!("Item cannot be browsed"));
+            }
+        };
+
+        Ok(BrowseResult {
+            list: BrowseList {
+                title,
+                subtitle: None,
+                image_key: None,
+                count: total,
+                level: key.level(),
+            },
+            items,
+            offset,
+        })
+    }
+
+    /// Play (or queue) a browse item on a player
+    pub async fn play_item(&self, player_id: &str, item_key: &str, mode: PlayMode) -> Result<()> {
+        let (verb, cmd) = match mode {
+            PlayMode::Play => ("play", "cmd:load"),
+            PlayMode::Add => ("add", "cmd:add"),
+        };
+        let params = match LmsBrowseKey::parse(item_key)? {
+            // Favorites and radio apps: "<cmd> playlist play|add item_id:<id>"
+            LmsBrowseKey::Xml {
+                cmd: app,
+                item_id: Some(id),
+            } => vec![
+                json!(app),
+                json!("playlist"),
+                json!(verb),
+                json!(format!("item_id:{}", id)),
+            ],
+            LmsBrowseKey::Url(url) => vec![json!("playlist"), json!(verb), json!(url)],
+            LmsBrowseKey::Track(id) => playlistcontrol(cmd, "track_id", id),
+            LmsBrowseKey::Album(id) => playlistcontrol(cmd, "album_id", id),
+            LmsBrowseKey::Artist(id) => playlistcontrol(cmd, "artist_id", id),
+            LmsBrowseKey::Playlist(id) => playlistcontrol(cmd, "playlist_id", id),
+            _ => return Err(anyhow!("Item is not playable: {}", item_key)),
+        };
+        self.rpc.execute(Some(player_id), params).await?;
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Browse helpers
+// =============================================================================
+
+/// Parsed browse item key
+///
+/// Keys are `<kind>[:<id>]`; `xml:<cmd>[:<item_id>]` addresses favorites and
+/// radio apps, which share the XMLBrowser `items`/`playlist` commands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LmsBrowseKey<'a> {
+    Root,
+    Xml {
+        cmd: &'a str,
+        item_id: Option<&'a str>,
+    },
+    Artists,
+    Artist(&'a str),
+    Albums,
+    Album(&'a str),
+    Playlists,
+    Playlist(&'a str),
+    Radios,
+    Search,
+    Track(&'a str),
+    Url(&'a str),
+}
+
+impl<'a> LmsBrowseKey<'a> {
+    fn parse(key: &'a str) -> Result<Self> {
+        let (kind, rest) = match key.split_once(':') {
+            Some((kind, rest)) => (kind, Some(rest)),
+            None => (key, None),
+        };
+        let id = || rest.filter(|r| !r.is_empty());
+        let parsed = match (kind, id()) {
+            ("", None) => Self::Root,
+            ("xml", Some(rest)) => match rest.split_once(':') {
+                Some((cmd, item_id)) => Self::Xml {
+                    cmd,
+                    item_id: Some(item_id),
+                },
+                None => Self::Xml {
+                    cmd: rest,
+                    item_id: None,
+                },
+            },
+            ("artists", None) => Self::Artists,
+            ("artist", Some(id)) => Self::Artist(id),
+            ("albums", None) => Self::Albums,
+            ("album", Some(id)) => Self::Album(id),
+            ("playlists", None) => Self::Playlists,
+            ("playlist", Some(id)) => Self::Playlist(id),
+            ("radios", None) => Self::Radios,
+            ("search", None) => Self::Search,
+            ("track", Some(id)) => Self::Track(id),
+            ("url", Some(url)) => Self::Url(url),
+            _ => return Err(anyhow!("Invalid item_key: {}", key)),
+        };
+        Ok(parsed)
+    }
+
+    fn level(&self) -> u32 {
+        match self {
+            Self::Root => 0,
+            Self::Xml { item_id: None, .. }
+            | Self::Artists
+            | Self::Albums
+            | Self::Playlists
+            | Self::Radios
+            | Self::Search => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// Top-level browse menu
+fn root_menu() -> Vec<BrowseItem> {
+    vec![
+        BrowseItem::new("Favorites", "xml:favorites", BrowseItemHint::List),
+        BrowseItem::new("Playlists", "playlists", BrowseItemHint::List),
+        BrowseItem::new("Artists", "artists", BrowseItemHint::List),
+        BrowseItem::new("Albums", "albums", BrowseItemHint::List),
+        BrowseItem::new("Radio", "radios", BrowseItemHint::List),
+        BrowseItem {
+            input_prompt: Some(BrowseInputPrompt {
+                prompt: "Search".to_string(),
+                action: "Go".to_string(),
+            }),
+            ..BrowseItem::new("Search", "search", BrowseItemHint::List)
+        },
+    ]
+}
+
+fn playlistcontrol(cmd: &str, field: &str, id: &str) -> Vec<Value> {
+    vec![
+        json!("playlistcontrol"),
+        json!(cmd),
+        json!(format!("{}:{}", field, id)),
+    ]
+}
+
+fn result_loop<'a>(result: &'a Value, name: &str) -> &'a [Value] {
+    result
+        .get(name)
+        .and_then(|v| v.as_array())
+        .map(|a| a.as_slice())
+        .unwrap_or_default()
+}
+
+fn str_field(value: &Value, name: &str) -> Option<String> {
+    value.get(name).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// IDs come back as numbers or strings depending on the query
+fn id_field(value: &Value, name: &str) -> Option<String> {
+    let v = value.get(name)?;
+    v.as_str()
+        .map(String::from)
+        .or_else(|| v.as_i64().map(|n| n.to_string()))
+}
+
+fn count_field(result: &Value) -> usize {
+    result.get("count").and_then(|v| v.as_u64()).unwrap_or(0) as usize
+}
+
+fn flag_field(value: &Value, name: &str) -> bool {
+    value.get(name).and_then(|v| v.as_i64()).unwrap_or(0) == 1
+}
+
+/// Resolve server-relative icon paths so they can be fetched as artwork
+fn absolute_image_url(base_url: &str, image: Option<String>) -> Option<String> {
+    let image = image.filter(|i| !i.is_empty())?;
+    if image.starts_with("http://") || image.starts_with("https://") {
+        Some(image)
+    } else {
+        Some(format!("{}/{}", base_url, image.trim_start_matches('/')))
+    }
+}
+
+/// Track entry: played by URL when the server reports one
+fn track_item(track: &Value) -> Option<BrowseItem> {
+    let key = match str_field(track, "url") {
+        Some(url) => format!("url:{}", url),
+        None => format!("track:{}", id_field(track, "id")?),
+    };
+    Some(
+        BrowseItem::new(str_field(track, "title")?, key, BrowseItemHint::Action)
+            .with_subtitle(str_field(track, "artist")),
+    )
+}
+
+/// XMLBrowser entry (favorites, radio apps)
+fn xml_item(base_url: &str, cmd: &str, item: &Value) -> BrowseItem {
+    let title = str_field(item, "name").unwrap_or_default();
+    let hint = match (flag_field(item, "isaudio"), flag_field(item, "hasitems")) {
+        (true, true) => Some(BrowseItemHint::ActionList),
+        (true, false) => Some(BrowseItemHint::Action),
+        (false, true) => Some(BrowseItemHint::List),
+        // Text-only entries
+        (false, false) => None,
+    };
+    BrowseItem {
+        title,
+        subtitle: None,
+        image_key: absolute_image_url(base_url, str_field(item, "image")),
+        item_key: hint
+            .and(id_field(item, "id"))
+            .map(|id| format!("xml:{}:{}", cmd, id)),
+        hint,
+        input_prompt: None,
+    }
+}
+
+/// Flatten search results into headed sections
+fn search_items(result: &Value) -> Vec<BrowseItem> {
+    let sections = [
+        (
+            "Artists",
+            "contributors_loop",
+            "contributor",
+            "contributor_id",
+            "artist",
+        ),
+        ("Albums", "albums_loop", "album", "album_id", "album"),
+        ("Tracks", "tracks_loop", "track", "track_id", "track"),
+    ];
+
+    let mut items = Vec::new();
+    for (header, loop_name, title_field, id_name, kind) in sections {
+        let entries = result_loop(result, loop_name);
+        if entries.is_empty() {
+            continue;
+        }
+        items.push(BrowseItem {
+            title: header.to_string(),
+            subtitle: None,
+            image_key: None,
+            item_key: None,
+            hint: Some(BrowseItemHint::Header),
+            input_prompt: None,
+        });
+        let hint = if kind == "track" {
+            BrowseItemHint::Action
+        } else {
+            BrowseItemHint::ActionList
+        };
+        items.extend(entries.iter().filter_map(|e| {
+            Some(BrowseItem::new(
+                str_field(e, title_field)?,
+                format!("{}:{}", kind, id_field(e, id_name)?),
+                hint,
+            ))
+        }));
+    }
+    items
 }
 
 /// Convert an LMS player to a unified Zone representation
-fn lms_player_to_zone(player: &LmsPlayer) -> Zone {
+fn lms_player_to_zone(instance: &str, player: &LmsPlayer) -> Zone {
     Zone {
-        zone_id: format!("lms:{}", player.playerid),
+        zone_id: format!("{}{}", zone_prefix(instance), player.playerid),
         zone_name: player.name.clone(),
         state: PlaybackState::from(player.state.as_str()),
         volume_control: Some(VolumeControl {
@@ -743,6 +1486,33 @@ fn lms_player_to_zone(player: &LmsPlayer) -> Zone {
     }
 }
 
+/// Build zones from players: unsynced players map 1:1, each sync group is a
+/// single zone under its master's ID with the group name and volume
+fn lms_zones(instance: &str, players: &HashMap<String, LmsPlayer>) -> Vec<Zone> {
+    let groups = sync_groups(players);
+    let mut zones = Vec::new();
+
+    for player in players.values() {
+        let in_group = groups
+            .iter()
+            .find(|g| g.members.iter().any(|m| m.playerid == player.playerid));
+        match in_group {
+            None => zones.push(lms_player_to_zone(instance, player)),
+            Some(group) if group.group_id == player.playerid => {
+                let mut zone = lms_player_to_zone(instance, player);
+                zone.zone_name = group.name.clone();
+                if let Some(vc) = zone.volume_control.as_mut() {
+                    vc.value = group.volume as f32;
+                }
+                zones.push(zone);
+            }
+            // Other group members are represented by the master's zone
+            Some(_) => {}
+        }
+    }
+    zones
+}
+
 /// Shared helper function for updating players from the polling task
 /// Uses LmsRpc to avoid code duplication between LmsAdapter and background task
 async fn update_players_internal(
@@ -752,9 +1522,6 @@ async fn update_players_internal(
 ) -> Result<()> {
     let players = rpc.get_players().await?;
 
-    let previous_ids: std::collections::HashSet<String> =
-        { state.read().await.players.keys().cloned().collect() };
-
     for mut player in players {
         match rpc.get_player_status(&player.playerid).await {
             Ok(status) => {
@@ -772,6 +1539,8 @@ async fn update_players_internal(
                 player.artwork_track_id = status.artwork_track_id;
                 player.coverid = status.coverid;
                 player.artwork_url = status.artwork_url;
+                player.sync_master = status.sync_master;
+                player.sync_slaves = status.sync_slaves;
             }
             Err(e) => {
                 tracing::warn!("Failed to get status for player {}: {}", player.playerid, e);
@@ -782,34 +1551,384 @@ async fn update_players_internal(
         state.players.insert(player.playerid.clone(), player);
     }
 
-    // Emit events for player set changes
-    let current_ids: std::collections::HashSet<String> =
-        { state.read().await.players.keys().cloned().collect() };
-
-    if previous_ids != current_ids {
-        let added: Vec<_> = current_ids.difference(&previous_ids).cloned().collect();
-        let removed: Vec<_> = previous_ids.difference(&current_ids).cloned().collect();
+    // Publish zone changes (synced players collapse into one group zone)
+    let mut state = state.write().await;
+    let zones = lms_zones(&state.instance_name, &state.players);
+    let current: HashMap<String, (String, String)> = zones
+        .iter()
+        .map(|z| {
+            (
+                z.zone_id.clone(),
+                (z.zone_name.clone(), z.state.to_string()),
+            )
+        })
+        .collect();
 
-        // Emit zone discovered events for new players
-        for player_id in &added {
-            if let Some(player) = state.read().await.players.get(player_id) {
-                tracing::debug!("LMS player discovered: {}", player_id);
-                let zone = lms_player_to_zone(player);
+    for zone in zones {
+        match state.published_zones.get(&zone.zone_id) {
+            None => {
+                tracing::debug!("LMS zone discovered: {}", zone.zone_id);
                 bus.publish(BusEvent::ZoneDiscovered { zone });
             }
+            Some(previous) if previous != &current[&zone.zone_id] => {
+                let (display_name, zone_state) = current[&zone.zone_id].clone();
+                bus.publish(BusEvent::ZoneUpdated {
+                    zone_id: zone.zone_id,
+                    display_name,
+                    state: zone_state,
+                });
+            }
+            Some(_) => {}
         }
-
-        // Emit zone removed events
-        for player_id in &removed {
-            tracing::debug!("LMS player removed: {}", player_id);
+    }
+    for zone_id in state.published_zones.keys() {
+        if !current.contains_key(zone_id) {
+            tracing::debug!("LMS zone removed: {}", zone_id);
             bus.publish(BusEvent::ZoneRemoved {
-                zone_id: format!("lms:{}", player_id),
+                zone_id: zone_id.clone(),
             });
         }
     }
+    state.published_zones = current;
 
     Ok(())
 }
 
+// =============================================================================
+// Multiple LMS servers
+// =============================================================================
+
+/// LMS instance info for API responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LmsInstanceInfo {
+    pub name: String,
+    pub host: Option<String>,
+    pub port: u16,
+    pub connected: bool,
+    pub player_count: usize,
+}
+
+/// Manager for multiple LMS servers
+///
+/// The default instance is the single-server adapter; named instances run
+/// alongside it with their own connection and `lms:<instance>:` zone IDs.
+/// Starting/stopping the manager starts/stops every configured instance.
+pub struct LmsInstanceManager {
+    instances: Arc<RwLock<HashMap<String, Arc<LmsAdapter>>>>,
+    bus: SharedBus,
+    /// Whether the LMS adapter is started (new instances start on add)
+    running: Arc<RwLock<bool>>,
+}
+
+impl LmsInstanceManager {
+    /// Create a manager around the default instance
+    pub fn new(bus: SharedBus, default: Arc<LmsAdapter>) -> Self {
+        let mut instances = HashMap::new();
+        instances.insert(DEFAULT_INSTANCE.to_string(), default);
+        Self {
+            instances: Arc::new(RwLock::new(instances)),
+            bus,
+            running: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Load named instances from config file
+    pub async fn load_from_config(&self) {
+        let configs = load_lms_configs();
+        let mut instances = self.instances.write().await;
+        for config in configs {
+            if instances.contains_key(&config.name) {
+                continue;
+            }
+            if let Err(e) = validate_instance_name(&config.name) {
+                tracing::warn!("Skipping LMS instance {}: {}", config.name, e);
+                continue;
+            }
+            let adapter = Arc::new(LmsAdapter::new_instance(self.bus.clone(), &config.name));
+            instances.insert(config.name, adapter);
+        }
+    }
+
+    /// Get or create an instance by name
+    pub async fn get_or_create(&self, name: &str) -> Result<Arc<LmsAdapter>> {
+        if let Some(adapter) = self.get(name).await {
+            return Ok(adapter);
+        }
+        validate_instance_name(name)?;
+
+        let mut instances = self.instances.write().await;
+        let adapter = instances
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(LmsAdapter::new_instance(self.bus.clone(), name)));
+        Ok(adapter.clone())
+    }
+
+    /// Get an instance by name (if it exists)
+    pub async fn get(&self, name: &str) -> Option<Arc<LmsAdapter>> {
+        self.instances.read().await.get(name).cloned()
+    }
+
+    /// Get the default instance
+    pub async fn get_default(&self) -> Arc<LmsAdapter> {
+        self.get(DEFAULT_INSTANCE)
+            .await
+            .expect("default LMS instance is always present")
+    }
+
+    /// Find the instance and player ID behind an `lms:` zone ID
+    pub async fn resolve_zone(&self, zone_id: &str) -> Option<(Arc<LmsAdapter>, String)> {
+        let rest = zone_id.strip_prefix("lms:")?;
+        if let Some((name, player_id)) = rest.split_once(':') {
+            if name != DEFAULT_INSTANCE {
+                if let Some(adapter) = self.get(name).await {
+                    return Some((adapter, player_id.to_string()));
+                }
+            }
+        }
+        Some((self.get_default().await, rest.to_string()))
+    }
+
+    /// Cached player behind an `lms:` zone ID, with the instance that owns it
+    pub async fn get_cached_player(&self, zone_id: &str) -> Option<(Arc<LmsAdapter>, LmsPlayer)> {
+        let (adapter, player_id) = self.resolve_zone(zone_id).await?;
+        let player = adapter.get_cached_player(&player_id).await?;
+        Some((adapter, player))
+    }
+
+    /// Zones from every instance
+    pub async fn get_cached_zones(&self) -> Vec<Zone> {
+        let adapters: Vec<Arc<LmsAdapter>> =
+            self.instances.read().await.values().cloned().collect();
+        let mut zones = Vec::new();
+        for adapter in adapters {
+            zones.extend(adapter.get_cached_zones().await);
+        }
+        zones
+    }
+
+    /// List all instances
+    pub async fn list_instances(&self) -> Vec<LmsInstanceInfo> {
+        let instances = self.instances.read().await;
+        let mut result = Vec::new();
+
+        for (name, adapter) in instances.iter() {
+            let status = adapter.get_status().await;
+            result.push(LmsInstanceInfo {
+                name: name.clone(),
+                host: status.host,
+                port: status.port,
+                connected: status.connected,
+                player_count: status.player_count,
+            });
+        }
+
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        result
+    }
+
+    /// Add or update an instance, (re)connecting it if LMS is running
+    ///
+    /// Connection failures are logged, not returned: the instance is saved
+    /// and retried the next time LMS starts.
+    pub async fn add_instance(
+        &self,
+        name: &str,
+        host: String,
+        port: Option<u16>,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Result<Arc<LmsAdapter>> {
+        let adapter = self.get_or_create(name).await?;
+        adapter.stop_internal().await;
+        adapter.configure(host, port, username, password).await;
+        if *self.running.read().await {
+            if let Err(e) = adapter.start_internal().await {
+                tracing::warn!("Failed to start LMS instance {}: {}", name, e);
+            }
+        }
+        Ok(adapter)
+    }
+
+    /// Remove a named instance (the default instance cannot be removed)
+    pub async fn remove_instance(&self, name: &str) -> Result<bool> {
+        if name == DEFAULT_INSTANCE {
+            return Err(anyhow!("The default LMS instance cannot be removed"));
+        }
+        let Some(adapter) = self.instances.write().await.remove(name) else {
+            return Ok(false);
+        };
+        adapter.stop_internal().await;
+
+        let mut configs = load_lms_configs();
+        configs.retain(|c| c.name != name);
+        save_lms_configs(&configs);
+        Ok(true)
+    }
+
+    /// Check if any instance is configured
+    pub async fn has_configured_instances(&self) -> bool {
+        let adapters: Vec<Arc<LmsAdapter>> =
+            self.instances.read().await.values().cloned().collect();
+        for adapter in adapters {
+            if adapter.is_configured().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Start every configured instance (internal - use Startable trait)
+    ///
+    /// Succeeds if at least one instance connects; failures are logged so one
+    /// unreachable server doesn't keep the others offline.
+    async fn start_internal(&self) -> Result<()> {
+        *self.running.write().await = true;
+
+        let adapters: Vec<(String, Arc<LmsAdapter>)> = self
+            .instances
+            .read()
+            .await
+            .iter()
+            .map(|(name, adapter)| (name.clone(), adapter.clone()))
+            .collect();
+
+        let mut first_error = None;
+        let mut started = 0;
+        for (name, adapter) in adapters {
+            if !adapter.is_configured().await {
+                continue;
+            }
+            match adapter.start_internal().await {
+                Ok(()) => started += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to start LMS instance {}: {}", name, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if started == 0 => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Stop every instance (internal - use Startable trait)
+    async fn stop_internal(&self) {
+        *self.running.write().await = false;
+
+        let adapters: Vec<Arc<LmsAdapter>> =
+            self.instances.read().await.values().cloned().collect();
+        for adapter in adapters {
+            adapter.stop_internal().await;
+        }
+    }
+}
+
+// =============================================================================
+// LMS UDP broadcast discovery
+// =============================================================================
+
+const LMS_DISCOVERY_PORT: u16 = 3483;
+const LMS_DISCOVERY_TIMEOUT_MS: u64 = 3000;
+
+/// Discovered LMS server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DiscoveredLms {
+    pub host: String,
+    /// JSON-RPC (web) port
+    pub port: u16,
+    pub name: String,
+    pub uuid: Option<String>,
+    pub version: Option<String>,
+}
+
+/// Discover LMS servers on the local network via UDP broadcast
+pub async fn discover_lms_servers(timeout_ms: Option<u64>) -> Result<Vec<DiscoveredLms>> {
+    let dest = SocketAddrV4::new(Ipv4Addr::BROADCAST, LMS_DISCOVERY_PORT);
+    discover_lms_servers_at(dest.into(), timeout_ms).await
+}
+
+/// Send the discovery request to a specific address (broadcast or unicast)
+pub async fn discover_lms_servers_at(
+    dest: SocketAddr,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<DiscoveredLms>> {
+    let timeout_duration = Duration::from_millis(timeout_ms.unwrap_or(LMS_DISCOVERY_TIMEOUT_MS));
+    let mut discovered: HashMap<String, DiscoveredLms> = HashMap::new();
+
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.set_broadcast(true)?;
+
+    // 'e' followed by the requested TLV tags (zero-length values)
+    let mut message = vec![b'e'];
+    for tag in [b"NAME", b"JSON", b"UUID", b"VERS"] {
+        message.extend_from_slice(tag);
+        message.push(0);
+    }
+    socket.send_to(&message, dest).await?;
+    tracing::debug!("Sent LMS discovery request to {}", dest);
+
+    let mut buf = [0u8; 1500];
+    let deadline = tokio::time::Instant::now() + timeout_duration;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+
+        match timeout(remaining, socket.recv_from(&mut buf)).await {
+            Ok(Ok((len, addr))) => {
+                if let Some(server) =
+                    parse_lms_discovery_response(&buf[..len], addr.ip().to_string())
+                {
+                    tracing::debug!("LMS discovery response from {}: {}", addr, server.name);
+                    discovered.insert(server.host.clone(), server);
+                }
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("LMS discovery recv error: {}", e);
+                break;
+            }
+            Err(_) => {
+                // Timeout - done receiving
+                break;
+            }
+        }
+    }
+
+    let result: Vec<DiscoveredLms> = discovered.into_values().collect();
+    tracing::info!("LMS discovery found {} server(s)", result.len());
+    Ok(result)
+}
+
+/// Parse an LMS discovery response: 'E' followed by TLVs
+/// (4-byte tag, 1-byte length, value)
+fn parse_lms_discovery_response(data: &[u8], host: String) -> Option<DiscoveredLms> {
+    let mut rest = data.strip_prefix(b"E")?;
+    let mut fields: HashMap<&[u8], String> = HashMap::new();
+    while rest.len() >= 5 {
+        let (tag, len) = (&rest[..4], rest[4] as usize);
+        let value = rest.get(5..5 + len)?;
+        fields.insert(tag, String::from_utf8_lossy(value).into_owned());
+        rest = &rest[5 + len..];
+    }
+
+    Some(DiscoveredLms {
+        host,
+        port: fields
+            .get(&b"JSON"[..])
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(DEFAULT_PORT),
+        name: fields
+            .remove(&b"NAME"[..])
+            .unwrap_or_else(|| "Lyrion Music Server".to_string()),
+        uuid: fields.remove(&b"UUID"[..]),
+        version: fields.remove(&b"VERS"[..]),
+    })
+}
+
 // Startable trait implementation via macro
 crate::impl_startable!(LmsAdapter, "lms", is_configured);
+crate::impl_startable!(LmsInstanceManager, "lms", has_configured_instances);
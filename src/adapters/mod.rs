@@ -1,10 +1,19 @@
-//! Audio source adapters (Roon, HQPlayer, LMS, OpenHome, UPnP)
+//! Audio source adapters (Roon, HQPlayer, LMS, OpenHome, UPnP, AirPlay, Spotify Connect,
+//! external subprocess adapters, remote bridges)
 
+pub mod abx;
+pub mod airplay;
+pub mod browse;
+pub mod didl;
+pub mod external;
 pub mod handle;
 pub mod hqplayer;
 pub mod lms;
 pub mod openhome;
+pub mod radio;
+pub mod remote;
 pub mod roon;
+pub mod spotify;
 pub mod traits;
 pub mod upnp;
 
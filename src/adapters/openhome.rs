@@ -3,10 +3,18 @@
 //! Uses SSDP for discovery and UPnP/SOAP for control of OpenHome devices.
 //! OpenHome is an extension of UPnP that provides richer metadata and more
 //! control actions (next/previous track, playlists, etc.)
+//!
+//! The Playlist service holds the renderer's queue on-device; it is read as
+//! an ID array plus per-track metadata and edited by track ID. The Product
+//! service lists the device's sources (Playlist, Radio, inputs, ...), switches
+//! between them and puts the device in standby, reported as its own state.
 
+use crate::adapters::didl::{parse_didl, DidlObject};
 use crate::bus::{BusEvent, PlaybackState, SharedBus, VolumeControl as BusVolumeControl, Zone};
+use base64::Engine;
 use futures::StreamExt;
 use quick_xml::de::from_str as xml_from_str;
+use quick_xml::escape::{escape, unescape};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use ssdp_client::{SearchTarget, URN};
@@ -29,6 +37,8 @@ const SSDP_SEARCH_INTERVAL: Duration = Duration::from_secs(30);
 const POLL_INTERVAL: Duration = Duration::from_secs(2);
 const STALE_THRESHOLD: Duration = Duration::from_secs(90);
 const SOAP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Track IDs per Playlist ReadList request
+const READ_LIST_CHUNK: usize = 100;
 
 /// OpenHome device information
 #[derive(Debug, Clone, Serialize)]
@@ -46,6 +56,43 @@ pub struct OpenHomeDevice {
     pub last_seen: std::time::Instant,
     #[serde(skip)]
     pub last_track_uri: Option<String>,
+    /// Active source name (Product service)
+    pub source: Option<String>,
+    pub standby: bool,
+    /// Sources as last read from the Product service
+    #[serde(skip)]
+    pub sources: Vec<OpenHomeSource>,
+    /// Services by name (e.g. "Playlist"), from the description
+    #[serde(skip)]
+    pub services: HashMap<String, OpenHomeService>,
+}
+
+/// A service from a device description
+#[derive(Debug, Clone)]
+pub struct OpenHomeService {
+    /// Full service type, e.g. `urn:av-openhome-org:service:Product:2`
+    pub service_type: String,
+    pub control_url: String,
+}
+
+/// A Product service source
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenHomeSource {
+    pub index: u32,
+    pub name: String,
+    /// Source type, e.g. Playlist, Radio, Spotify, Analog, Digital, Hdmi
+    #[serde(rename = "type")]
+    pub source_type: String,
+    pub visible: bool,
+}
+
+/// A Radio service preset channel
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenHomeRadioChannel {
+    pub id: u32,
+    pub title: String,
+    pub uri: String,
+    pub image: Option<String>,
 }
 
 /// Track metadata from OpenHome device
@@ -100,6 +147,8 @@ pub struct OpenHomeZone {
     pub output_name: String,
     pub device_name: Option<String>,
     pub volume_control: Option<VolumeControl>,
+    /// Active source name (e.g. "Playlist", "TV")
+    pub source: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -111,6 +160,34 @@ pub struct VolumeControl {
     pub is_muted: bool,
 }
 
+/// A track in a device's Playlist
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenHomeQueueItem {
+    /// Playlist track ID (stable while the track is in the playlist)
+    pub id: u32,
+    pub uri: String,
+    pub metadata: Option<DidlObject>,
+}
+
+/// A device's Playlist (its on-device queue)
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenHomeQueue {
+    pub items: Vec<OpenHomeQueueItem>,
+    /// Index of the current track
+    pub current: Option<usize>,
+    pub shuffle: bool,
+    pub repeat: bool,
+}
+
+/// Parsed device description
+struct DeviceDescription {
+    uuid: Option<String>,
+    friendly_name: Option<String>,
+    manufacturer: Option<String>,
+    model_name: Option<String>,
+    services: HashMap<String, OpenHomeService>,
+}
+
 struct OpenHomeState {
     devices: HashMap<String, OpenHomeDevice>,
     running: bool,
@@ -281,6 +358,10 @@ impl OpenHomeAdapter {
                             track_info: None,
                             last_seen: std::time::Instant::now(),
                             last_track_uri: None,
+                            source: None,
+                            standby: false,
+                            sources: Vec::new(),
+                            services: HashMap::new(),
                         };
 
                         s.devices.insert(uuid.clone(), device);
@@ -325,12 +406,8 @@ impl OpenHomeAdapter {
         Ok(())
     }
 
-    async fn fetch_device_info(
-        state: &Arc<RwLock<OpenHomeState>>,
-        http: &Client,
-        uuid: &str,
-        location: &str,
-    ) -> anyhow::Result<()> {
+    /// Fetch and parse a device description
+    async fn fetch_description(http: &Client, location: &str) -> anyhow::Result<DeviceDescription> {
         let response = http.get(location).send().await?;
         let xml = response.text().await?;
 
@@ -342,23 +419,88 @@ impl OpenHomeAdapter {
 
         #[derive(Deserialize)]
         struct DeviceDesc {
+            #[serde(rename = "UDN")]
+            udn: Option<String>,
             #[serde(rename = "friendlyName")]
             friendly_name: Option<String>,
             manufacturer: Option<String>,
             #[serde(rename = "modelName")]
             model_name: Option<String>,
+            #[serde(rename = "serviceList")]
+            service_list: Option<ServiceList>,
+        }
+
+        #[derive(Deserialize)]
+        struct ServiceList {
+            service: Vec<ServiceDesc>,
+        }
+
+        #[derive(Deserialize)]
+        struct ServiceDesc {
+            #[serde(rename = "serviceType")]
+            service_type: String,
+            #[serde(rename = "controlURL")]
+            control_url: Option<String>,
         }
 
         let root: Root = xml_from_str(&xml)?;
+        let base_url = Self::get_base_url(location)?;
+
+        // urn:av-openhome-org:service:Playlist:1 -> Playlist
+        let services = root
+            .device
+            .service_list
+            .map(|list| list.service)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|service| {
+                let name = service.service_type.split(':').nth(3)?.to_string();
+                let url = service.control_url?;
+                let control_url = if url.starts_with("http://") || url.starts_with("https://") {
+                    url
+                } else if url.starts_with('/') {
+                    format!("{}{}", base_url, url)
+                } else {
+                    format!("{}/{}", base_url, url)
+                };
+                Some((
+                    name,
+                    OpenHomeService {
+                        service_type: service.service_type,
+                        control_url,
+                    },
+                ))
+            })
+            .collect();
+
+        Ok(DeviceDescription {
+            uuid: root
+                .device
+                .udn
+                .map(|udn| udn.trim().trim_start_matches("uuid:").to_string()),
+            friendly_name: root.device.friendly_name,
+            manufacturer: root.device.manufacturer,
+            model_name: root.device.model_name,
+            services,
+        })
+    }
+
+    async fn fetch_device_info(
+        state: &Arc<RwLock<OpenHomeState>>,
+        http: &Client,
+        uuid: &str,
+        location: &str,
+    ) -> anyhow::Result<()> {
+        let description = Self::fetch_description(http, location).await?;
 
         let mut s = state.write().await;
         if let Some(device) = s.devices.get_mut(uuid) {
-            device.name = root
-                .device
+            device.name = description
                 .friendly_name
                 .unwrap_or_else(|| format!("OpenHome {}", &uuid[..8.min(uuid.len())]));
-            device.manufacturer = root.device.manufacturer;
-            device.model = root.device.model_name;
+            device.manufacturer = description.manufacturer;
+            device.model = description.model_name;
+            device.services = description.services;
 
             tracing::info!(
                 "Got OpenHome device info: {} - {} {}",
@@ -436,6 +578,11 @@ impl OpenHomeAdapter {
     ) -> anyhow::Result<()> {
         let base_url = Self::get_base_url(location)?;
 
+        // Poll standby and source (devices without a Product service skip this)
+        if let Err(e) = Self::poll_product(state, bus, http, uuid).await {
+            tracing::trace!("No Product state for {}: {}", uuid, e);
+        }
+
         // Poll transport state
         let transport_state = Self::soap_call(
             http,
@@ -450,7 +597,7 @@ impl OpenHomeAdapter {
             if let Some(new_state) = Self::extract_xml_value(&response, "Value") {
                 let new_state = new_state.to_lowercase();
                 let mut s = state.write().await;
-                if let Some(device) = s.devices.get_mut(uuid) {
+                if let Some(device) = s.devices.get_mut(uuid).filter(|d| !d.standby) {
                     if device.state != new_state {
                         device.state = new_state.clone();
                         bus.publish(BusEvent::ZoneUpdated {
@@ -680,6 +827,7 @@ impl OpenHomeAdapter {
                         max: 100,
                         is_muted: d.muted,
                     }),
+                    source: d.source.clone(),
                 }
             })
             .collect()
@@ -842,6 +990,13 @@ impl OpenHomeAdapter {
                     device.volume = Some(new_vol);
                 }
             }
+            "source" => {
+                let index = value.ok_or_else(|| anyhow::anyhow!("source needs a value"))?;
+                self.set_source(uuid, &index.to_string()).await?;
+            }
+            "standby" => {
+                self.set_standby(uuid, value.map(|v| v != 0)).await?;
+            }
             _ => {
                 anyhow::bail!("Unknown action: {}", action);
             }
@@ -862,6 +1017,497 @@ impl OpenHomeAdapter {
         Ok(())
     }
 
+    /// One of a device's services (by name, e.g. "Product")
+    fn lookup_service(
+        state: &OpenHomeState,
+        uuid: &str,
+        service: &str,
+    ) -> anyhow::Result<OpenHomeService> {
+        let device = state
+            .devices
+            .get(uuid)
+            .ok_or_else(|| anyhow::anyhow!("Device not found: {}", uuid))?;
+        if let Some(found) = device.services.get(service) {
+            return Ok(found.clone());
+        }
+        if !device.services.is_empty() {
+            anyhow::bail!("Device has no {} service", service);
+        }
+        // Description not fetched (yet); assume the conventional path
+        Ok(OpenHomeService {
+            service_type: format!("urn:av-openhome-org:service:{}:1", service),
+            control_url: format!("{}/{}", Self::get_base_url(&device.location)?, service),
+        })
+    }
+
+    /// Call a service action, failing on SOAP faults
+    async fn service_call(
+        http: &Client,
+        service: &OpenHomeService,
+        action: &str,
+        args: &str,
+    ) -> anyhow::Result<String> {
+        let response = Self::soap_call(
+            http,
+            &service.control_url,
+            &service.service_type,
+            action,
+            args,
+        )
+        .await?;
+        if response.contains("Fault>") {
+            let reason = Self::extract_xml_value(&response, "errorDescription")
+                .unwrap_or_else(|| "SOAP fault".to_string());
+            anyhow::bail!("{} failed: {}", action, reason);
+        }
+        Ok(response)
+    }
+
+    /// Add a device by its description URL (e.g. one SSDP can't reach)
+    pub async fn add_device(&self, location: &str) -> anyhow::Result<String> {
+        let description = Self::fetch_description(&self.http, location).await?;
+        let uuid = description
+            .uuid
+            .ok_or_else(|| anyhow::anyhow!("Device description has no UDN"))?;
+
+        let device = OpenHomeDevice {
+            uuid: uuid.clone(),
+            name: description
+                .friendly_name
+                .unwrap_or_else(|| format!("OpenHome {}", &uuid[..8.min(uuid.len())])),
+            manufacturer: description.manufacturer,
+            model: description.model_name,
+            location: location.to_string(),
+            state: "stopped".to_string(),
+            volume: None,
+            muted: false,
+            track_info: None,
+            last_seen: std::time::Instant::now(),
+            last_track_uri: None,
+            source: None,
+            standby: false,
+            sources: Vec::new(),
+            services: description.services,
+        };
+
+        let zone = openhome_device_to_zone(&device);
+        self.state
+            .write()
+            .await
+            .devices
+            .insert(uuid.clone(), device);
+        self.bus.publish(BusEvent::ZoneDiscovered { zone });
+        Ok(uuid)
+    }
+
+    // =========================================================================
+    // Playlist service (on-device queue)
+    // =========================================================================
+
+    /// Call a Playlist action
+    async fn playlist_call(&self, uuid: &str, action: &str, args: &str) -> anyhow::Result<String> {
+        let service = Self::lookup_service(&*self.state.read().await, uuid, "Playlist")?;
+        Self::service_call(&self.http, &service, action, args).await
+    }
+
+    /// Track IDs in playlist order
+    pub async fn playlist_ids(&self, uuid: &str) -> anyhow::Result<Vec<u32>> {
+        let response = self.playlist_call(uuid, "IdArray", "").await?;
+        let array = Self::extract_xml_value(&response, "Array").unwrap_or_default();
+        decode_id_array(array.trim())
+    }
+
+    /// Read the playlist with track metadata
+    pub async fn get_queue(&self, uuid: &str) -> anyhow::Result<OpenHomeQueue> {
+        let ids = self.playlist_ids(uuid).await?;
+
+        let mut items = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(READ_LIST_CHUNK) {
+            let id_list = chunk
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let response = self
+                .playlist_call(uuid, "ReadList", &format!("<IdList>{}</IdList>", id_list))
+                .await?;
+            let track_list = Self::extract_xml_value(&response, "TrackList").unwrap_or_default();
+            items.extend(parse_track_list(&track_list)?);
+        }
+
+        let current_id = self
+            .playlist_call(uuid, "Id", "")
+            .await
+            .ok()
+            .and_then(|r| Self::extract_xml_value(&r, "Value"))
+            .and_then(|v| v.trim().parse::<u32>().ok());
+        let shuffle = self.playlist_flag(uuid, "Shuffle").await?;
+        let repeat = self.playlist_flag(uuid, "Repeat").await?;
+
+        Ok(OpenHomeQueue {
+            current: current_id.and_then(|id| items.iter().position(|item| item.id == id)),
+            items,
+            shuffle,
+            repeat,
+        })
+    }
+
+    async fn playlist_flag(&self, uuid: &str, action: &str) -> anyhow::Result<bool> {
+        let response = self.playlist_call(uuid, action, "").await?;
+        Ok(Self::extract_xml_value(&response, "Value").is_some_and(|v| v == "true" || v == "1"))
+    }
+
+    /// Track ID at a playlist position
+    pub async fn playlist_id_at(&self, uuid: &str, index: usize) -> anyhow::Result<u32> {
+        self.playlist_ids(uuid)
+            .await?
+            .get(index)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Queue index out of range: {}", index))
+    }
+
+    /// Insert a track after `after_id` (0 = at the start; None = at the end),
+    /// returning its new ID
+    pub async fn insert_track(
+        &self,
+        uuid: &str,
+        after_id: Option<u32>,
+        uri: &str,
+        metadata: Option<&str>,
+    ) -> anyhow::Result<u32> {
+        let after_id = match after_id {
+            Some(id) => id,
+            None => self.playlist_ids(uuid).await?.last().copied().unwrap_or(0),
+        };
+        let response = self
+            .playlist_call(
+                uuid,
+                "Insert",
+                &format!(
+                    "<AfterId>{}</AfterId><Uri>{}</Uri><Metadata>{}</Metadata>",
+                    after_id,
+                    escape(uri),
+                    escape(metadata.unwrap_or(""))
+                ),
+            )
+            .await?;
+        Self::extract_xml_value(&response, "NewId")
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or_else(|| anyhow::anyhow!("Insert returned no track ID"))
+    }
+
+    /// Remove a track by ID
+    pub async fn delete_track(&self, uuid: &str, id: u32) -> anyhow::Result<()> {
+        self.playlist_call(uuid, "DeleteId", &format!("<Value>{}</Value>", id))
+            .await?;
+        Ok(())
+    }
+
+    /// Remove all tracks
+    pub async fn clear_queue(&self, uuid: &str) -> anyhow::Result<()> {
+        self.playlist_call(uuid, "DeleteAll", "").await?;
+        Ok(())
+    }
+
+    /// Play the track with the given ID
+    pub async fn seek_id(&self, uuid: &str, id: u32) -> anyhow::Result<()> {
+        self.playlist_call(uuid, "SeekId", &format!("<Value>{}</Value>", id))
+            .await?;
+        self.playlist_call(uuid, "Play", "").await?;
+        Ok(())
+    }
+
+    /// Set shuffle and/or repeat
+    pub async fn set_queue_mode(
+        &self,
+        uuid: &str,
+        shuffle: Option<bool>,
+        repeat: Option<bool>,
+    ) -> anyhow::Result<()> {
+        if let Some(shuffle) = shuffle {
+            self.playlist_call(uuid, "SetShuffle", &format!("<Value>{}</Value>", shuffle))
+                .await?;
+        }
+        if let Some(repeat) = repeat {
+            self.playlist_call(uuid, "SetRepeat", &format!("<Value>{}</Value>", repeat))
+                .await?;
+        }
+        Ok(())
+    }
+
+    // =========================================================================
+    // Product service (sources, standby)
+    // =========================================================================
+
+    /// Read the source list from the Product service
+    async fn fetch_sources(
+        http: &Client,
+        product: &OpenHomeService,
+    ) -> anyhow::Result<Vec<OpenHomeSource>> {
+        let response = Self::service_call(http, product, "SourceXml", "").await?;
+        let xml = Self::extract_xml_value(&response, "Value").unwrap_or_default();
+        let xml = unescape(&xml).map_err(|e| anyhow::anyhow!("Invalid SourceXml: {}", e))?;
+        let field = |source: &str, tag: &str| {
+            Self::extract_xml_value(source, tag)
+                .and_then(|v| unescape(&v).ok().map(|v| v.into_owned()))
+                .unwrap_or_default()
+        };
+
+        Ok(xml
+            .split("<Source>")
+            .skip(1)
+            .enumerate()
+            .map(|(index, source)| OpenHomeSource {
+                index: index as u32,
+                name: field(source, "Name"),
+                source_type: field(source, "Type"),
+                visible: field(source, "Visible") != "false",
+            })
+            .collect())
+    }
+
+    /// List a device's sources (refreshing the cached list)
+    pub async fn get_sources(&self, uuid: &str) -> anyhow::Result<Vec<OpenHomeSource>> {
+        let product = Self::lookup_service(&*self.state.read().await, uuid, "Product")?;
+        let sources = Self::fetch_sources(&self.http, &product).await?;
+        if let Some(device) = self.state.write().await.devices.get_mut(uuid) {
+            device.sources = sources.clone();
+        }
+        Ok(sources)
+    }
+
+    /// Switch the active source, by index or by name/type (case-insensitive)
+    pub async fn set_source(&self, uuid: &str, source: &str) -> anyhow::Result<()> {
+        let sources = self.get_sources(uuid).await?;
+        let found = match source.parse::<u32>() {
+            Ok(index) => sources.iter().find(|s| s.index == index),
+            Err(_) => sources
+                .iter()
+                .find(|s| s.name.eq_ignore_ascii_case(source))
+                .or_else(|| {
+                    sources
+                        .iter()
+                        .find(|s| s.source_type.eq_ignore_ascii_case(source))
+                }),
+        }
+        .ok_or_else(|| anyhow::anyhow!("Unknown source: {}", source))?;
+
+        let product = Self::lookup_service(&*self.state.read().await, uuid, "Product")?;
+        Self::service_call(
+            &self.http,
+            &product,
+            "SetSourceIndex",
+            &format!("<Value>{}</Value>", found.index),
+        )
+        .await?;
+
+        let mut state = self.state.write().await;
+        if let Some(device) = state.devices.get_mut(uuid) {
+            device.source = Some(found.name.clone());
+            // Selecting a source wakes the device
+            Self::apply_standby(&self.bus, device, false);
+        }
+        Ok(())
+    }
+
+    /// Put a device in or out of standby (`None` toggles)
+    pub async fn set_standby(&self, uuid: &str, standby: Option<bool>) -> anyhow::Result<()> {
+        let (product, current) = {
+            let state = self.state.read().await;
+            let product = Self::lookup_service(&state, uuid, "Product")?;
+            (product, state.devices.get(uuid).is_some_and(|d| d.standby))
+        };
+        let standby = standby.unwrap_or(!current);
+        Self::service_call(
+            &self.http,
+            &product,
+            "SetStandby",
+            &format!("<Value>{}</Value>", standby),
+        )
+        .await?;
+
+        let mut state = self.state.write().await;
+        if let Some(device) = state.devices.get_mut(uuid) {
+            Self::apply_standby(&self.bus, device, standby);
+        }
+        Ok(())
+    }
+
+    /// Record standby; while in standby the zone's state is "standby"
+    fn apply_standby(bus: &SharedBus, device: &mut OpenHomeDevice, standby: bool) {
+        if device.standby == standby {
+            return;
+        }
+        device.standby = standby;
+        // Transport polling fills the real state back in on wake
+        device.state = if standby { "standby" } else { "stopped" }.to_string();
+        bus.publish(BusEvent::ZoneUpdated {
+            zone_id: format!("openhome:{}", device.uuid),
+            display_name: device.name.clone(),
+            state: device.state.clone(),
+        });
+    }
+
+    /// Poll standby and the active source
+    async fn poll_product(
+        state: &Arc<RwLock<OpenHomeState>>,
+        bus: &SharedBus,
+        http: &Client,
+        uuid: &str,
+    ) -> anyhow::Result<()> {
+        let (product, needs_sources) = {
+            let s = state.read().await;
+            let product = Self::lookup_service(&s, uuid, "Product")?;
+            let needs_sources = s.devices.get(uuid).is_some_and(|d| d.sources.is_empty());
+            (product, needs_sources)
+        };
+
+        let response = Self::service_call(http, &product, "Standby", "").await?;
+        let standby =
+            Self::extract_xml_value(&response, "Value").is_some_and(|v| v == "true" || v == "1");
+        let index = Self::service_call(http, &product, "SourceIndex", "")
+            .await
+            .ok()
+            .and_then(|r| Self::extract_xml_value(&r, "Value"))
+            .and_then(|v| v.trim().parse::<u32>().ok());
+        let sources = if needs_sources {
+            Some(Self::fetch_sources(http, &product).await?)
+        } else {
+            None
+        };
+
+        let mut s = state.write().await;
+        if let Some(device) = s.devices.get_mut(uuid) {
+            if let Some(sources) = sources {
+                device.sources = sources;
+            }
+            device.source = index.and_then(|i| {
+                device
+                    .sources
+                    .iter()
+                    .find(|source| source.index == i)
+                    .map(|source| source.name.clone())
+            });
+            Self::apply_standby(bus, device, standby);
+        }
+        Ok(())
+    }
+
+    // =========================================================================
+    // Radio service (presets, internet radio streams)
+    // =========================================================================
+
+    /// Call a Radio action
+    async fn radio_call(&self, uuid: &str, action: &str, args: &str) -> anyhow::Result<String> {
+        let service = Self::lookup_service(&*self.state.read().await, uuid, "Radio")?;
+        Self::service_call(&self.http, &service, action, args).await
+    }
+
+    /// List the device's radio presets
+    pub async fn get_radio_channels(
+        &self,
+        uuid: &str,
+    ) -> anyhow::Result<Vec<OpenHomeRadioChannel>> {
+        let response = self.radio_call(uuid, "IdArray", "").await?;
+        let array = Self::extract_xml_value(&response, "Array").unwrap_or_default();
+        // Unused preset slots are ID 0
+        let ids: Vec<u32> = decode_id_array(array.trim())?
+            .into_iter()
+            .filter(|id| *id != 0)
+            .collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let id_list = ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let response = self
+            .radio_call(uuid, "ReadList", &format!("<IdList>{}</IdList>", id_list))
+            .await?;
+        let channel_list = Self::extract_xml_value(&response, "ChannelList").unwrap_or_default();
+
+        // Channels share the playlist's Entry format, minus the Uri field
+        Ok(parse_track_list(&channel_list)?
+            .into_iter()
+            .map(|entry| {
+                let metadata = entry.metadata.unwrap_or_default();
+                let uri = if entry.uri.is_empty() {
+                    metadata.uri().unwrap_or_default().to_string()
+                } else {
+                    entry.uri
+                };
+                OpenHomeRadioChannel {
+                    id: entry.id,
+                    title: metadata.title,
+                    uri,
+                    image: metadata.album_art_uri,
+                }
+            })
+            .collect())
+    }
+
+    /// Tune a radio preset and play it
+    pub async fn set_radio_channel(&self, uuid: &str, id: u32) -> anyhow::Result<()> {
+        let channel = self
+            .get_radio_channels(uuid)
+            .await?
+            .into_iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown radio channel: {}", id))?;
+        self.select_radio_source(uuid).await;
+        self.radio_call(
+            uuid,
+            "SetId",
+            &format!("<Value>{}</Value><Uri>{}</Uri>", id, escape(&channel.uri)),
+        )
+        .await?;
+        self.radio_call(uuid, "Play", "").await?;
+        Ok(())
+    }
+
+    /// Tune an arbitrary stream URL (with DIDL-Lite metadata) and play it
+    pub async fn play_radio_url(
+        &self,
+        uuid: &str,
+        uri: &str,
+        metadata: &str,
+    ) -> anyhow::Result<()> {
+        self.select_radio_source(uuid).await;
+        self.radio_call(
+            uuid,
+            "SetChannel",
+            &format!(
+                "<Uri>{}</Uri><Metadata>{}</Metadata>",
+                escape(uri),
+                escape(metadata)
+            ),
+        )
+        .await?;
+        self.radio_call(uuid, "Play", "").await?;
+        Ok(())
+    }
+
+    /// Switch to the Radio source if the device lists one
+    ///
+    /// Most devices switch on their own when the Radio service plays, so a
+    /// missing Product service or Radio source isn't an error.
+    async fn select_radio_source(&self, uuid: &str) {
+        let Ok(sources) = self.get_sources(uuid).await else {
+            return;
+        };
+        if let Some(radio) = sources
+            .iter()
+            .find(|s| s.source_type.eq_ignore_ascii_case("Radio"))
+        {
+            if let Err(e) = self.set_source(uuid, &radio.index.to_string()).await {
+                tracing::debug!("Failed to select Radio source on {}: {}", uuid, e);
+            }
+        }
+    }
+
     /// Fetch album art image
     pub async fn get_image(&self, image_url: &str) -> anyhow::Result<ImageData> {
         if !image_url.starts_with("http://") && !image_url.starts_with("https://") {
@@ -892,6 +1538,43 @@ pub struct ImageData {
     pub data: Vec<u8>,
 }
 
+/// Decode a Playlist IdArray (base64 of big-endian u32 track IDs)
+fn decode_id_array(array: &str) -> anyhow::Result<Vec<u32>> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(array)
+        .map_err(|e| anyhow::anyhow!("Invalid IdArray: {}", e))?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// Parse a Playlist ReadList TrackList (XML escaped inside the SOAP response)
+fn parse_track_list(track_list: &str) -> anyhow::Result<Vec<OpenHomeQueueItem>> {
+    let xml = unescape(track_list).map_err(|e| anyhow::anyhow!("Invalid TrackList: {}", e))?;
+    let field = |entry: &str, tag: &str| {
+        OpenHomeAdapter::extract_xml_value(entry, tag)
+            .and_then(|v| unescape(&v).ok().map(|v| v.into_owned()))
+    };
+
+    let mut items = Vec::new();
+    for entry in xml.split("<Entry>").skip(1) {
+        let Some(id) = field(entry, "Id").and_then(|id| id.trim().parse().ok()) else {
+            continue;
+        };
+        let metadata = field(entry, "Metadata")
+            .filter(|m| !m.is_empty())
+            .and_then(|m| parse_didl(&m).ok())
+            .and_then(|objects| objects.into_iter().next());
+        items.push(OpenHomeQueueItem {
+            id,
+            uri: field(entry, "Uri").unwrap_or_default(),
+            metadata,
+        });
+    }
+    Ok(items)
+}
+
 /// Decode HTML entities
 fn html_decode(s: &str) -> String {
     s.replace("&lt;", "<")
@@ -0,0 +1,217 @@
+//! Bridge-level internet radio favorites
+//!
+//! A list of stream URLs that can be played on any zone, whatever its source:
+//! OpenHome devices tune the stream on their Radio service, UPnP renderers get
+//! it via SetAVTransportURI, and LMS players load it as a playlist URL. This
+//! makes "Radio Paradise in the kitchen" a single knob action.
+//!
+//! Favorites are persisted in the config dir.
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::adapters::browse::PlayMode;
+use crate::adapters::didl::{DidlObject, DidlResource};
+use crate::adapters::lms::LmsInstanceManager;
+use crate::adapters::openhome::OpenHomeAdapter;
+use crate::adapters::upnp::UPnPAdapter;
+use crate::config::get_config_dir;
+
+const RADIO_FAVORITES_FILE: &str = "radio-favorites.json";
+
+fn favorites_path() -> PathBuf {
+    get_config_dir().join(RADIO_FAVORITES_FILE)
+}
+
+/// An internet radio station
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RadioFavorite {
+    /// Stable ID derived from the name (e.g. "radio-paradise")
+    pub id: String,
+    pub name: String,
+    /// Stream URL (http/https)
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+impl RadioFavorite {
+    /// DIDL-Lite description of the stream (renderer metadata)
+    pub fn to_didl(&self) -> DidlObject {
+        DidlObject {
+            id: self.id.clone(),
+            parent_id: "-1".to_string(),
+            title: self.name.clone(),
+            class: "object.item.audioItem.audioBroadcast".to_string(),
+            album_art_uri: self.image.clone(),
+            resources: vec![DidlResource {
+                uri: self.url.clone(),
+                protocol_info: Some("http-get:*:*:*".to_string()),
+                duration: None,
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+/// Turn a station name into an ID ("Radio Paradise" -> "radio-paradise")
+fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Radio favorites list, playable on any zone
+pub struct RadioFavorites {
+    favorites: RwLock<Vec<RadioFavorite>>,
+    lms: Arc<LmsInstanceManager>,
+    openhome: Arc<OpenHomeAdapter>,
+    upnp: Arc<UPnPAdapter>,
+}
+
+impl RadioFavorites {
+    /// Create the favorites list, loading any saved stations
+    pub fn new(
+        lms: Arc<LmsInstanceManager>,
+        openhome: Arc<OpenHomeAdapter>,
+        upnp: Arc<UPnPAdapter>,
+    ) -> Self {
+        Self {
+            favorites: RwLock::new(Self::load()),
+            lms,
+            openhome,
+            upnp,
+        }
+    }
+
+    fn load() -> Vec<RadioFavorite> {
+        let path = favorites_path();
+        if !path.exists() {
+            return Vec::new();
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(favorites) => favorites,
+                Err(e) => {
+                    tracing::warn!("Failed to parse radio favorites: {}", e);
+                    Vec::new()
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Failed to read radio favorites: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn save(favorites: &[RadioFavorite]) {
+        let path = favorites_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(favorites) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save radio favorites: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize radio favorites: {}", e),
+        }
+    }
+
+    /// List all favorites
+    pub async fn list(&self) -> Vec<RadioFavorite> {
+        self.favorites.read().await.clone()
+    }
+
+    /// Find a favorite by ID or name (case-insensitive)
+    pub async fn find(&self, key: &str) -> Option<RadioFavorite> {
+        let favorites = self.favorites.read().await;
+        favorites
+            .iter()
+            .find(|f| f.id == key)
+            .or_else(|| favorites.iter().find(|f| f.name.eq_ignore_ascii_case(key)))
+            .cloned()
+    }
+
+    /// Add a station, returning it with its assigned ID
+    pub async fn add(&self, name: &str, url: &str, image: Option<String>) -> Result<RadioFavorite> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Station name is required");
+        }
+        let parsed = url::Url::parse(url).map_err(|e| anyhow!("Invalid stream URL: {}", e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            bail!("Stream URL must be http or https");
+        }
+
+        let mut favorites = self.favorites.write().await;
+        let base = match slugify(name) {
+            slug if slug.is_empty() => "station".to_string(),
+            slug => slug,
+        };
+        let mut id = base.clone();
+        let mut n = 2;
+        while favorites.iter().any(|f| f.id == id) {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+
+        let favorite = RadioFavorite {
+            id,
+            name: name.to_string(),
+            url: url.to_string(),
+            image,
+        };
+        favorites.push(favorite.clone());
+        Self::save(&favorites);
+        tracing::info!("Added radio favorite {} ({})", favorite.name, favorite.url);
+        Ok(favorite)
+    }
+
+    /// Remove a station by ID, returning whether it existed
+    pub async fn remove(&self, id: &str) -> bool {
+        let mut favorites = self.favorites.write().await;
+        let before = favorites.len();
+        favorites.retain(|f| f.id != id);
+        let removed = favorites.len() != before;
+        if removed {
+            Self::save(&favorites);
+        }
+        removed
+    }
+
+    /// Play a favorite (by ID or name) on a zone, using whatever the zone's
+    /// source supports
+    pub async fn play(&self, zone_id: &str, key: &str) -> Result<RadioFavorite> {
+        let favorite = self
+            .find(key)
+            .await
+            .ok_or_else(|| anyhow!("Unknown radio favorite: {}", key))?;
+
+        if let Some(uuid) = zone_id.strip_prefix("openhome:") {
+            self.openhome
+                .play_radio_url(uuid, &favorite.url, &favorite.to_didl().to_didl_lite())
+                .await?;
+        } else if let Some(uuid) = zone_id.strip_prefix("upnp:") {
+            self.upnp.play_stream(uuid, favorite.to_didl()).await?;
+        } else if let Some((lms, player_id)) = self.lms.resolve_zone(zone_id).await {
+            lms.play_item(&player_id, &format!("url:{}", favorite.url), PlayMode::Play)
+                .await?;
+        } else if zone_id.starts_with("roon:") || !zone_id.contains(':') {
+            // Roon only tunes stations from its own Live Radio list, through
+            // the browse service, which this build doesn't subscribe to
+            bail!("Radio favorites can't be played on Roon zones yet");
+        } else {
+            bail!("Zone can't play radio streams: {}", zone_id);
+        }
+
+        tracing::info!("Playing radio favorite {} on {}", favorite.name, zone_id);
+        Ok(favorite)
+    }
+}
@@ -0,0 +1,836 @@
+//! Remote adapter - federates zones from other unified-hifi-control bridges
+//!
+//! Each configured bridge is followed through its `/zones` API (periodic
+//! resync) and `/events` SSE stream. Its zones are re-published on the local
+//! bus as `remote:<bridge>:<zone_id>`; now playing, artwork and control
+//! requests are proxied back to the owning bridge.
+//!
+//! Zones a bridge itself federates (`remote:` zone IDs) are not re-exported,
+//! so two bridges can follow each other without looping.
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+
+use crate::bus::{BusEvent, PlaybackState, SharedBus, Zone};
+use crate::config::get_config_dir;
+
+const REMOTE_CONFIG_FILE: &str = "remote-config.json";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Full zone list refresh while the event stream is connected
+const RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+/// The remote sends SSE keep-alives every 15s; give up after missing a few
+const EVENTS_IDLE_TIMEOUT: Duration = Duration::from_secs(45);
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+fn config_path() -> PathBuf {
+    get_config_dir().join(REMOTE_CONFIG_FILE)
+}
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// A remote bridge to federate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemoteBridgeConfig {
+    /// Bridge name (used in zone IDs, must not contain ':')
+    pub name: String,
+    /// Base URL of the remote bridge (e.g. "http://studio.local:8088")
+    pub url: String,
+}
+
+/// Saved config for persistence
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedRemoteConfig {
+    bridges: Vec<RemoteBridgeConfig>,
+}
+
+// =============================================================================
+// Remote API payloads
+// =============================================================================
+
+/// Zone as listed by a remote bridge's `/zones`
+#[derive(Debug, Clone, Deserialize)]
+struct RemoteZoneInfo {
+    zone_id: String,
+    zone_name: String,
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteZonesResponse {
+    zones: Vec<RemoteZoneInfo>,
+}
+
+/// Now playing as returned by a remote bridge's `/now_playing`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteNowPlaying {
+    #[serde(default)]
+    pub line1: String,
+    #[serde(default)]
+    pub line2: String,
+    #[serde(default)]
+    pub line3: Option<String>,
+    #[serde(default)]
+    pub is_playing: bool,
+    #[serde(default)]
+    pub volume: Option<f64>,
+    #[serde(default)]
+    pub volume_type: Option<String>,
+    #[serde(default)]
+    pub volume_min: Option<f64>,
+    #[serde(default)]
+    pub volume_max: Option<f64>,
+    #[serde(default)]
+    pub volume_step: Option<f64>,
+    #[serde(default)]
+    pub image_key: Option<String>,
+    #[serde(default)]
+    pub seek_position: Option<i64>,
+    #[serde(default)]
+    pub length: Option<u32>,
+    #[serde(default)]
+    pub is_play_allowed: bool,
+    #[serde(default)]
+    pub is_pause_allowed: bool,
+    #[serde(default)]
+    pub is_next_allowed: bool,
+    #[serde(default)]
+    pub is_previous_allowed: bool,
+}
+
+/// Split "remote:<bridge>:<zone>" into (bridge, zone)
+fn split_zone_id(zone_id: &str) -> Option<(&str, &str)> {
+    zone_id.strip_prefix("remote:")?.split_once(':')
+}
+
+fn is_federated(zone_id: &str) -> bool {
+    zone_id.starts_with("remote:")
+}
+
+// =============================================================================
+// Bridge state
+// =============================================================================
+
+#[derive(Debug, Clone)]
+struct RemoteZone {
+    zone_name: String,
+    /// Source adapter on the remote bridge (e.g. "roon")
+    source: String,
+    state: String,
+}
+
+#[derive(Debug)]
+struct Bridge {
+    config: RemoteBridgeConfig,
+    /// Zones by remote zone_id
+    zones: HashMap<String, RemoteZone>,
+    connected: bool,
+    last_error: Option<String>,
+}
+
+impl Bridge {
+    fn new(config: RemoteBridgeConfig) -> Self {
+        Self {
+            config,
+            zones: HashMap::new(),
+            connected: false,
+            last_error: None,
+        }
+    }
+
+    fn zone_id(&self, remote_zone_id: &str) -> String {
+        format!("remote:{}:{}", self.config.name, remote_zone_id)
+    }
+
+    fn to_zone(&self, remote_zone_id: &str, zone: &RemoteZone) -> Zone {
+        Zone {
+            zone_id: self.zone_id(remote_zone_id),
+            zone_name: zone.zone_name.clone(),
+            state: PlaybackState::from(zone.state.as_str()),
+            volume_control: None,
+            now_playing: None,
+            source: "remote".to_string(),
+            is_controllable: true,
+            is_seekable: false,
+            last_updated: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+
+    /// Forget all zones (bridge unreachable), returning their local IDs
+    fn clear_zones(&mut self) -> Vec<String> {
+        let ids: Vec<String> = self.zones.keys().map(|id| self.zone_id(id)).collect();
+        self.zones.clear();
+        ids
+    }
+}
+
+/// Reconcile a bridge's zones with a fresh `/zones` listing
+fn apply_zone_list(bus: &SharedBus, bridge: &mut Bridge, zones: Vec<RemoteZoneInfo>) {
+    let listed: Vec<String> = zones.iter().map(|z| z.zone_id.clone()).collect();
+
+    let removed: Vec<String> = bridge
+        .zones
+        .keys()
+        .filter(|id| !listed.contains(id))
+        .cloned()
+        .collect();
+    for id in removed {
+        bridge.zones.remove(&id);
+        bus.publish(BusEvent::ZoneRemoved {
+            zone_id: bridge.zone_id(&id),
+        });
+    }
+
+    for info in zones {
+        if is_federated(&info.zone_id) {
+            continue;
+        }
+        let zone = RemoteZone {
+            zone_name: info.zone_name,
+            source: info.source,
+            state: info.state,
+        };
+        match bridge.zones.get(&info.zone_id) {
+            None => {
+                bus.publish(BusEvent::ZoneDiscovered {
+                    zone: bridge.to_zone(&info.zone_id, &zone),
+                });
+            }
+            Some(existing)
+                if existing.zone_name != zone.zone_name || existing.state != zone.state =>
+            {
+                bus.publish(BusEvent::ZoneUpdated {
+                    zone_id: bridge.zone_id(&info.zone_id),
+                    display_name: zone.zone_name.clone(),
+                    state: zone.state.clone(),
+                });
+            }
+            Some(_) => {}
+        }
+        bridge.zones.insert(info.zone_id, zone);
+    }
+}
+
+/// Re-publish a remote bus event under this bridge's prefix
+fn apply_remote_event(bus: &SharedBus, bridge: &mut Bridge, event: BusEvent) {
+    match event {
+        BusEvent::ZoneDiscovered { zone } if !is_federated(&zone.zone_id) => {
+            let remote = RemoteZone {
+                zone_name: zone.zone_name.clone(),
+                source: zone.source.clone(),
+                state: zone.state.to_string(),
+            };
+            let remote_id = zone.zone_id.clone();
+            let mut local = zone;
+            local.zone_id = bridge.zone_id(&remote_id);
+            local.source = "remote".to_string();
+            bridge.zones.insert(remote_id, remote);
+            bus.publish(BusEvent::ZoneDiscovered { zone: local });
+        }
+        BusEvent::ZoneUpdated {
+            zone_id,
+            display_name,
+            state,
+        } => {
+            if let Some(zone) = bridge.zones.get_mut(&zone_id) {
+                zone.zone_name = display_name.clone();
+                zone.state = state.clone();
+                bus.publish(BusEvent::ZoneUpdated {
+                    zone_id: bridge.zone_id(&zone_id),
+                    display_name,
+                    state,
+                });
+            }
+        }
+        BusEvent::ZoneRemoved { zone_id } if bridge.zones.remove(&zone_id).is_some() => {
+            bus.publish(BusEvent::ZoneRemoved {
+                zone_id: bridge.zone_id(&zone_id),
+            });
+        }
+        BusEvent::NowPlayingChanged {
+            zone_id,
+            title,
+            artist,
+            album,
+            image_key,
+        } if bridge.zones.contains_key(&zone_id) => {
+            bus.publish(BusEvent::NowPlayingChanged {
+                zone_id: bridge.zone_id(&zone_id),
+                title,
+                artist,
+                album,
+                image_key,
+            });
+        }
+        BusEvent::SeekPositionChanged { zone_id, position }
+            if bridge.zones.contains_key(&zone_id) =>
+        {
+            bus.publish(BusEvent::SeekPositionChanged {
+                zone_id: bridge.zone_id(&zone_id),
+                position,
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Incremental parser for a `text/event-stream` body
+///
+/// Yields the `data:` payload of each complete event; comments (keep-alives)
+/// and other fields are ignored.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: String,
+    /// Bytes of an incomplete UTF-8 sequence carried over between chunks
+    carry: Vec<u8>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a chunk of the stream, returning the data of completed events
+    ///
+    /// Chunks may split a UTF-8 character or a CRLF; both are put back
+    /// together before parsing.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.carry.extend_from_slice(chunk);
+        let valid = match std::str::from_utf8(&self.carry) {
+            Ok(s) => s.len(),
+            Err(e) => e.valid_up_to(),
+        };
+        self.buffer
+            .push_str(&String::from_utf8_lossy(&self.carry[..valid]));
+        self.carry.drain(..valid);
+        if self.carry.len() > 4 {
+            self.carry.clear();
+        }
+        if self.buffer.contains("\r\n") {
+            self.buffer = self.buffer.replace("\r\n", "\n");
+        }
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.find("\n\n") {
+            let block: String = self.buffer.drain(..end + 2).collect();
+            let data: Vec<&str> = block
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|d| d.strip_prefix(' ').unwrap_or(d))
+                .collect();
+            if !data.is_empty() {
+                events.push(data.join("\n"));
+            }
+        }
+        events
+    }
+}
+
+// =============================================================================
+// Status types
+// =============================================================================
+
+/// Remote adapter status
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteStatus {
+    pub running: bool,
+    pub bridge_count: usize,
+    pub bridges: Vec<RemoteBridgeSummary>,
+}
+
+/// Per-bridge status
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteBridgeSummary {
+    pub name: String,
+    pub url: String,
+    pub connected: bool,
+    pub zone_count: usize,
+    pub last_error: Option<String>,
+}
+
+/// A federated zone
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteZoneSummary {
+    /// Local zone ID ("remote:<bridge>:<zone_id>")
+    pub zone_id: String,
+    pub zone_name: String,
+    pub bridge: String,
+    /// Source adapter on the remote bridge
+    pub source: String,
+    pub state: String,
+}
+
+// =============================================================================
+// Adapter
+// =============================================================================
+
+#[derive(Default)]
+struct RemoteState {
+    bridges: HashMap<String, Bridge>,
+    configs: Vec<RemoteBridgeConfig>,
+    running: bool,
+}
+
+/// Remote adapter federating zones from other bridges
+pub struct RemoteAdapter {
+    state: Arc<RwLock<RemoteState>>,
+    bus: SharedBus,
+    http: Client,
+    /// Long-lived SSE requests must not be subject to the request timeout
+    events_http: Client,
+    /// Wrapped in RwLock to allow creating fresh token on restart
+    shutdown: Arc<RwLock<CancellationToken>>,
+}
+
+impl RemoteAdapter {
+    pub fn new(bus: SharedBus) -> Self {
+        let adapter = Self {
+            state: Arc::new(RwLock::new(RemoteState::default())),
+            bus,
+            http: Client::builder()
+                .timeout(HTTP_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            events_http: Client::builder()
+                .connect_timeout(HTTP_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            shutdown: Arc::new(RwLock::new(CancellationToken::new())),
+        };
+        // Load saved config synchronously at startup
+        adapter.load_config_sync();
+        adapter
+    }
+
+    /// Load config from disk (sync, for startup)
+    fn load_config_sync(&self) {
+        let path = config_path();
+        if path.exists() {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => match serde_json::from_str::<SavedRemoteConfig>(&content) {
+                    Ok(saved) => {
+                        // Use try_write to avoid async in sync context
+                        if let Ok(mut state) = self.state.try_write() {
+                            tracing::info!(
+                                "Loaded remote config from disk ({} bridges)",
+                                saved.bridges.len()
+                            );
+                            state.configs = saved.bridges;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to parse remote config: {}", e),
+                },
+                Err(e) => tracing::warn!("Failed to read remote config: {}", e),
+            }
+        }
+    }
+
+    /// Save config to disk
+    async fn save_config(&self) {
+        let saved = SavedRemoteConfig {
+            bridges: self.state.read().await.configs.clone(),
+        };
+        let path = config_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(&saved) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save remote config: {}", e);
+                } else {
+                    tracing::info!("Saved remote config to disk");
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize remote config: {}", e),
+        }
+    }
+
+    /// Configure bridges (takes effect on next start)
+    pub async fn configure(&self, bridges: Vec<RemoteBridgeConfig>) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for bridge in &bridges {
+            if bridge.name.trim().is_empty() {
+                return Err(anyhow!("Bridge name must not be empty"));
+            }
+            if bridge.name.contains(':') {
+                return Err(anyhow!("Bridge name must not contain ':'"));
+            }
+            if !names.insert(bridge.name.as_str()) {
+                return Err(anyhow!("Duplicate bridge name: {}", bridge.name));
+            }
+            if !bridge.url.starts_with("http://") && !bridge.url.starts_with("https://") {
+                return Err(anyhow!("Invalid bridge URL: {}", bridge.url));
+            }
+        }
+
+        self.state.write().await.configs = bridges;
+        self.save_config().await;
+        Ok(())
+    }
+
+    /// Get configured bridges
+    pub async fn get_config(&self) -> Vec<RemoteBridgeConfig> {
+        self.state.read().await.configs.clone()
+    }
+
+    /// Whether any bridge is configured
+    pub async fn is_configured(&self) -> bool {
+        !self.state.read().await.configs.is_empty()
+    }
+
+    /// Start following all bridges (internal - use Startable trait)
+    async fn start_internal(&self) -> Result<()> {
+        let configs = {
+            let mut state = self.state.write().await;
+            if state.running {
+                return Ok(());
+            }
+            state.running = true;
+            state.bridges = state
+                .configs
+                .iter()
+                .map(|c| (c.name.clone(), Bridge::new(c.clone())))
+                .collect();
+            state.configs.clone()
+        };
+
+        // Create fresh cancellation token for this run (previous token may be cancelled)
+        let shutdown = {
+            let mut token = self.shutdown.write().await;
+            *token = CancellationToken::new();
+            token.clone()
+        };
+
+        for config in configs {
+            let state = self.state.clone();
+            let bus = self.bus.clone();
+            let http = self.http.clone();
+            let events_http = self.events_http.clone();
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                Self::bridge_loop(config, state, bus, http, events_http, shutdown).await;
+            });
+        }
+
+        tracing::info!("Remote adapter started");
+        Ok(())
+    }
+
+    /// Follow one bridge, reconnecting with backoff
+    async fn bridge_loop(
+        config: RemoteBridgeConfig,
+        state: Arc<RwLock<RemoteState>>,
+        bus: SharedBus,
+        http: Client,
+        events_http: Client,
+        shutdown: CancellationToken,
+    ) {
+        let mut backoff = RECONNECT_BACKOFF_MIN;
+
+        loop {
+            let result = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                result = Self::follow_bridge(&config, &state, &bus, &http, &events_http) => result,
+            };
+
+            let was_connected = {
+                let mut s = state.write().await;
+                let Some(bridge) = s.bridges.get_mut(&config.name) else {
+                    break;
+                };
+                let was_connected = bridge.connected;
+                bridge.connected = false;
+                if let Err(e) = &result {
+                    bridge.last_error = Some(e.to_string());
+                }
+                for zone_id in bridge.clear_zones() {
+                    bus.publish(BusEvent::ZoneRemoved { zone_id });
+                }
+                was_connected
+            };
+            if was_connected {
+                backoff = RECONNECT_BACKOFF_MIN;
+            }
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Remote bridge {} unavailable: {} (retrying in {:?})",
+                    config.name,
+                    e,
+                    backoff
+                );
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX);
+        }
+
+        tracing::info!("Remote bridge {} loop stopped", config.name);
+    }
+
+    /// Sync zones and stream events until the connection fails
+    async fn follow_bridge(
+        config: &RemoteBridgeConfig,
+        state: &Arc<RwLock<RemoteState>>,
+        bus: &SharedBus,
+        http: &Client,
+        events_http: &Client,
+    ) -> Result<()> {
+        let base = config.url.trim_end_matches('/');
+
+        let mut response = events_http.get(format!("{}/events", base)).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("events: HTTP {}", response.status()));
+        }
+        // Subscribe before listing so no change falls between the two
+        Self::resync(config, state, bus, http).await?;
+        {
+            let mut s = state.write().await;
+            if let Some(bridge) = s.bridges.get_mut(&config.name) {
+                bridge.connected = true;
+                bridge.last_error = None;
+            }
+        }
+        tracing::info!("Remote bridge {} connected", config.name);
+
+        let mut parser = SseParser::new();
+        let mut resync_interval = interval(RESYNC_INTERVAL);
+        resync_interval.tick().await;
+
+        loop {
+            tokio::select! {
+                chunk = tokio::time::timeout(EVENTS_IDLE_TIMEOUT, response.chunk()) => {
+                    let chunk = match chunk {
+                        Ok(Ok(Some(chunk))) => chunk,
+                        Ok(Ok(None)) => return Err(anyhow!("event stream closed")),
+                        Ok(Err(e)) => return Err(e.into()),
+                        Err(_) => return Err(anyhow!("event stream idle")),
+                    };
+                    let events = parser.push(&chunk);
+                    if events.is_empty() {
+                        continue;
+                    }
+                    let mut s = state.write().await;
+                    let Some(bridge) = s.bridges.get_mut(&config.name) else {
+                        return Ok(());
+                    };
+                    for data in events {
+                        match serde_json::from_str::<BusEvent>(&data) {
+                            Ok(event) => apply_remote_event(bus, bridge, event),
+                            Err(e) => tracing::debug!(
+                                "Remote bridge {}: unhandled event: {}",
+                                config.name,
+                                e
+                            ),
+                        }
+                    }
+                }
+                _ = resync_interval.tick() => {
+                    Self::resync(config, state, bus, http).await?;
+                }
+            }
+        }
+    }
+
+    /// Fetch the bridge's zone list and reconcile
+    async fn resync(
+        config: &RemoteBridgeConfig,
+        state: &Arc<RwLock<RemoteState>>,
+        bus: &SharedBus,
+        http: &Client,
+    ) -> Result<()> {
+        let base = config.url.trim_end_matches('/');
+        let response = http.get(format!("{}/zones", base)).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("zones: HTTP {}", response.status()));
+        }
+        let zones: RemoteZonesResponse = response.json().await?;
+
+        let mut s = state.write().await;
+        if let Some(bridge) = s.bridges.get_mut(&config.name) {
+            apply_zone_list(bus, bridge, zones.zones);
+        }
+        Ok(())
+    }
+
+    /// Stop following bridges (internal - use Startable trait)
+    async fn stop_internal(&self) {
+        // Cancel background tasks first
+        self.shutdown.read().await.cancel();
+
+        let mut state = self.state.write().await;
+        state.running = false;
+        for bridge in state.bridges.values_mut() {
+            for zone_id in bridge.clear_zones() {
+                self.bus.publish(BusEvent::ZoneRemoved { zone_id });
+            }
+        }
+        state.bridges.clear();
+        tracing::info!("Remote adapter stopped");
+    }
+
+    /// Get adapter status
+    pub async fn get_status(&self) -> RemoteStatus {
+        let state = self.state.read().await;
+        let mut bridges: Vec<_> = state
+            .bridges
+            .values()
+            .map(|b| RemoteBridgeSummary {
+                name: b.config.name.clone(),
+                url: b.config.url.clone(),
+                connected: b.connected,
+                zone_count: b.zones.len(),
+                last_error: b.last_error.clone(),
+            })
+            .collect();
+        bridges.sort_by(|a, b| a.name.cmp(&b.name));
+        RemoteStatus {
+            running: state.running,
+            bridge_count: bridges.len(),
+            bridges,
+        }
+    }
+
+    /// Get all federated zones
+    pub async fn get_zones(&self) -> Vec<RemoteZoneSummary> {
+        let state = self.state.read().await;
+        let mut zones: Vec<_> = state
+            .bridges
+            .values()
+            .flat_map(|b| {
+                b.zones.iter().map(|(id, z)| RemoteZoneSummary {
+                    zone_id: b.zone_id(id),
+                    zone_name: z.zone_name.clone(),
+                    bridge: b.config.name.clone(),
+                    source: z.source.clone(),
+                    state: z.state.clone(),
+                })
+            })
+            .collect();
+        zones.sort_by(|a, b| a.zone_id.cmp(&b.zone_id));
+        zones
+    }
+
+    /// Resolve a local zone ID to (bridge base URL, remote zone ID)
+    async fn resolve(&self, zone_id: &str) -> Result<(String, String)> {
+        let (name, remote_id) =
+            split_zone_id(zone_id).ok_or_else(|| anyhow!("Invalid zone_id: {}", zone_id))?;
+        let state = self.state.read().await;
+        let bridge = state
+            .bridges
+            .get(name)
+            .ok_or_else(|| anyhow!("Remote bridge not found: {}", name))?;
+        if !bridge.zones.contains_key(remote_id) {
+            return Err(anyhow!("Zone not found: {}", zone_id));
+        }
+        Ok((
+            bridge.config.url.trim_end_matches('/').to_string(),
+            remote_id.to_string(),
+        ))
+    }
+
+    /// Whether a federated zone is currently known
+    pub async fn has_zone(&self, zone_id: &str) -> bool {
+        self.resolve(zone_id).await.is_ok()
+    }
+
+    /// Fetch now playing from the owning bridge
+    pub async fn get_now_playing(&self, zone_id: &str) -> Result<RemoteNowPlaying> {
+        let (base, remote_id) = self.resolve(zone_id).await?;
+        let response = self
+            .http
+            .get(format!("{}/now_playing", base))
+            .query(&[("zone_id", remote_id.as_str())])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("now_playing: HTTP {}", response.status()));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Fetch artwork from the owning bridge
+    ///
+    /// Returns (content_type, bytes).
+    pub async fn get_image(
+        &self,
+        zone_id: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(String, Vec<u8>)> {
+        let (base, remote_id) = self.resolve(zone_id).await?;
+        let response = self
+            .http
+            .get(format!("{}/now_playing/image", base))
+            .query(&[
+                ("zone_id", remote_id),
+                ("width", width.to_string()),
+                ("height", height.to_string()),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("image: HTTP {}", response.status()));
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("image/jpeg")
+            .to_string();
+        Ok((content_type, response.bytes().await?.to_vec()))
+    }
+
+    /// Proxy a control action to the owning bridge
+    pub async fn control(
+        &self,
+        zone_id: &str,
+        action: &str,
+        value: Option<serde_json::Value>,
+    ) -> Result<()> {
+        let (base, remote_id) = self.resolve(zone_id).await?;
+        let response = self
+            .http
+            .post(format!("{}/control", base))
+            .json(&serde_json::json!({
+                "zone_id": remote_id,
+                "action": action,
+                "value": value,
+            }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|body| body.get("error").and_then(|e| e.as_str()).map(String::from))
+                .unwrap_or_else(|| format!("HTTP {}", status));
+            return Err(anyhow!("Remote control failed: {}", error));
+        }
+        Ok(())
+    }
+}
+
+// Startable trait implementation via macro
+crate::impl_startable!(RemoteAdapter, "remote", is_configured);
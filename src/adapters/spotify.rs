@@ -0,0 +1,901 @@
+//! Spotify Connect adapter - exposes librespot receivers as zones
+//!
+//! Two backends are supported per receiver:
+//! - librespot: its `--onevent` hook posts the player event environment to
+//!   `POST /spotify/onevent?receiver=<name>` (e.g. `env | curl --data-binary @- ...`).
+//!   Read-only: librespot has no control API.
+//! - go-librespot: its local HTTP API is polled for status and used for
+//!   play/pause/next/previous/volume.
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+
+use crate::bus::{BusEvent, PlaybackState, SharedBus, VolumeControl, VolumeScale, Zone};
+use crate::config::get_config_dir;
+
+const SPOTIFY_CONFIG_FILE: &str = "spotify-config.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+/// librespot reports volume as 0..=65535
+const LIBRESPOT_VOLUME_MAX: f64 = 65535.0;
+/// Report a seek when polled position drifts this far from the extrapolated one
+const SEEK_THRESHOLD_MS: i64 = 3000;
+/// Hosts Spotify serves cover art from
+const COVER_HOSTS: &[&str] = &["i.scdn.co"];
+
+/// Whether a cover URL points at Spotify's image CDN
+///
+/// Cover URLs arrive through the unauthenticated onevent hook and are fetched
+/// server-side, so anything else is dropped rather than proxied.
+fn is_cover_url(url: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(url) else {
+        return false;
+    };
+    url.scheme() == "https"
+        && url.username().is_empty()
+        && url.password().is_none()
+        && url.port().is_none()
+        && url.host_str().is_some_and(|h| COVER_HOSTS.contains(&h))
+}
+
+fn config_path() -> PathBuf {
+    get_config_dir().join(SPOTIFY_CONFIG_FILE)
+}
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// How a receiver reports its state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SpotifyBackend {
+    /// librespot `--onevent` hook (push, read-only)
+    Librespot,
+    /// go-librespot local API (e.g. "http://localhost:3678")
+    GoLibrespot { url: String },
+}
+
+/// A Spotify Connect receiver
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpotifyReceiverConfig {
+    /// Zone name (also used in the zone_id)
+    pub name: String,
+    pub backend: SpotifyBackend,
+}
+
+/// Saved config for persistence
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedSpotifyConfig {
+    receivers: Vec<SpotifyReceiverConfig>,
+}
+
+// =============================================================================
+// librespot --onevent parsing
+// =============================================================================
+
+/// Parse an `env` dump from the librespot `--onevent` hook
+///
+/// librespot separates multiple values (ARTISTS, COVERS) with newlines, so a
+/// line that doesn't start a new `KEY=` continues the previous value.
+pub fn parse_onevent_env(body: &str) -> HashMap<String, String> {
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in body.lines() {
+        let key = line.split_once('=').map(|(k, _)| k).filter(|k| {
+            !k.is_empty()
+                && k.chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        });
+        match key {
+            Some(key) => {
+                let value = &line[key.len() + 1..];
+                vars.insert(key.to_string(), value.to_string());
+                current = Some(key.to_string());
+            }
+            None => {
+                if let Some(value) = current.as_ref().and_then(|k| vars.get_mut(k)) {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+
+    vars
+}
+
+// =============================================================================
+// go-librespot API types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct GoLibrespotStatus {
+    #[serde(default)]
+    stopped: bool,
+    #[serde(default)]
+    paused: bool,
+    #[serde(default)]
+    buffering: bool,
+    #[serde(default)]
+    volume: u32,
+    #[serde(default)]
+    volume_steps: u32,
+    #[serde(default)]
+    device_name: Option<String>,
+    #[serde(default)]
+    track: Option<GoLibrespotTrack>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoLibrespotTrack {
+    uri: String,
+    name: String,
+    #[serde(default)]
+    artist_names: Vec<String>,
+    #[serde(default)]
+    album_name: String,
+    #[serde(default)]
+    album_cover_url: Option<String>,
+    #[serde(default)]
+    position: u64,
+    #[serde(default)]
+    duration: u64,
+}
+
+// =============================================================================
+// Receiver state
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+struct SpotifyTrack {
+    uri: String,
+    title: String,
+    artist: String,
+    album: String,
+    cover_url: Option<String>,
+    duration_ms: u64,
+}
+
+struct Receiver {
+    config: SpotifyReceiverConfig,
+    state: String,
+    track: Option<SpotifyTrack>,
+    position_ms: u64,
+    position_at: Instant,
+    /// Volume in percent
+    volume: Option<f32>,
+    /// go-librespot volume steps (volume resolution)
+    volume_steps: u32,
+    /// Connected Spotify client (librespot) or device name (go-librespot)
+    client_name: Option<String>,
+    reachable: bool,
+}
+
+impl Receiver {
+    fn new(config: SpotifyReceiverConfig) -> Self {
+        Self {
+            config,
+            state: "stopped".to_string(),
+            track: None,
+            position_ms: 0,
+            position_at: Instant::now(),
+            volume: None,
+            volume_steps: 0,
+            client_name: None,
+            reachable: false,
+        }
+    }
+
+    fn zone_id(&self) -> String {
+        format!("spotify:{}", self.config.name)
+    }
+
+    fn is_controllable(&self) -> bool {
+        matches!(self.config.backend, SpotifyBackend::GoLibrespot { .. })
+    }
+
+    /// Current position in ms, extrapolated while playing
+    fn position_ms(&self) -> u64 {
+        let mut position = self.position_ms;
+        if self.state == "playing" {
+            position += self.position_at.elapsed().as_millis() as u64;
+        }
+        match &self.track {
+            Some(track) if track.duration_ms > 0 => position.min(track.duration_ms),
+            _ => position,
+        }
+    }
+
+    fn set_position(&mut self, position_ms: u64) {
+        self.position_ms = position_ms;
+        self.position_at = Instant::now();
+    }
+
+    fn set_state(&mut self, state: &str) -> bool {
+        if self.state == state {
+            return false;
+        }
+        // Freeze the extrapolated position at the transition
+        let position = self.position_ms();
+        self.state = state.to_string();
+        self.set_position(position);
+        true
+    }
+
+    fn to_zone(&self) -> Zone {
+        Zone {
+            zone_id: self.zone_id(),
+            zone_name: self.config.name.clone(),
+            state: PlaybackState::from(self.state.as_str()),
+            volume_control: self.volume.map(|v| VolumeControl {
+                value: v,
+                min: 0.0,
+                max: 100.0,
+                step: 1.0,
+                is_muted: false,
+                scale: VolumeScale::Percentage,
+                output_id: None,
+            }),
+            now_playing: self.track.as_ref().map(|t| crate::bus::NowPlaying {
+                title: t.title.clone(),
+                artist: t.artist.clone(),
+                album: t.album.clone(),
+                image_key: t.cover_url.clone(),
+                seek_position: Some(self.position_ms() as f64 / 1000.0),
+                duration: Some(t.duration_ms as f64 / 1000.0),
+                metadata: None,
+            }),
+            source: "spotify".to_string(),
+            is_controllable: self.is_controllable(),
+            is_seekable: self.is_controllable(),
+            last_updated: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+}
+
+/// What an update changed
+#[derive(Debug, Default)]
+struct Changes {
+    state: bool,
+    track: bool,
+    seek: bool,
+}
+
+fn publish_changes(bus: &SharedBus, receiver: &Receiver, changes: &Changes) {
+    if changes.state {
+        bus.publish(BusEvent::ZoneUpdated {
+            zone_id: receiver.zone_id(),
+            display_name: receiver.config.name.clone(),
+            state: receiver.state.clone(),
+        });
+    }
+    if changes.track {
+        let track = receiver.track.as_ref();
+        bus.publish(BusEvent::NowPlayingChanged {
+            zone_id: receiver.zone_id(),
+            title: track.map(|t| t.title.clone()),
+            artist: track.map(|t| t.artist.clone()),
+            album: track.map(|t| t.album.clone()),
+            image_key: track.and_then(|t| t.cover_url.clone()),
+        });
+    }
+    if changes.seek {
+        bus.publish(BusEvent::SeekPositionChanged {
+            zone_id: receiver.zone_id(),
+            position: (receiver.position_ms() / 1000) as i64,
+        });
+    }
+}
+
+/// Apply a librespot player event
+fn apply_onevent(receiver: &mut Receiver, vars: &HashMap<String, String>) -> Result<Changes> {
+    let event = vars
+        .get("PLAYER_EVENT")
+        .ok_or_else(|| anyhow!("Missing PLAYER_EVENT"))?;
+    let position = vars.get("POSITION_MS").and_then(|v| v.trim().parse().ok());
+    let mut changes = Changes::default();
+
+    match event.as_str() {
+        "track_changed" => {
+            let track = SpotifyTrack {
+                uri: vars
+                    .get("URI")
+                    .or_else(|| vars.get("TRACK_ID"))
+                    .cloned()
+                    .unwrap_or_default(),
+                title: vars.get("NAME").cloned().unwrap_or_default(),
+                artist: vars
+                    .get("ARTISTS")
+                    .map(|a| a.lines().collect::<Vec<_>>().join(", "))
+                    .unwrap_or_default(),
+                album: vars.get("ALBUM").cloned().unwrap_or_default(),
+                cover_url: vars
+                    .get("COVERS")
+                    .and_then(|c| c.lines().next())
+                    .filter(|c| is_cover_url(c))
+                    .map(|c| c.to_string()),
+                duration_ms: vars
+                    .get("DURATION_MS")
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(0),
+            };
+            changes.track = receiver.track.as_ref() != Some(&track);
+            receiver.track = Some(track);
+            receiver.set_position(0);
+        }
+        "playing" | "started" => {
+            changes.state = receiver.set_state("playing");
+        }
+        "paused" => {
+            changes.state = receiver.set_state("paused");
+        }
+        "stopped" | "session_disconnected" => {
+            changes.state = receiver.set_state("stopped");
+            changes.track = receiver.track.take().is_some();
+            if event == "session_disconnected" {
+                receiver.client_name = None;
+            }
+        }
+        "loading" | "preloading" => {
+            changes.state = receiver.set_state("loading");
+        }
+        "seeked" | "position_correction" => {
+            changes.seek = true;
+        }
+        "volume_changed" | "volume_set" => {
+            receiver.volume = vars
+                .get("VOLUME")
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .map(|v| (v / LIBRESPOT_VOLUME_MAX * 100.0).round() as f32);
+        }
+        "session_client_changed" => {
+            receiver.client_name = vars.get("CLIENT_NAME").cloned();
+        }
+        _ => {}
+    }
+
+    if let Some(position) = position {
+        receiver.set_position(position);
+    }
+    receiver.reachable = true;
+    Ok(changes)
+}
+
+/// Apply a go-librespot status snapshot
+fn apply_status(receiver: &mut Receiver, status: GoLibrespotStatus) -> Changes {
+    let mut changes = Changes::default();
+
+    let state = if status.stopped || status.track.is_none() {
+        "stopped"
+    } else if status.buffering {
+        "buffering"
+    } else if status.paused {
+        "paused"
+    } else {
+        "playing"
+    };
+
+    let track = status.track.map(|t| {
+        (
+            SpotifyTrack {
+                uri: t.uri,
+                title: t.name,
+                artist: t.artist_names.join(", "),
+                album: t.album_name,
+                cover_url: t.album_cover_url.filter(|u| is_cover_url(u)),
+                duration_ms: t.duration,
+            },
+            t.position,
+        )
+    });
+
+    let expected = receiver.position_ms() as i64;
+    changes.state = receiver.set_state(state);
+    match track {
+        Some((track, position)) => {
+            changes.track = receiver.track.as_ref() != Some(&track);
+            changes.seek = !changes.track && (position as i64 - expected).abs() > SEEK_THRESHOLD_MS;
+            receiver.track = Some(track);
+            receiver.set_position(position);
+        }
+        None => {
+            changes.track = receiver.track.take().is_some();
+        }
+    }
+
+    if status.volume_steps > 0 {
+        receiver.volume_steps = status.volume_steps;
+        receiver.volume =
+            Some((status.volume as f64 / status.volume_steps as f64 * 100.0).round() as f32);
+    }
+    receiver.client_name = status.device_name;
+    changes
+}
+
+// =============================================================================
+// API types
+// =============================================================================
+
+/// Spotify adapter status
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotifyStatus {
+    pub running: bool,
+    pub receiver_count: usize,
+    pub receivers: Vec<SpotifyReceiverSummary>,
+}
+
+/// Receiver summary for status response
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotifyReceiverSummary {
+    pub name: String,
+    pub backend: String,
+    pub state: String,
+    /// Whether the receiver has reported in (event received or API reachable)
+    pub reachable: bool,
+    pub client_name: Option<String>,
+}
+
+/// Zone info for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotifyZone {
+    pub zone_id: String,
+    pub zone_name: String,
+    pub state: String,
+    pub is_controllable: bool,
+}
+
+/// Now playing info from a Spotify Connect receiver
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotifyNowPlaying {
+    pub zone_id: String,
+    pub line1: String,
+    pub line2: String,
+    pub line3: String,
+    pub is_playing: bool,
+    pub volume: Option<f32>,
+    pub volume_min: f32,
+    pub volume_max: f32,
+    pub seek_position: Option<i64>,
+    pub length: Option<u32>,
+    pub image_key: Option<String>,
+    /// Spotify URI of the current track
+    pub uri: Option<String>,
+}
+
+// =============================================================================
+// Adapter
+// =============================================================================
+
+#[derive(Default)]
+struct SpotifyState {
+    receivers: HashMap<String, Receiver>,
+    configs: Vec<SpotifyReceiverConfig>,
+    running: bool,
+}
+
+/// Spotify Connect adapter for librespot and go-librespot receivers
+pub struct SpotifyAdapter {
+    state: Arc<RwLock<SpotifyState>>,
+    bus: SharedBus,
+    http: Client,
+    /// Wrapped in RwLock to allow creating fresh token on restart
+    shutdown: Arc<RwLock<CancellationToken>>,
+}
+
+impl SpotifyAdapter {
+    pub fn new(bus: SharedBus) -> Self {
+        let adapter = Self {
+            state: Arc::new(RwLock::new(SpotifyState::default())),
+            bus,
+            http: Client::builder()
+                .timeout(HTTP_TIMEOUT)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+            shutdown: Arc::new(RwLock::new(CancellationToken::new())),
+        };
+        // Load saved config synchronously at startup
+        adapter.load_config_sync();
+        adapter
+    }
+
+    /// Load config from disk (sync, for startup)
+    fn load_config_sync(&self) {
+        let path = config_path();
+        if path.exists() {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => match serde_json::from_str::<SavedSpotifyConfig>(&content) {
+                    Ok(saved) => {
+                        // Use try_write to avoid async in sync context
+                        if let Ok(mut state) = self.state.try_write() {
+                            tracing::info!(
+                                "Loaded Spotify config from disk ({} receivers)",
+                                saved.receivers.len()
+                            );
+                            state.configs = saved.receivers;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to parse Spotify config: {}", e),
+                },
+                Err(e) => tracing::warn!("Failed to read Spotify config: {}", e),
+            }
+        }
+    }
+
+    /// Save config to disk
+    async fn save_config(&self) {
+        let saved = SavedSpotifyConfig {
+            receivers: self.state.read().await.configs.clone(),
+        };
+        let path = config_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(&saved) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save Spotify config: {}", e);
+                } else {
+                    tracing::info!("Saved Spotify config to disk");
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize Spotify config: {}", e),
+        }
+    }
+
+    /// Configure receivers (takes effect on next start)
+    pub async fn configure(&self, receivers: Vec<SpotifyReceiverConfig>) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for receiver in &receivers {
+            if receiver.name.trim().is_empty() {
+                return Err(anyhow!("Receiver name must not be empty"));
+            }
+            if !names.insert(receiver.name.as_str()) {
+                return Err(anyhow!("Duplicate receiver name: {}", receiver.name));
+            }
+            if let SpotifyBackend::GoLibrespot { url } = &receiver.backend {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(anyhow!("Invalid go-librespot URL: {}", url));
+                }
+            }
+        }
+
+        self.state.write().await.configs = receivers;
+        self.save_config().await;
+        Ok(())
+    }
+
+    /// Get configured receivers
+    pub async fn get_config(&self) -> Vec<SpotifyReceiverConfig> {
+        self.state.read().await.configs.clone()
+    }
+
+    /// Check if any receiver is configured
+    pub async fn is_configured(&self) -> bool {
+        !self.state.read().await.configs.is_empty()
+    }
+
+    /// Start polling/accepting events (internal - use Startable trait)
+    async fn start_internal(&self) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            if state.running {
+                return Ok(());
+            }
+            state.running = true;
+            state.receivers = state
+                .configs
+                .iter()
+                .map(|c| (c.name.clone(), Receiver::new(c.clone())))
+                .collect();
+            for receiver in state.receivers.values() {
+                self.bus.publish(BusEvent::ZoneDiscovered {
+                    zone: receiver.to_zone(),
+                });
+            }
+        }
+
+        // Create fresh cancellation token for this run (previous token may be cancelled)
+        let shutdown = {
+            let mut token = self.shutdown.write().await;
+            *token = CancellationToken::new();
+            token.clone()
+        };
+
+        let state = self.state.clone();
+        let bus = self.bus.clone();
+        let http = self.http.clone();
+        tokio::spawn(async move {
+            Self::poll_loop(state, bus, http, shutdown).await;
+        });
+
+        tracing::info!("Spotify adapter started");
+        Ok(())
+    }
+
+    /// Poll go-librespot receivers
+    async fn poll_loop(
+        state: Arc<RwLock<SpotifyState>>,
+        bus: SharedBus,
+        http: Client,
+        shutdown: CancellationToken,
+    ) {
+        let mut poll_interval = interval(POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    tracing::info!("Spotify poll loop shutting down");
+                    break;
+                }
+                _ = poll_interval.tick() => {
+                    let targets: Vec<(String, String)> = {
+                        let s = state.read().await;
+                        s.receivers
+                            .values()
+                            .filter_map(|r| match &r.config.backend {
+                                SpotifyBackend::GoLibrespot { url } => {
+                                    Some((r.config.name.clone(), url.clone()))
+                                }
+                                SpotifyBackend::Librespot => None,
+                            })
+                            .collect()
+                    };
+
+                    for (name, url) in targets {
+                        let result = Self::fetch_status(&http, &url).await;
+                        let mut s = state.write().await;
+                        let Some(receiver) = s.receivers.get_mut(&name) else {
+                            continue;
+                        };
+                        match result {
+                            Ok(status) => {
+                                receiver.reachable = true;
+                                let changes = apply_status(receiver, status);
+                                publish_changes(&bus, receiver, &changes);
+                            }
+                            Err(e) => {
+                                if receiver.reachable {
+                                    tracing::warn!("go-librespot {} unreachable: {}", name, e);
+                                }
+                                receiver.reachable = false;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::info!("Spotify poll loop stopped");
+    }
+
+    async fn fetch_status(http: &Client, url: &str) -> Result<GoLibrespotStatus> {
+        let response = http
+            .get(format!("{}/status", url.trim_end_matches('/')))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("HTTP {}", response.status()));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Ingest a librespot `--onevent` environment dump
+    pub async fn handle_onevent(&self, name: &str, body: &str) -> Result<()> {
+        let vars = parse_onevent_env(body);
+        let mut state = self.state.write().await;
+        let receiver = state
+            .receivers
+            .get_mut(name)
+            .ok_or_else(|| anyhow!("Spotify receiver not found: {}", name))?;
+        if receiver.config.backend != SpotifyBackend::Librespot {
+            return Err(anyhow!(
+                "Spotify receiver {} is not a librespot receiver",
+                name
+            ));
+        }
+
+        let changes = apply_onevent(receiver, &vars)?;
+        tracing::debug!(
+            "librespot event for {}: {}",
+            name,
+            vars.get("PLAYER_EVENT").map(String::as_str).unwrap_or("")
+        );
+        publish_changes(&self.bus, receiver, &changes);
+        Ok(())
+    }
+
+    /// Stop polling (internal - use Startable trait)
+    async fn stop_internal(&self) {
+        // Cancel background tasks first
+        self.shutdown.read().await.cancel();
+
+        let mut state = self.state.write().await;
+        state.running = false;
+        state.receivers.clear();
+        tracing::info!("Spotify adapter stopped");
+    }
+
+    /// Get adapter status
+    pub async fn get_status(&self) -> SpotifyStatus {
+        let state = self.state.read().await;
+        let mut receivers: Vec<_> = state
+            .receivers
+            .values()
+            .map(|r| SpotifyReceiverSummary {
+                name: r.config.name.clone(),
+                backend: match r.config.backend {
+                    SpotifyBackend::Librespot => "librespot",
+                    SpotifyBackend::GoLibrespot { .. } => "go-librespot",
+                }
+                .to_string(),
+                state: r.state.clone(),
+                reachable: r.reachable,
+                client_name: r.client_name.clone(),
+            })
+            .collect();
+        receivers.sort_by(|a, b| a.name.cmp(&b.name));
+        SpotifyStatus {
+            running: state.running,
+            receiver_count: receivers.len(),
+            receivers,
+        }
+    }
+
+    /// Get all receivers as zones
+    pub async fn get_zones(&self) -> Vec<SpotifyZone> {
+        let state = self.state.read().await;
+        let mut zones: Vec<_> = state
+            .receivers
+            .values()
+            .map(|r| SpotifyZone {
+                zone_id: r.config.name.clone(),
+                zone_name: r.config.name.clone(),
+                state: r.state.clone(),
+                is_controllable: r.is_controllable(),
+            })
+            .collect();
+        zones.sort_by(|a, b| a.zone_name.cmp(&b.zone_name));
+        zones
+    }
+
+    /// Get now playing info for a receiver
+    pub async fn get_now_playing(&self, name: &str) -> Option<SpotifyNowPlaying> {
+        let state = self.state.read().await;
+        let receiver = state.receivers.get(name)?;
+        let track = receiver.track.as_ref();
+
+        Some(SpotifyNowPlaying {
+            zone_id: name.to_string(),
+            line1: track
+                .map(|t| t.title.clone())
+                .unwrap_or_else(|| receiver.config.name.clone()),
+            line2: track.map(|t| t.artist.clone()).unwrap_or_default(),
+            line3: track.map(|t| t.album.clone()).unwrap_or_default(),
+            is_playing: receiver.state == "playing",
+            volume: receiver.volume,
+            volume_min: 0.0,
+            volume_max: 100.0,
+            seek_position: track.map(|_| (receiver.position_ms() / 1000) as i64),
+            length: track.map(|t| (t.duration_ms / 1000) as u32),
+            image_key: track.and_then(|t| t.cover_url.clone()),
+            uri: track.map(|t| t.uri.clone()),
+        })
+    }
+
+    /// Fetch the current cover art of a receiver
+    ///
+    /// Returns (content_type, bytes).
+    pub async fn get_image(&self, name: &str) -> Result<(String, Vec<u8>)> {
+        let url = {
+            let state = self.state.read().await;
+            state
+                .receivers
+                .get(name)
+                .and_then(|r| r.track.as_ref())
+                .and_then(|t| t.cover_url.clone())
+                .filter(|u| is_cover_url(u))
+                .ok_or_else(|| anyhow!("No cover art for {}", name))?
+        };
+
+        let response = self.http.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Cover fetch failed: {}", response.status()));
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("image/jpeg")
+            .to_string();
+        Ok((content_type, response.bytes().await?.to_vec()))
+    }
+
+    /// Send control command to a receiver
+    ///
+    /// Actions: play, pause, play_pause, next, previous, vol_abs (percent),
+    /// vol_up, vol_down, seek (seconds). Only go-librespot receivers can be
+    /// controlled.
+    pub async fn control(&self, name: &str, action: &str, value: Option<f64>) -> Result<()> {
+        let (url, volume, volume_steps) = {
+            let state = self.state.read().await;
+            let receiver = state
+                .receivers
+                .get(name)
+                .ok_or_else(|| anyhow!("Spotify receiver not found: {}", name))?;
+            match &receiver.config.backend {
+                SpotifyBackend::GoLibrespot { url } => (
+                    url.trim_end_matches('/').to_string(),
+                    receiver.volume,
+                    receiver.volume_steps,
+                ),
+                SpotifyBackend::Librespot => {
+                    return Err(anyhow!("librespot receiver {} is read-only", name))
+                }
+            }
+        };
+
+        let (path, body) = match action {
+            "play" => ("/player/resume", None),
+            "pause" => ("/player/pause", None),
+            "play_pause" | "playpause" => ("/player/playpause", None),
+            "next" => ("/player/next", None),
+            "previous" | "prev" => ("/player/prev", None),
+            "seek" => {
+                let seconds = value.ok_or_else(|| anyhow!("seek requires a value"))?;
+                (
+                    "/player/seek",
+                    Some(json!({ "position": (seconds * 1000.0) as u64 })),
+                )
+            }
+            "vol_abs" | "volume" | "vol_up" | "volume_up" | "vol_down" | "volume_down" => {
+                if volume_steps == 0 {
+                    return Err(anyhow!("Volume of {} is not known yet", name));
+                }
+                let current = volume.unwrap_or(0.0) as f64;
+                let percent = match action {
+                    "vol_abs" | "volume" => {
+                        value.ok_or_else(|| anyhow!("{} requires a value", action))?
+                    }
+                    "vol_up" | "volume_up" => current + value.unwrap_or(1.0),
+                    _ => current - value.unwrap_or(1.0),
+                }
+                .clamp(0.0, 100.0);
+                let steps = (percent / 100.0 * volume_steps as f64).round() as u32;
+                ("/player/volume", Some(json!({ "volume": steps })))
+            }
+            _ => return Err(anyhow!("Unknown action: {}", action)),
+        };
+
+        let mut request = self.http.post(format!("{}{}", url, path));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "go-librespot {} failed: {}",
+                path,
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Startable trait implementation via macro
+crate::impl_startable!(SpotifyAdapter, "spotify", is_configured);
@@ -1,17 +1,30 @@
 //! UPnP/DLNA adapter - discovers and controls UPnP Media Renderers
 //!
 //! Uses SSDP for discovery and UPnP AV Transport service for control.
-//! Pure UPnP/DLNA has limited metadata support compared to OpenHome.
-//! Specifically, next/previous track are NOT supported by pure UPnP.
+//! Pure UPnP/DLNA has limited metadata support compared to OpenHome, and no
+//! queue: next/previous only work on tracks queued through the bridge. The
+//! bridge keeps a queue per renderer, hands the following track over with
+//! SetNextAVTransportURI for gapless playback, and advances when polling sees
+//! the renderer move on. Renderers without SetNextAVTransportURI get the next
+//! track loaded when they stop at the end of one.
+//!
+//! Media Servers (MinimServer, Asset, JRiver, ...) are discovered too; their
+//! ContentDirectory can be browsed/searched and items sent to a renderer with
+//! SetAVTransportURI, making the bridge a complete DLNA control point.
 
+use crate::adapters::browse::{BrowseItem, BrowseItemHint, BrowseList, BrowseResult, PlayMode};
+use crate::adapters::didl::{parse_didl, DidlObject};
 use crate::bus::{BusEvent, PlaybackState, SharedBus, VolumeControl as BusVolumeControl, Zone};
+use crate::config::get_config_dir;
 use futures::StreamExt;
 use quick_xml::de::from_str as xml_from_str;
+use quick_xml::escape::{escape, unescape};
 use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use ssdp_client::{SearchTarget, URN};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -19,12 +32,19 @@ use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 
 const MEDIA_RENDERER_URN: &str = "urn:schemas-upnp-org:device:MediaRenderer:1";
+const MEDIA_SERVER_URN: &str = "urn:schemas-upnp-org:device:MediaServer:1";
+const CONTENT_DIRECTORY_URN: &str = "urn:schemas-upnp-org:service:ContentDirectory:1";
 const AV_TRANSPORT_URN: &str = "urn:schemas-upnp-org:service:AVTransport:1";
 const RENDERING_CONTROL_URN: &str = "urn:schemas-upnp-org:service:RenderingControl:1";
 const SSDP_SEARCH_INTERVAL: Duration = Duration::from_secs(30);
 const POLL_INTERVAL: Duration = Duration::from_secs(2);
 const STALE_THRESHOLD: Duration = Duration::from_secs(90);
 const SOAP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default page size for browse/search requests
+pub const BROWSE_PAGE_SIZE: usize = 100;
+/// ContentDirectory root container
+const ROOT_OBJECT_ID: &str = "0";
+const QUEUE_FILE: &str = "upnp-queues.json";
 
 /// UPnP Media Renderer information
 #[derive(Debug, Clone, Serialize)]
@@ -45,6 +65,20 @@ pub struct UPnPRenderer {
     pub rendering_control_url: Option<String>,
 }
 
+/// UPnP Media Server (ContentDirectory) information
+#[derive(Debug, Clone, Serialize)]
+pub struct UPnPMediaServer {
+    pub uuid: String,
+    pub name: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub location: String,
+    #[serde(skip)]
+    pub last_seen: std::time::Instant,
+    #[serde(skip)]
+    pub content_directory_url: String,
+}
+
 /// UPnP adapter status
 #[derive(Debug, Clone, Serialize)]
 pub struct UPnPStatus {
@@ -100,11 +134,94 @@ pub struct VolumeControl {
     pub is_muted: bool,
 }
 
+/// Bridge-side play queue for a renderer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UPnPQueue {
+    pub items: Vec<DidlObject>,
+    /// Index of the track loaded on the renderer
+    #[serde(default)]
+    pub current: Option<usize>,
+    /// Index of the track handed over with SetNextAVTransportURI
+    #[serde(skip)]
+    next_set: Option<usize>,
+    /// Whether the renderer rejected SetNextAVTransportURI
+    #[serde(skip)]
+    no_gapless: bool,
+    /// Stopped or paused by the user; don't start the next track on stop
+    #[serde(skip)]
+    held: bool,
+}
+
+impl UPnPQueue {
+    fn current_item(&self) -> Option<&DidlObject> {
+        self.current.and_then(|i| self.items.get(i))
+    }
+}
+
+fn queue_path() -> PathBuf {
+    get_config_dir().join(QUEUE_FILE)
+}
+
+/// Load persisted queues (keyed by renderer UUID)
+fn load_queues() -> HashMap<String, UPnPQueue> {
+    let path = queue_path();
+    if !path.exists() {
+        return HashMap::new();
+    }
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Failed to parse UPnP queues: {}", e);
+            HashMap::new()
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to read UPnP queues: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+fn save_queues(queues: &HashMap<String, UPnPQueue>) {
+    let path = queue_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(queues) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                tracing::error!("Failed to save UPnP queues: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("Failed to serialize UPnP queues: {}", e),
+    }
+}
+
 struct UPnPState {
     renderers: HashMap<String, UPnPRenderer>,
+    servers: HashMap<String, UPnPMediaServer>,
+    /// Queues survive renderers going away (and restarts)
+    queues: HashMap<String, UPnPQueue>,
     running: bool,
 }
 
+/// Parsed device description (service URLs made absolute)
+struct DeviceDescription {
+    uuid: Option<String>,
+    friendly_name: Option<String>,
+    manufacturer: Option<String>,
+    model_name: Option<String>,
+    /// (service type, control URL)
+    services: Vec<(String, String)>,
+}
+
+impl DeviceDescription {
+    fn control_url(&self, service: &str) -> Option<String> {
+        self.services
+            .iter()
+            .find(|(service_type, _)| service_type.contains(service))
+            .map(|(_, url)| url.clone())
+    }
+}
+
 /// UPnP adapter for discovering and controlling DLNA Media Renderers
 pub struct UPnPAdapter {
     state: Arc<RwLock<UPnPState>>,
@@ -120,6 +237,8 @@ impl UPnPAdapter {
         Self {
             state: Arc::new(RwLock::new(UPnPState {
                 renderers: HashMap::new(),
+                servers: HashMap::new(),
+                queues: load_queues(),
                 running: false,
             })),
             bus,
@@ -190,6 +309,9 @@ impl UPnPAdapter {
                     if let Err(e) = Self::perform_search(&state, &bus, &http).await {
                         tracing::warn!("SSDP search failed: {}", e);
                     }
+                    if let Err(e) = Self::perform_server_search(&state, &http).await {
+                        tracing::warn!("SSDP MediaServer search failed: {}", e);
+                    }
 
                     // Cleanup stale renderers
                     Self::cleanup_stale(&state, &bus).await;
@@ -281,12 +403,8 @@ impl UPnPAdapter {
         Ok(())
     }
 
-    async fn fetch_device_info(
-        state: &Arc<RwLock<UPnPState>>,
-        http: &Client,
-        uuid: &str,
-        location: &str,
-    ) -> anyhow::Result<()> {
+    /// Fetch and parse a device description
+    async fn fetch_description(http: &Client, location: &str) -> anyhow::Result<DeviceDescription> {
         let response = http.get(location).send().await?;
         let xml = response.text().await?;
 
@@ -298,6 +416,8 @@ impl UPnPAdapter {
 
         #[derive(Deserialize)]
         struct DeviceDesc {
+            #[serde(rename = "UDN")]
+            udn: Option<String>,
             #[serde(rename = "friendlyName")]
             friendly_name: Option<String>,
             manufacturer: Option<String>,
@@ -325,29 +445,50 @@ impl UPnPAdapter {
         // Get base URL
         let base_url = Self::get_base_url(location)?;
 
+        let services = root
+            .device
+            .service_list
+            .map(|list| list.service)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|service| {
+                let url = service.control_url?;
+                Some((service.service_type, join_url(&base_url, &url)))
+            })
+            .collect();
+
+        Ok(DeviceDescription {
+            uuid: root
+                .device
+                .udn
+                .map(|udn| udn.trim().trim_start_matches("uuid:").to_string()),
+            friendly_name: root.device.friendly_name,
+            manufacturer: root.device.manufacturer,
+            model_name: root.device.model_name,
+            services,
+        })
+    }
+
+    async fn fetch_device_info(
+        state: &Arc<RwLock<UPnPState>>,
+        http: &Client,
+        uuid: &str,
+        location: &str,
+    ) -> anyhow::Result<()> {
+        let description = Self::fetch_description(http, location).await?;
+
         let mut s = state.write().await;
         if let Some(renderer) = s.renderers.get_mut(uuid) {
-            renderer.name = root
-                .device
+            renderer.name = description
                 .friendly_name
+                .clone()
                 .unwrap_or_else(|| format!("Renderer {}", &uuid[..8.min(uuid.len())]));
-            renderer.manufacturer = root.device.manufacturer;
-            renderer.model = root.device.model_name;
+            renderer.manufacturer = description.manufacturer.clone();
+            renderer.model = description.model_name.clone();
 
             // Extract service URLs
-            if let Some(services) = root.device.service_list {
-                for service in services.service {
-                    if service.service_type.contains("AVTransport") {
-                        if let Some(url) = service.control_url {
-                            renderer.av_transport_url = Some(format!("{}{}", base_url, url));
-                        }
-                    } else if service.service_type.contains("RenderingControl") {
-                        if let Some(url) = service.control_url {
-                            renderer.rendering_control_url = Some(format!("{}{}", base_url, url));
-                        }
-                    }
-                }
-            }
+            renderer.av_transport_url = description.control_url("AVTransport");
+            renderer.rendering_control_url = description.control_url("RenderingControl");
 
             tracing::info!(
                 "Got UPnP device info: {} - {} {}",
@@ -360,6 +501,81 @@ impl UPnPAdapter {
         Ok(())
     }
 
+    async fn perform_server_search(
+        state: &Arc<RwLock<UPnPState>>,
+        http: &Client,
+    ) -> anyhow::Result<()> {
+        let urn: URN = MEDIA_SERVER_URN.parse()?;
+        let search_target = SearchTarget::URN(urn);
+        let responses =
+            ssdp_client::search(&search_target, Duration::from_secs(3), 2, None).await?;
+
+        futures::pin_mut!(responses);
+
+        while let Some(response) = responses.next().await {
+            let response = match response {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::debug!("SSDP response error: {}", e);
+                    continue;
+                }
+            };
+
+            let location = response.location().to_string();
+            let uuid = match response.usn().split("::").next() {
+                Some(s) if s.starts_with("uuid:") => s.trim_start_matches("uuid:").to_string(),
+                _ => continue,
+            };
+
+            if let Some(server) = state.write().await.servers.get_mut(&uuid) {
+                server.last_seen = std::time::Instant::now();
+                continue;
+            }
+
+            if let Err(e) = Self::register_server(state, http, &location).await {
+                tracing::warn!("Failed to add UPnP MediaServer at {}: {}", location, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetch a MediaServer's description and add it to the server list
+    async fn register_server(
+        state: &Arc<RwLock<UPnPState>>,
+        http: &Client,
+        location: &str,
+    ) -> anyhow::Result<UPnPMediaServer> {
+        let description = Self::fetch_description(http, location).await?;
+        let uuid = description
+            .uuid
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Device description has no UDN"))?;
+        let content_directory_url = description
+            .control_url("ContentDirectory")
+            .ok_or_else(|| anyhow::anyhow!("Device has no ContentDirectory service"))?;
+
+        let server = UPnPMediaServer {
+            name: description
+                .friendly_name
+                .unwrap_or_else(|| format!("Media Server {}", &uuid[..8.min(uuid.len())])),
+            uuid: uuid.clone(),
+            manufacturer: description.manufacturer,
+            model: description.model_name,
+            location: location.to_string(),
+            last_seen: std::time::Instant::now(),
+            content_directory_url,
+        };
+
+        tracing::info!(
+            "Discovered UPnP MediaServer: {} at {}",
+            server.name,
+            location
+        );
+        state.write().await.servers.insert(uuid, server.clone());
+        Ok(server)
+    }
+
     async fn cleanup_stale(state: &Arc<RwLock<UPnPState>>, bus: &SharedBus) {
         let mut s = state.write().await;
         let now = std::time::Instant::now();
@@ -378,6 +594,9 @@ impl UPnPAdapter {
                 zone_id: format!("upnp:{}", uuid),
             });
         }
+
+        s.servers
+            .retain(|_, server| now.duration_since(server.last_seen) <= STALE_THRESHOLD);
     }
 
     async fn poll_loop(
@@ -440,6 +659,7 @@ impl UPnPAdapter {
         rc_url: Option<&str>,
     ) -> anyhow::Result<()> {
         // Poll transport state
+        let mut transition = None;
         if let Some(url) = av_url {
             let transport_info = Self::soap_call(
                 http,
@@ -465,6 +685,7 @@ impl UPnPAdapter {
                     let mut s = state.write().await;
                     if let Some(renderer) = s.renderers.get_mut(uuid) {
                         if renderer.state != new_state {
+                            transition = Some((renderer.state.clone(), new_state.clone()));
                             renderer.state = new_state.clone();
                             bus.publish(BusEvent::ZoneUpdated {
                                 zone_id: format!("upnp:{}", uuid),
@@ -475,6 +696,10 @@ impl UPnPAdapter {
                     }
                 }
             }
+
+            if let Err(e) = Self::advance_queue(state, bus, http, uuid, url, transition).await {
+                tracing::debug!("Failed to advance UPnP queue for {}: {}", uuid, e);
+            }
         }
 
         // Poll volume
@@ -587,6 +812,7 @@ impl UPnPAdapter {
         let mut state = self.state.write().await;
         state.running = false;
         state.renderers.clear();
+        state.servers.clear();
         tracing::info!("UPnP adapter stopped");
     }
 
@@ -615,6 +841,10 @@ impl UPnPAdapter {
             .renderers
             .values()
             .map(|r| {
+                let queued = state
+                    .queues
+                    .get(&r.uuid)
+                    .is_some_and(|q| q.current.is_some());
                 let device_name = match (&r.manufacturer, &r.model) {
                     (Some(m), Some(model)) => Some(format!("{} {}", m, model)),
                     (Some(m), None) => Some(m.clone()),
@@ -634,13 +864,17 @@ impl UPnPAdapter {
                         max: 100,
                         is_muted: r.muted,
                     }),
-                    // Pure UPnP doesn't support these
-                    unsupported: vec![
-                        "next".to_string(),
-                        "previous".to_string(),
-                        "track_metadata".to_string(),
-                        "album_art".to_string(),
-                    ],
+                    // Pure UPnP doesn't support these unless playing from the queue
+                    unsupported: if queued {
+                        Vec::new()
+                    } else {
+                        vec![
+                            "next".to_string(),
+                            "previous".to_string(),
+                            "track_metadata".to_string(),
+                            "album_art".to_string(),
+                        ]
+                    },
                 }
             })
             .collect()
@@ -663,7 +897,27 @@ impl UPnPAdapter {
         let state = self.state.read().await;
         let renderer = state.renderers.get(uuid)?;
 
-        // Pure UPnP doesn't provide track metadata
+        // Pure UPnP doesn't provide track metadata; use the queued track's
+        if let Some(item) = state.queues.get(uuid).and_then(|q| q.current_item()) {
+            return Some(UPnPNowPlaying {
+                zone_id: uuid.to_string(),
+                line1: item.title.clone(),
+                line2: item.artist.clone().unwrap_or_default(),
+                line3: item.album.clone().unwrap_or_default(),
+                is_playing: renderer.state == "playing",
+                volume: renderer.volume,
+                volume_min: 0,
+                volume_max: 100,
+                seek_position: None,
+                length: item
+                    .resources
+                    .first()
+                    .and_then(|r| r.duration.as_deref())
+                    .and_then(parse_duration),
+                image_key: item.album_art_uri.clone(),
+            });
+        }
+
         Some(UPnPNowPlaying {
             zone_id: uuid.to_string(),
             line1: renderer.name.clone(),
@@ -698,6 +952,10 @@ impl UPnPAdapter {
             )
         };
 
+        if matches!(action, "play" | "pause" | "play_pause" | "stop") {
+            self.set_held(uuid, action).await;
+        }
+
         match action {
             "play" => {
                 let url = av_url
@@ -771,11 +1029,24 @@ impl UPnPAdapter {
                 )
                 .await?;
             }
-            "next" => {
-                anyhow::bail!("Next track not supported by pure UPnP renderers");
-            }
-            "previous" | "prev" => {
-                anyhow::bail!("Previous track not supported by pure UPnP renderers");
+            "next" | "previous" | "prev" => {
+                let current = {
+                    let state = self.state.read().await;
+                    state.queues.get(uuid).and_then(|q| q.current)
+                };
+                let Some(current) = current else {
+                    anyhow::bail!(
+                        "Next/previous need a queue; pure UPnP renderers have none of their own"
+                    );
+                };
+                let index = if action == "next" {
+                    current + 1
+                } else {
+                    current
+                        .checked_sub(1)
+                        .ok_or_else(|| anyhow::anyhow!("Already at the start of the queue"))?
+                };
+                return self.play_queue_index(uuid, index).await;
             }
             "vol_abs" | "volume" => {
                 let url = rc_url
@@ -867,6 +1138,639 @@ impl UPnPAdapter {
 
         Ok(())
     }
+
+    // =========================================================================
+    // Media servers (ContentDirectory) and playback
+    // =========================================================================
+
+    /// Add a renderer by its description URL (e.g. one SSDP can't reach)
+    pub async fn add_renderer(&self, location: &str) -> anyhow::Result<String> {
+        let description = Self::fetch_description(&self.http, location).await?;
+        let uuid = description
+            .uuid
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Device description has no UDN"))?;
+
+        let renderer = UPnPRenderer {
+            uuid: uuid.clone(),
+            name: description
+                .friendly_name
+                .clone()
+                .unwrap_or_else(|| format!("Renderer {}", &uuid[..8.min(uuid.len())])),
+            manufacturer: description.manufacturer.clone(),
+            model: description.model_name.clone(),
+            location: location.to_string(),
+            state: "stopped".to_string(),
+            volume: None,
+            muted: false,
+            last_seen: std::time::Instant::now(),
+            av_transport_url: description.control_url("AVTransport"),
+            rendering_control_url: description.control_url("RenderingControl"),
+        };
+
+        let zone = upnp_renderer_to_zone(&renderer);
+        self.state
+            .write()
+            .await
+            .renderers
+            .insert(uuid.clone(), renderer);
+        self.bus.publish(BusEvent::ZoneDiscovered { zone });
+        Ok(uuid)
+    }
+
+    /// Add a media server by its description URL (e.g. one SSDP can't reach)
+    pub async fn add_media_server(&self, location: &str) -> anyhow::Result<UPnPMediaServer> {
+        Self::register_server(&self.state, &self.http, location).await
+    }
+
+    /// Get all discovered media servers
+    pub async fn get_media_servers(&self) -> Vec<UPnPMediaServer> {
+        let state = self.state.read().await;
+        let mut servers: Vec<_> = state.servers.values().cloned().collect();
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        servers
+    }
+
+    async fn content_directory_url(&self, server_id: &str) -> anyhow::Result<(String, String)> {
+        let state = self.state.read().await;
+        let server = state
+            .servers
+            .get(server_id)
+            .ok_or_else(|| anyhow::anyhow!("Media server not found: {}", server_id))?;
+        Ok((server.name.clone(), server.content_directory_url.clone()))
+    }
+
+    /// Run a ContentDirectory Browse/Search action, returning the DIDL-Lite
+    /// objects and the total match count
+    async fn content_directory_call(
+        &self,
+        url: &str,
+        action: &str,
+        args: &str,
+    ) -> anyhow::Result<(Vec<DidlObject>, usize)> {
+        let response =
+            Self::soap_call(&self.http, url, CONTENT_DIRECTORY_URN, action, args).await?;
+        let result = Self::extract_xml_value(&response, "Result").ok_or_else(|| {
+            let fault = Self::extract_xml_value(&response, "errorDescription")
+                .unwrap_or_else(|| "no Result in response".to_string());
+            anyhow::anyhow!("{} failed: {}", action, fault)
+        })?;
+        let didl =
+            unescape(&result).map_err(|e| anyhow::anyhow!("Invalid {} result: {}", action, e))?;
+        let objects = parse_didl(&didl)?;
+        let total = Self::extract_xml_value(&response, "TotalMatches")
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(objects.len());
+        Ok((objects, total))
+    }
+
+    /// Fetch a single object's metadata
+    async fn browse_metadata(&self, url: &str, object_id: &str) -> anyhow::Result<DidlObject> {
+        let args = format!(
+            "<ObjectID>{}</ObjectID><BrowseFlag>BrowseMetadata</BrowseFlag><Filter>*</Filter>\
+             <StartingIndex>0</StartingIndex><RequestedCount>1</RequestedCount><SortCriteria></SortCriteria>",
+            escape(object_id)
+        );
+        let (objects, _) = self.content_directory_call(url, "Browse", &args).await?;
+        objects
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Object not found: {}", object_id))
+    }
+
+    /// Browse a media server container (`item_key` is a ContentDirectory
+    /// object ID; omit for the root)
+    ///
+    /// ContentDirectory doesn't report depth, so lists below the root
+    /// report level 1.
+    pub async fn browse(
+        &self,
+        server_id: &str,
+        item_key: Option<&str>,
+        offset: usize,
+        count: usize,
+    ) -> anyhow::Result<BrowseResult> {
+        let (server_name, url) = self.content_directory_url(server_id).await?;
+        let object_id = item_key.unwrap_or(ROOT_OBJECT_ID);
+
+        let (title, image_key, level) = if object_id == ROOT_OBJECT_ID {
+            (server_name, None, 0)
+        } else {
+            let container = self.browse_metadata(&url, object_id).await?;
+            (container.title, container.album_art_uri, 1)
+        };
+
+        let args = format!(
+            "<ObjectID>{}</ObjectID><BrowseFlag>BrowseDirectChildren</BrowseFlag><Filter>*</Filter>\
+             <StartingIndex>{}</StartingIndex><RequestedCount>{}</RequestedCount><SortCriteria></SortCriteria>",
+            escape(object_id),
+            offset,
+            count
+        );
+        let (objects, total) = self.content_directory_call(&url, "Browse", &args).await?;
+
+        Ok(BrowseResult {
+            list: BrowseList {
+                title,
+                subtitle: None,
+                image_key,
+                count: total,
+                level,
+            },
+            items: objects.iter().filter_map(didl_browse_item).collect(),
+            offset,
+        })
+    }
+
+    /// Search a media server for audio tracks by title, artist or album
+    pub async fn search(
+        &self,
+        server_id: &str,
+        query: &str,
+        container_id: Option<&str>,
+        offset: usize,
+        count: usize,
+    ) -> anyhow::Result<BrowseResult> {
+        let (_, url) = self.content_directory_url(server_id).await?;
+        let term = query.replace('\\', "\\\\").replace('"', "\\\"");
+        let criteria = format!(
+            "upnp:class derivedfrom \"object.item.audioItem\" and \
+             (dc:title contains \"{term}\" or upnp:artist contains \"{term}\" or upnp:album contains \"{term}\")"
+        );
+        let args = format!(
+            "<ContainerID>{}</ContainerID><SearchCriteria>{}</SearchCriteria><Filter>*</Filter>\
+             <StartingIndex>{}</StartingIndex><RequestedCount>{}</RequestedCount><SortCriteria></SortCriteria>",
+            escape(container_id.unwrap_or(ROOT_OBJECT_ID)),
+            escape(&criteria),
+            offset,
+            count
+        );
+        let (objects, total) = self.content_directory_call(&url, "Search", &args).await?;
+
+        Ok(BrowseResult {
+            list: BrowseList {
+                title: format!("Search: {}", query),
+                subtitle: None,
+                image_key: None,
+                count: total,
+                level: 1,
+            },
+            items: objects.iter().filter_map(didl_browse_item).collect(),
+            offset,
+        })
+    }
+
+    /// Play or queue a media server item on a renderer
+    ///
+    /// Containers (albums, playlists) queue their tracks. `PlayMode::Play`
+    /// replaces the queue and starts playing; `PlayMode::Add` appends.
+    pub async fn play_item(
+        &self,
+        uuid: &str,
+        server_id: &str,
+        item_key: &str,
+        mode: PlayMode,
+    ) -> anyhow::Result<()> {
+        if !self.state.read().await.renderers.contains_key(uuid) {
+            anyhow::bail!("Renderer not found: {}", uuid);
+        }
+        let (_, cd_url) = self.content_directory_url(server_id).await?;
+
+        let item = self.browse_metadata(&cd_url, item_key).await?;
+        let tracks = if item.is_container {
+            self.container_tracks(&cd_url, item_key).await?
+        } else if item.uri().is_some() {
+            vec![item]
+        } else {
+            anyhow::bail!("Item has no playable resource: {}", item_key);
+        };
+        if tracks.is_empty() {
+            anyhow::bail!("Nothing playable in {}", item_key);
+        }
+
+        match mode {
+            PlayMode::Play => {
+                {
+                    let mut state = self.state.write().await;
+                    state.queues.insert(
+                        uuid.to_string(),
+                        UPnPQueue {
+                            items: tracks,
+                            ..Default::default()
+                        },
+                    );
+                }
+                self.play_queue_index(uuid, 0).await
+            }
+            PlayMode::Add => {
+                let mut state = self.state.write().await;
+                state
+                    .queues
+                    .entry(uuid.to_string())
+                    .or_default()
+                    .items
+                    .extend(tracks);
+                save_queues(&state.queues);
+                drop(state);
+                // The appended track may now be the one to preload
+                if let Some(av_url) = self.av_transport_url(uuid).await {
+                    Self::prepare_next(&self.state, &self.http, uuid, &av_url).await;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Play a single stream (e.g. internet radio) on a renderer
+    ///
+    /// The stream replaces the queue, so queue navigation and now playing
+    /// behave as for any other track.
+    pub async fn play_stream(&self, uuid: &str, item: DidlObject) -> anyhow::Result<()> {
+        if item.uri().is_none() {
+            anyhow::bail!("Stream has no URI");
+        }
+        {
+            let mut state = self.state.write().await;
+            if !state.renderers.contains_key(uuid) {
+                anyhow::bail!("Renderer not found: {}", uuid);
+            }
+            state.queues.insert(
+                uuid.to_string(),
+                UPnPQueue {
+                    items: vec![item],
+                    ..Default::default()
+                },
+            );
+        }
+        self.play_queue_index(uuid, 0).await
+    }
+
+    /// Playable tracks directly inside a container, in server order
+    async fn container_tracks(
+        &self,
+        url: &str,
+        container_id: &str,
+    ) -> anyhow::Result<Vec<DidlObject>> {
+        let mut tracks = Vec::new();
+        let mut offset = 0;
+        loop {
+            let args = format!(
+                "<ObjectID>{}</ObjectID><BrowseFlag>BrowseDirectChildren</BrowseFlag><Filter>*</Filter>\
+                 <StartingIndex>{}</StartingIndex><RequestedCount>{}</RequestedCount><SortCriteria></SortCriteria>",
+                escape(container_id),
+                offset,
+                BROWSE_PAGE_SIZE
+            );
+            let (objects, total) = self.content_directory_call(url, "Browse", &args).await?;
+            offset += objects.len();
+            let page_empty = objects.is_empty();
+            tracks.extend(
+                objects
+                    .into_iter()
+                    .filter(|o| o.is_audio_item() && o.uri().is_some()),
+            );
+            if page_empty || offset >= total {
+                break;
+            }
+        }
+        Ok(tracks)
+    }
+
+    /// Get a renderer's queue
+    pub async fn get_queue(&self, uuid: &str) -> UPnPQueue {
+        let state = self.state.read().await;
+        state.queues.get(uuid).cloned().unwrap_or_default()
+    }
+
+    /// Remove a track from a renderer's queue (not the one playing)
+    pub async fn remove_queue_item(&self, uuid: &str, index: usize) -> anyhow::Result<()> {
+        {
+            let mut state = self.state.write().await;
+            let queue = state
+                .queues
+                .get_mut(uuid)
+                .ok_or_else(|| anyhow::anyhow!("No queue for renderer: {}", uuid))?;
+            if index >= queue.items.len() {
+                anyhow::bail!("Queue index out of range: {}", index);
+            }
+            if queue.current == Some(index) {
+                anyhow::bail!("Can't remove the track that is playing");
+            }
+            queue.items.remove(index);
+            if let Some(current) = queue.current.filter(|&c| c > index) {
+                queue.current = Some(current - 1);
+            }
+            // The preloaded next track may have changed
+            queue.next_set = None;
+            save_queues(&state.queues);
+        }
+        if let Some(av_url) = self.av_transport_url(uuid).await {
+            Self::prepare_next(&self.state, &self.http, uuid, &av_url).await;
+        }
+        Ok(())
+    }
+
+    /// Clear a renderer's queue (playback of the current track continues)
+    pub async fn clear_queue(&self, uuid: &str) {
+        let mut state = self.state.write().await;
+        if state.queues.remove(uuid).is_some() {
+            save_queues(&state.queues);
+        }
+    }
+
+    /// Load and play a queued track, preloading the one after it
+    pub async fn play_queue_index(&self, uuid: &str, index: usize) -> anyhow::Result<()> {
+        let item = {
+            let state = self.state.read().await;
+            let queue = state
+                .queues
+                .get(uuid)
+                .ok_or_else(|| anyhow::anyhow!("No queue for renderer: {}", uuid))?;
+            queue
+                .items
+                .get(index)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Queue index out of range: {}", index))?
+        };
+        let av_url = self
+            .av_transport_url(uuid)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("No AVTransport URL"))?;
+
+        Self::load_track(&self.http, &av_url, &item).await?;
+        Self::soap_call(
+            &self.http,
+            &av_url,
+            AV_TRANSPORT_URN,
+            "Play",
+            "<InstanceID>0</InstanceID><Speed>1</Speed>",
+        )
+        .await?;
+        Self::set_current(&self.state, &self.bus, uuid, index).await;
+        Self::prepare_next(&self.state, &self.http, uuid, &av_url).await;
+        Ok(())
+    }
+
+    async fn av_transport_url(&self, uuid: &str) -> Option<String> {
+        let state = self.state.read().await;
+        state
+            .renderers
+            .get(uuid)
+            .and_then(|r| r.av_transport_url.clone())
+    }
+
+    async fn set_held(&self, uuid: &str, action: &str) {
+        let mut state = self.state.write().await;
+        let is_playing = state
+            .renderers
+            .get(uuid)
+            .is_some_and(|r| r.state == "playing");
+        if let Some(queue) = state.queues.get_mut(uuid) {
+            queue.held = match action {
+                "play" => false,
+                "play_pause" => is_playing,
+                _ => true,
+            };
+        }
+    }
+
+    /// Load a track on the renderer (SetAVTransportURI)
+    async fn load_track(http: &Client, av_url: &str, item: &DidlObject) -> anyhow::Result<()> {
+        let uri = item
+            .uri()
+            .ok_or_else(|| anyhow::anyhow!("Item has no playable resource: {}", item.id))?;
+        let response = Self::soap_call(
+            http,
+            av_url,
+            AV_TRANSPORT_URN,
+            "SetAVTransportURI",
+            &format!(
+                "<InstanceID>0</InstanceID><CurrentURI>{}</CurrentURI><CurrentURIMetaData>{}</CurrentURIMetaData>",
+                escape(uri),
+                escape(item.to_didl_lite())
+            ),
+        )
+        .await?;
+        if let Some(fault) = soap_fault(&response) {
+            anyhow::bail!("SetAVTransportURI failed: {}", fault);
+        }
+        Ok(())
+    }
+
+    /// Mark a queued track as the one playing
+    async fn set_current(
+        state: &Arc<RwLock<UPnPState>>,
+        bus: &SharedBus,
+        uuid: &str,
+        index: usize,
+    ) {
+        let mut s = state.write().await;
+        let Some(queue) = s.queues.get_mut(uuid) else {
+            return;
+        };
+        queue.current = Some(index);
+        queue.next_set = None;
+        queue.held = false;
+        if let Some(item) = queue.items.get(index) {
+            bus.publish(BusEvent::NowPlayingChanged {
+                zone_id: format!("upnp:{}", uuid),
+                title: Some(item.title.clone()),
+                artist: item.artist.clone(),
+                album: item.album.clone(),
+                image_key: item.album_art_uri.clone(),
+            });
+        }
+        save_queues(&s.queues);
+    }
+
+    /// Hand the track after the current one to the renderer for gapless
+    /// playback (SetNextAVTransportURI), if not done already
+    async fn prepare_next(state: &Arc<RwLock<UPnPState>>, http: &Client, uuid: &str, av_url: &str) {
+        let (index, item) = {
+            let s = state.read().await;
+            let Some(queue) = s.queues.get(uuid) else {
+                return;
+            };
+            let Some(index) = queue.current.map(|c| c + 1) else {
+                return;
+            };
+            if queue.no_gapless || queue.next_set == Some(index) {
+                return;
+            }
+            match queue.items.get(index) {
+                Some(item) => (index, item.clone()),
+                None => return,
+            }
+        };
+        let Some(uri) = item.uri() else {
+            return;
+        };
+
+        let result = Self::soap_call(
+            http,
+            av_url,
+            AV_TRANSPORT_URN,
+            "SetNextAVTransportURI",
+            &format!(
+                "<InstanceID>0</InstanceID><NextURI>{}</NextURI><NextURIMetaData>{}</NextURIMetaData>",
+                escape(uri),
+                escape(item.to_didl_lite())
+            ),
+        )
+        .await;
+        let accepted = match result {
+            Ok(response) => match soap_fault(&response) {
+                Some(fault) => {
+                    tracing::info!(
+                        "UPnP renderer {} has no gapless support ({}); loading tracks on stop",
+                        uuid,
+                        fault
+                    );
+                    false
+                }
+                None => true,
+            },
+            Err(e) => {
+                tracing::debug!("SetNextAVTransportURI failed for {}: {}", uuid, e);
+                return;
+            }
+        };
+
+        let mut s = state.write().await;
+        if let Some(queue) = s.queues.get_mut(uuid) {
+            if accepted {
+                queue.next_set = Some(index);
+            } else {
+                queue.no_gapless = true;
+            }
+        }
+    }
+
+    /// Follow the renderer through the queue: notice it moving on to the
+    /// preloaded track, or load the next one when it stops at the end of a
+    /// track (renderers without SetNextAVTransportURI)
+    async fn advance_queue(
+        state: &Arc<RwLock<UPnPState>>,
+        bus: &SharedBus,
+        http: &Client,
+        uuid: &str,
+        av_url: &str,
+        transition: Option<(String, String)>,
+    ) -> anyhow::Result<()> {
+        let (current, next, no_gapless, held) = {
+            let s = state.read().await;
+            let Some(queue) = s.queues.get(uuid) else {
+                return Ok(());
+            };
+            let Some(current) = queue.current else {
+                return Ok(());
+            };
+            (
+                current,
+                queue.items.get(current + 1).cloned(),
+                queue.no_gapless,
+                queue.held,
+            )
+        };
+        let Some(next) = next else {
+            return Ok(());
+        };
+
+        let response = Self::soap_call(
+            http,
+            av_url,
+            AV_TRANSPORT_URN,
+            "GetPositionInfo",
+            "<InstanceID>0</InstanceID>",
+        )
+        .await?;
+        let track_uri = Self::extract_xml_value(&response, "TrackURI")
+            .and_then(|uri| unescape(&uri).ok().map(|u| u.into_owned()));
+
+        if track_uri.is_some() && track_uri.as_deref() == next.uri() {
+            tracing::debug!(
+                "UPnP renderer {} moved on to queue index {}",
+                uuid,
+                current + 1
+            );
+            Self::set_current(state, bus, uuid, current + 1).await;
+            Self::prepare_next(state, http, uuid, av_url).await;
+            return Ok(());
+        }
+
+        let finished = matches!(
+            transition,
+            Some((ref from, ref to)) if from == "playing" && to == "stopped"
+        );
+        if no_gapless && finished && !held {
+            Self::load_track(http, av_url, &next).await?;
+            Self::soap_call(
+                http,
+                av_url,
+                AV_TRANSPORT_URN,
+                "Play",
+                "<InstanceID>0</InstanceID><Speed>1</Speed>",
+            )
+            .await?;
+            Self::set_current(state, bus, uuid, current + 1).await;
+        }
+        Ok(())
+    }
+}
+
+/// Error description of a SOAP fault response, if it is one
+fn soap_fault(response: &str) -> Option<String> {
+    if !response.contains("Fault>") {
+        return None;
+    }
+    Some(
+        UPnPAdapter::extract_xml_value(response, "errorDescription")
+            .or_else(|| UPnPAdapter::extract_xml_value(response, "errorCode"))
+            .unwrap_or_else(|| "SOAP fault".to_string()),
+    )
+}
+
+/// Parse a DIDL-Lite duration (H:MM:SS(.F)) into seconds
+fn parse_duration(duration: &str) -> Option<u32> {
+    let mut seconds = 0u32;
+    for part in duration.split('.').next()?.split(':') {
+        seconds = seconds * 60 + part.parse::<u32>().ok()?;
+    }
+    Some(seconds)
+}
+
+/// Join a (possibly relative) control URL onto a device's base URL
+fn join_url(base_url: &str, url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else if url.starts_with('/') {
+        format!("{}{}", base_url, url)
+    } else {
+        format!("{}/{}", base_url, url)
+    }
+}
+
+/// Map a DIDL-Lite object to a browse item (containers browse, tracks play)
+fn didl_browse_item(object: &DidlObject) -> Option<BrowseItem> {
+    let hint = if object.is_container {
+        BrowseItemHint::List
+    } else if object.uri().is_some() {
+        BrowseItemHint::Action
+    } else {
+        return None;
+    };
+    let subtitle = match (&object.artist, &object.album) {
+        (Some(artist), Some(album)) if !object.is_container => {
+            Some(format!("{} - {}", artist, album))
+        }
+        (Some(artist), _) => Some(artist.clone()),
+        (None, album) => album.clone(),
+    };
+    Some(
+        BrowseItem::new(&object.title, &object.id, hint)
+            .with_subtitle(subtitle)
+            .with_image_key(object.album_art_uri.clone()),
+    )
 }
 
 /// Convert a UPnP renderer to a unified Zone representation
@@ -5,16 +5,19 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
-use crate::bus::{BusEvent, NowPlaying, SharedBus, Zone};
+use crate::bus::{BusEvent, NowPlaying, SharedBus, VolumeControl, Zone};
 
 /// ZoneAggregator maintains unified zone state from all adapters.
 /// - Subscribes to bus events
 /// - Maintains HashMap of zones by zone_id
 /// - Flushes zones when adapter stops
+/// - Overrides volume control for zones with a volume delegate
 /// - Provides query interface for API layer
 pub struct ZoneAggregator {
     zones: Arc<RwLock<HashMap<String, Zone>>>,
     now_playing: Arc<RwLock<HashMap<String, NowPlaying>>>,
+    /// Delegated volume control by zone_id (survives zone rediscovery)
+    volume_delegates: Arc<RwLock<HashMap<String, VolumeControl>>>,
     bus: SharedBus,
 }
 
@@ -23,6 +26,7 @@ impl ZoneAggregator {
         Self {
             zones: Arc::new(RwLock::new(HashMap::new())),
             now_playing: Arc::new(RwLock::new(HashMap::new())),
+            volume_delegates: Arc::new(RwLock::new(HashMap::new())),
             bus,
         }
     }
@@ -79,6 +83,36 @@ impl ZoneAggregator {
                     self.now_playing.write().await.insert(zone_id, np);
                 }
 
+                BusEvent::ZoneVolumeDelegated {
+                    zone_id,
+                    volume_control,
+                } => {
+                    debug!("Zone volume delegate changed: {}", zone_id);
+                    let mut delegates = self.volume_delegates.write().await;
+                    match volume_control {
+                        Some(vc) => {
+                            delegates.insert(zone_id, vc);
+                        }
+                        None => {
+                            delegates.remove(&zone_id);
+                        }
+                    }
+                }
+
+                BusEvent::VolumeChanged {
+                    output_id,
+                    value,
+                    is_muted,
+                } => {
+                    // Keep delegated zones in sync with their volume output
+                    for vc in self.volume_delegates.write().await.values_mut() {
+                        if vc.output_id.as_deref() == Some(output_id.as_str()) {
+                            vc.value = value;
+                            vc.is_muted = is_muted;
+                        }
+                    }
+                }
+
                 BusEvent::AdapterStopping { adapter, .. } => {
                     info!("Flushing zones for adapter: {}", adapter);
                     let prefix = format!("{}:", adapter);
@@ -119,26 +153,52 @@ impl ZoneAggregator {
         info!("ZoneAggregator stopped");
     }
 
+    /// Apply a delegated volume control (if any) to a zone snapshot
+    fn with_volume_delegate(zone: &Zone, delegates: &HashMap<String, VolumeControl>) -> Zone {
+        let mut zone = zone.clone();
+        if let Some(vc) = delegates.get(&zone.zone_id) {
+            zone.volume_control = Some(vc.clone());
+        }
+        zone
+    }
+
     /// Get all zones
     pub async fn get_zones(&self) -> Vec<Zone> {
-        self.zones.read().await.values().cloned().collect()
+        let delegates = self.volume_delegates.read().await;
+        self.zones
+            .read()
+            .await
+            .values()
+            .map(|z| Self::with_volume_delegate(z, &delegates))
+            .collect()
     }
 
     /// Get zones for a specific adapter
     pub async fn get_zones_by_adapter(&self, adapter: &str) -> Vec<Zone> {
         let prefix = format!("{}:", adapter);
+        let delegates = self.volume_delegates.read().await;
         self.zones
             .read()
             .await
             .values()
             .filter(|z| z.zone_id.starts_with(&prefix))
-            .cloned()
+            .map(|z| Self::with_volume_delegate(z, &delegates))
             .collect()
     }
 
     /// Get a specific zone
     pub async fn get_zone(&self, zone_id: &str) -> Option<Zone> {
-        self.zones.read().await.get(zone_id).cloned()
+        let delegates = self.volume_delegates.read().await;
+        self.zones
+            .read()
+            .await
+            .get(zone_id)
+            .map(|z| Self::with_volume_delegate(z, &delegates))
+    }
+
+    /// Get the cached delegated volume control for a zone (if delegated)
+    pub async fn get_volume_delegate(&self, zone_id: &str) -> Option<VolumeControl> {
+        self.volume_delegates.read().await.get(zone_id).cloned()
     }
 
     /// Get now playing for a zone
@@ -1,21 +1,33 @@
 //! HTTP API handlers
 
-use crate::adapters::hqplayer::{HqpAdapter, HqpInstanceManager, HqpZoneLinkService};
-use crate::adapters::lms::LmsAdapter;
+use crate::adapters::abx::AbxTester;
+use crate::adapters::airplay::AirPlayAdapter;
+use crate::adapters::browse::PlayMode;
+use crate::adapters::external::{ExternalAdapterConfig, ExternalAdapterManager};
+use crate::adapters::hqplayer::{
+    HqpAdapter, HqpFormatRule, HqpInstanceManager, HqpLibraryDirectory, HqpPlaylistItem, HqpPreset,
+    HqpZoneLinkService,
+};
+use crate::adapters::lms::{LmsAdapter, LmsInstanceManager, DEFAULT_INSTANCE};
 use crate::adapters::openhome::OpenHomeAdapter;
+use crate::adapters::radio::RadioFavorites;
+use crate::adapters::remote::{RemoteAdapter, RemoteBridgeConfig};
 use crate::adapters::roon::RoonAdapter;
+use crate::adapters::spotify::SpotifyAdapter;
 use crate::adapters::upnp::UPnPAdapter;
 use crate::adapters::Startable;
 use crate::aggregator::ZoneAggregator;
 use crate::bus::SharedBus;
 use crate::coordinator::AdapterCoordinator;
 use crate::knobs::KnobStore;
+use crate::volume::delegation::delegated_action;
+use crate::volume::{VolumeDelegationService, VolumeOutputConfig, VolumeOutputManager};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::{
         sse::{Event, KeepAlive, Sse},
-        IntoResponse,
+        IntoResponse, Response,
     },
     Json,
 };
@@ -37,8 +49,19 @@ pub struct AppState {
     pub hqp_instances: Arc<HqpInstanceManager>,
     pub hqp_zone_links: Arc<HqpZoneLinkService>,
     pub lms: Arc<LmsAdapter>,
+    pub lms_instances: Arc<LmsInstanceManager>,
     pub openhome: Arc<OpenHomeAdapter>,
     pub upnp: Arc<UPnPAdapter>,
+    pub airplay: Arc<AirPlayAdapter>,
+    pub spotify: Arc<SpotifyAdapter>,
+    pub remote: Arc<RemoteAdapter>,
+    pub external_adapters: Arc<ExternalAdapterManager>,
+    pub volume_outputs: Arc<VolumeOutputManager>,
+    pub volume_delegates: Arc<VolumeDelegationService>,
+    /// Internet radio favorites, playable on any zone
+    pub radio_favorites: Arc<RadioFavorites>,
+    /// Blind A/B/X comparison of HQPlayer presets
+    pub abx: Arc<AbxTester>,
     pub knobs: KnobStore,
     pub bus: SharedBus,
     pub aggregator: Arc<ZoneAggregator>,
@@ -59,8 +82,15 @@ impl AppState {
         hqp_instances: Arc<HqpInstanceManager>,
         hqp_zone_links: Arc<HqpZoneLinkService>,
         lms: Arc<LmsAdapter>,
+        lms_instances: Arc<LmsInstanceManager>,
         openhome: Arc<OpenHomeAdapter>,
         upnp: Arc<UPnPAdapter>,
+        airplay: Arc<AirPlayAdapter>,
+        spotify: Arc<SpotifyAdapter>,
+        remote: Arc<RemoteAdapter>,
+        external_adapters: Arc<ExternalAdapterManager>,
+        volume_outputs: Arc<VolumeOutputManager>,
+        volume_delegates: Arc<VolumeDelegationService>,
         knobs: KnobStore,
         bus: SharedBus,
         aggregator: Arc<ZoneAggregator>,
@@ -69,14 +99,29 @@ impl AppState {
         start_time: Instant,
         shutdown: CancellationToken,
     ) -> Self {
+        let radio_favorites = Arc::new(RadioFavorites::new(
+            lms_instances.clone(),
+            openhome.clone(),
+            upnp.clone(),
+        ));
+        let abx = Arc::new(AbxTester::new(hqp_instances.clone()));
         Self {
             roon,
             hqplayer,
             hqp_instances,
             hqp_zone_links,
             lms,
+            lms_instances,
             openhome,
             upnp,
+            airplay,
+            spotify,
+            remote,
+            external_adapters,
+            volume_outputs,
+            volume_delegates,
+            radio_favorites,
+            abx,
             knobs,
             bus,
             aggregator,
@@ -106,7 +151,7 @@ pub struct ZonesWrapper<T: Serialize> {
     pub zones: Vec<T>,
 }
 
-/// HQPlayer instances response wrapper - clients expect {instances: [...]}
+/// Instances response wrapper (HQPlayer, LMS) - clients expect {instances: [...]}
 #[derive(Serialize)]
 pub struct InstancesWrapper<T: Serialize> {
     pub instances: Vec<T>,
@@ -118,6 +163,30 @@ pub struct PlayersWrapper<T: Serialize> {
     pub players: Vec<T>,
 }
 
+/// Volume outputs response wrapper - clients expect {outputs: [...]}
+#[derive(Serialize)]
+pub struct OutputsWrapper<T: Serialize> {
+    pub outputs: Vec<T>,
+}
+
+/// LMS sync groups response wrapper - clients expect {groups: [...]}
+#[derive(Serialize)]
+pub struct GroupsWrapper<T: Serialize> {
+    pub groups: Vec<T>,
+}
+
+/// External adapters response wrapper - clients expect {adapters: [...]}
+#[derive(Serialize)]
+pub struct AdaptersWrapper<T: Serialize> {
+    pub adapters: Vec<T>,
+}
+
+/// Media servers response wrapper - clients expect {servers: [...]}
+#[derive(Serialize)]
+pub struct ServersWrapper<T: Serialize> {
+    pub servers: Vec<T>,
+}
+
 /// General status response
 #[derive(Serialize)]
 pub struct StatusResponse {
@@ -198,10 +267,16 @@ pub struct ControlRequest {
 }
 
 /// POST /roon/control - Control playback
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
 pub async fn roon_control_handler(
     State(state): State<AppState>,
     Json(req): Json<ControlRequest>,
 ) -> impl IntoResponse {
+    let zone_key = format!("roon:{}", req.zone_id);
+    if let Some(response) = delegated_response(&state, &zone_key, &req.action, None).await {
+        return response;
+    }
     match state.roon.control(&req.zone_id, &req.action).await {
         Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
         Err(e) => (
@@ -223,11 +298,116 @@ pub struct VolumeRequest {
     pub relative: bool,
 }
 
+/// Send a volume or mute action to the zone's volume delegate, if any
+///
+/// Every volume and mute entry point goes through here first, so a zone
+/// with a delegate (or volume routed to HQPlayer) never moves its source's
+/// own volume. Returns `None` when the zone's volume stays with its source
+/// adapter. `vol_rel` values are device steps on the delegate.
+pub(crate) async fn delegated_control(
+    state: &AppState,
+    zone_key: &str,
+    action: &str,
+    value: Option<f32>,
+) -> Option<anyhow::Result<()>> {
+    let (action, value) = match (action, value) {
+        ("vol_rel", Some(delta)) if delta < 0.0 => ("vol_down", Some(-delta)),
+        ("vol_rel", delta) => ("vol_up", delta),
+        (action, value) => (delegated_action(action)?, value),
+    };
+    let nothing_to_do = action == "vol_up" && value.is_some_and(|v| v == 0.0);
+
+    if state
+        .volume_delegates
+        .get_output_for_zone(zone_key)
+        .await
+        .is_some()
+    {
+        if nothing_to_do {
+            return Some(Ok(()));
+        }
+        return Some(
+            state
+                .volume_delegates
+                .control(zone_key, action, value)
+                .await,
+        );
+    }
+    if state.hqp_zone_links.routes_volume(zone_key).await {
+        if nothing_to_do {
+            return Some(Ok(()));
+        }
+        return Some(
+            state
+                .hqp_zone_links
+                .control_volume(zone_key, action, value)
+                .await,
+        );
+    }
+    None
+}
+
+/// `delegated_control` for the per-source handlers, as their response
+async fn delegated_response(
+    state: &AppState,
+    zone_key: &str,
+    action: &str,
+    value: Option<f32>,
+) -> Option<Response> {
+    let result = delegated_control(state, zone_key, action, value).await?;
+    Some(match result {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    })
+}
+
+/// Zone ID with its source prefix (handlers take the bare device ID too)
+fn prefixed_zone_id(prefix: &str, zone_id: &str) -> String {
+    if zone_id.starts_with(&format!("{}:", prefix)) {
+        zone_id.to_string()
+    } else {
+        format!("{}:{}", prefix, zone_id)
+    }
+}
+
+/// Volume request as a control action (`vol_rel` or `vol_abs`)
+fn volume_action(relative: bool) -> &'static str {
+    if relative {
+        "vol_rel"
+    } else {
+        "vol_abs"
+    }
+}
+
 /// POST /roon/volume - Change volume
+///
+/// Zones with a volume delegate move the delegate instead.
 pub async fn roon_volume_handler(
     State(state): State<AppState>,
     Json(req): Json<VolumeRequest>,
 ) -> impl IntoResponse {
+    let zone = state
+        .roon
+        .get_zones()
+        .await
+        .into_iter()
+        .find(|z| z.outputs.iter().any(|o| o.output_id == req.output_id));
+    if let Some(zone) = zone {
+        let zone_key = format!("roon:{}", zone.zone_id);
+        let action = volume_action(req.relative);
+        if let Some(response) =
+            delegated_response(&state, &zone_key, action, Some(req.value as f32)).await
+        {
+            return response;
+        }
+    }
+
     match state
         .roon
         .change_volume(&req.output_id, req.value, req.relative)
@@ -564,28 +744,67 @@ pub async fn hqp_set_matrix_profile_handler(
 // LMS handlers
 // =============================================================================
 
+/// Query params selecting an LMS instance
+#[derive(Deserialize)]
+pub struct LmsInstanceQuery {
+    /// Instance name (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+/// Look up the LMS instance a request targets (404 if unknown)
+async fn lms_instance(
+    state: &AppState,
+    instance: Option<&str>,
+) -> Result<Arc<LmsAdapter>, Response> {
+    let name = instance.unwrap_or(DEFAULT_INSTANCE);
+    state.lms_instances.get(name).await.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("LMS instance not found: {}", name),
+            }),
+        )
+            .into_response()
+    })
+}
+
 /// GET /lms/status - LMS connection status
 pub async fn lms_status_handler(
     State(state): State<AppState>,
-) -> Json<crate::adapters::lms::LmsStatus> {
-    Json(state.lms.get_status().await)
+    Query(params): Query<LmsInstanceQuery>,
+) -> impl IntoResponse {
+    match lms_instance(&state, params.instance.as_deref()).await {
+        Ok(lms) => Json(lms.get_status().await).into_response(),
+        Err(response) => response,
+    }
 }
 
 /// GET /lms/players - Get all players
 pub async fn lms_players_handler(
     State(state): State<AppState>,
-) -> Json<PlayersWrapper<crate::adapters::lms::LmsPlayer>> {
-    Json(PlayersWrapper {
-        players: state.lms.get_cached_players().await,
-    })
+    Query(params): Query<LmsInstanceQuery>,
+) -> impl IntoResponse {
+    match lms_instance(&state, params.instance.as_deref()).await {
+        Ok(lms) => Json(PlayersWrapper {
+            players: lms.get_cached_players().await,
+        })
+        .into_response(),
+        Err(response) => response,
+    }
 }
 
 /// GET /lms/player/:player_id - Get specific player
 pub async fn lms_player_handler(
     State(state): State<AppState>,
     Path(player_id): Path<String>,
+    Query(params): Query<LmsInstanceQuery>,
 ) -> impl IntoResponse {
-    match state.lms.get_cached_player(&player_id).await {
+    let lms = match lms_instance(&state, params.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    match lms.get_cached_player(&player_id).await {
         Some(player) => (StatusCode::OK, Json(player)).into_response(),
         None => (
             StatusCode::NOT_FOUND,
@@ -600,6 +819,9 @@ pub async fn lms_player_handler(
 /// LMS control request
 #[derive(Deserialize)]
 pub struct LmsControlRequest {
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
     pub player_id: String,
     pub action: String,
     #[serde(default)]
@@ -607,15 +829,23 @@ pub struct LmsControlRequest {
 }
 
 /// POST /lms/control - Control LMS player
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
 pub async fn lms_control_handler(
     State(state): State<AppState>,
     Json(req): Json<LmsControlRequest>,
 ) -> impl IntoResponse {
-    match state
-        .lms
-        .control(&req.player_id, &req.action, req.value)
-        .await
+    let lms = match lms_instance(&state, req.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    let zone_key = lms.zone_id_for_player(&req.player_id).await;
+    if let Some(response) =
+        delegated_response(&state, &zone_key, &req.action, req.value.map(|v| v as f32)).await
     {
+        return response;
+    }
+    match lms.control(&req.player_id, &req.action, req.value).await {
         Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
         Err(e) => (
             StatusCode::BAD_REQUEST,
@@ -630,22 +860,203 @@ pub async fn lms_control_handler(
 /// LMS volume request
 #[derive(Deserialize)]
 pub struct LmsVolumeRequest {
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
     pub player_id: String,
     pub value: i32,
     #[serde(default)]
     pub relative: bool,
+    /// Move the player's whole sync group
+    #[serde(default)]
+    pub group: bool,
 }
 
 /// POST /lms/volume - Change LMS player volume
+///
+/// Zones with a volume delegate move the delegate instead.
 pub async fn lms_volume_handler(
     State(state): State<AppState>,
     Json(req): Json<LmsVolumeRequest>,
 ) -> impl IntoResponse {
-    match state
-        .lms
-        .change_volume(&req.player_id, req.value, req.relative)
+    let lms = match lms_instance(&state, req.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    let zone_key = lms.zone_id_for_player(&req.player_id).await;
+    let action = volume_action(req.relative);
+    if let Some(response) =
+        delegated_response(&state, &zone_key, action, Some(req.value as f32)).await
+    {
+        return response;
+    }
+    let result = if req.group {
+        lms.change_zone_volume(&req.player_id, req.value, req.relative)
+            .await
+    } else {
+        lms.change_volume(&req.player_id, req.value, req.relative)
+            .await
+    };
+    match result {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Query params for LMS browse
+#[derive(Deserialize)]
+pub struct LmsBrowseQuery {
+    /// Item to browse into (omit for the root menu)
+    #[serde(default)]
+    pub item_key: Option<String>,
+    /// Search term (for the search item)
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub count: Option<usize>,
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+/// GET /lms/browse - Browse favorites, playlists, library and radio apps
+pub async fn lms_browse_handler(
+    State(state): State<AppState>,
+    Query(params): Query<LmsBrowseQuery>,
+) -> impl IntoResponse {
+    let lms = match lms_instance(&state, params.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    let count = params
+        .count
+        .unwrap_or(crate::adapters::lms::BROWSE_PAGE_SIZE);
+    match lms
+        .browse(
+            params.item_key.as_deref(),
+            params.query.as_deref(),
+            params.offset,
+            count,
+        )
         .await
     {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// LMS play request
+#[derive(Deserialize)]
+pub struct LmsPlayRequest {
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
+    pub player_id: String,
+    pub item_key: String,
+    #[serde(default)]
+    pub mode: PlayMode,
+}
+
+/// POST /lms/play - Play or queue a browse item
+pub async fn lms_play_handler(
+    State(state): State<AppState>,
+    Json(req): Json<LmsPlayRequest>,
+) -> impl IntoResponse {
+    let lms = match lms_instance(&state, req.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    match lms.play_item(&req.player_id, &req.item_key, req.mode).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /lms/groups - Get sync groups
+pub async fn lms_groups_handler(
+    State(state): State<AppState>,
+    Query(params): Query<LmsInstanceQuery>,
+) -> impl IntoResponse {
+    match lms_instance(&state, params.instance.as_deref()).await {
+        Ok(lms) => Json(GroupsWrapper {
+            groups: lms.get_sync_groups().await,
+        })
+        .into_response(),
+        Err(response) => response,
+    }
+}
+
+/// LMS sync request
+#[derive(Deserialize)]
+pub struct LmsSyncRequest {
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
+    pub player_id: String,
+    /// Player whose group to join
+    pub target_id: String,
+}
+
+/// POST /lms/sync - Sync a player to another player
+pub async fn lms_sync_handler(
+    State(state): State<AppState>,
+    Json(req): Json<LmsSyncRequest>,
+) -> impl IntoResponse {
+    let lms = match lms_instance(&state, req.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    match lms.sync(&req.player_id, &req.target_id).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// LMS unsync request
+#[derive(Deserialize)]
+pub struct LmsUnsyncRequest {
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
+    pub player_id: String,
+}
+
+/// POST /lms/unsync - Remove a player from its sync group
+pub async fn lms_unsync_handler(
+    State(state): State<AppState>,
+    Json(req): Json<LmsUnsyncRequest>,
+) -> impl IntoResponse {
+    let lms = match lms_instance(&state, req.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    match lms.unsync(&req.player_id).await {
         Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
         Err(e) => (
             StatusCode::BAD_REQUEST,
@@ -657,6 +1068,102 @@ pub async fn lms_volume_handler(
     }
 }
 
+// =============================================================================
+// LMS multi-instance handlers
+// =============================================================================
+
+/// GET /lms/instances - List all LMS instances
+pub async fn lms_instances_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let instances = state.lms_instances.list_instances().await;
+    Json(InstancesWrapper { instances })
+}
+
+/// LMS add instance request
+#[derive(Deserialize)]
+pub struct LmsAddInstanceRequest {
+    pub name: String,
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// POST /lms/instances - Add or update an LMS instance
+pub async fn lms_add_instance_handler(
+    State(state): State<AppState>,
+    Json(req): Json<LmsAddInstanceRequest>,
+) -> impl IntoResponse {
+    if req.host.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Host is required".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    match state
+        .lms_instances
+        .add_instance(
+            &req.name,
+            req.host.clone(),
+            req.port,
+            req.username,
+            req.password,
+        )
+        .await
+    {
+        Ok(adapter) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "ok": true,
+                "name": req.name,
+                "host": req.host,
+                "port": req.port.unwrap_or(9000),
+                "connected": adapter.get_status().await.connected
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// DELETE /lms/instances/:name - Remove an LMS instance
+pub async fn lms_remove_instance_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    match state.lms_instances.remove_instance(&name).await {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "removed": name})),
+        )
+            .into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Instance not found: {}", name),
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
 // =============================================================================
 // SSE Events
 // =============================================================================
@@ -764,10 +1271,18 @@ pub struct OpenHomeControlRequest {
 }
 
 /// POST /openhome/control - Control OpenHome device
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
 pub async fn openhome_control_handler(
     State(state): State<AppState>,
     Json(req): Json<OpenHomeControlRequest>,
 ) -> impl IntoResponse {
+    let zone_key = prefixed_zone_id("openhome", &req.zone_id);
+    if let Some(response) =
+        delegated_response(&state, &zone_key, &req.action, req.value.map(|v| v as f32)).await
+    {
+        return response;
+    }
     match state
         .openhome
         .control(&req.zone_id, &req.action, req.value)
@@ -784,62 +1299,49 @@ pub async fn openhome_control_handler(
     }
 }
 
-// =============================================================================
-// UPnP handlers
-// =============================================================================
+/// Sources response wrapper - clients expect {sources: [...]}
+#[derive(Serialize)]
+pub struct SourcesWrapper<T: Serialize> {
+    pub sources: Vec<T>,
+}
 
-/// GET /upnp/status - UPnP discovery status
-pub async fn upnp_status_handler(
-    State(state): State<AppState>,
-) -> Json<crate::adapters::upnp::UPnPStatus> {
-    Json(state.upnp.get_status().await)
-}
-
-/// GET /upnp/zones - List all discovered UPnP renderers
-pub async fn upnp_zones_handler(
-    State(state): State<AppState>,
-) -> Json<ZonesWrapper<crate::adapters::upnp::UPnPZone>> {
-    Json(ZonesWrapper {
-        zones: state.upnp.get_zones().await,
-    })
+/// OpenHome zone query parameters
+#[derive(Deserialize)]
+pub struct OpenHomeZoneQuery {
+    pub zone_id: String,
 }
 
-/// GET /upnp/zone/:zone_id/now_playing - Get now playing for renderer
-pub async fn upnp_now_playing_handler(
+/// GET /openhome/sources - List a device's sources
+pub async fn openhome_sources_handler(
     State(state): State<AppState>,
-    Path(zone_id): Path<String>,
+    Query(params): Query<OpenHomeZoneQuery>,
 ) -> impl IntoResponse {
-    match state.upnp.get_now_playing(&zone_id).await {
-        Some(np) => (StatusCode::OK, Json(np)).into_response(),
-        None => (
-            StatusCode::NOT_FOUND,
+    match state.openhome.get_sources(&params.zone_id).await {
+        Ok(sources) => (StatusCode::OK, Json(SourcesWrapper { sources })).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
-                error: format!("Renderer not found: {}", zone_id),
+                error: e.to_string(),
             }),
         )
             .into_response(),
     }
 }
 
-/// UPnP control request
+/// OpenHome source selection request
 #[derive(Deserialize)]
-pub struct UPnPControlRequest {
+pub struct OpenHomeSourceRequest {
     pub zone_id: String,
-    pub action: String,
-    #[serde(default)]
-    pub value: Option<i32>,
+    /// Source name, type or index
+    pub source: String,
 }
 
-/// POST /upnp/control - Control UPnP renderer
-pub async fn upnp_control_handler(
+/// POST /openhome/source - Switch a device's active source
+pub async fn openhome_source_handler(
     State(state): State<AppState>,
-    Json(req): Json<UPnPControlRequest>,
+    Json(req): Json<OpenHomeSourceRequest>,
 ) -> impl IntoResponse {
-    match state
-        .upnp
-        .control(&req.zone_id, &req.action, req.value)
-        .await
-    {
+    match state.openhome.set_source(&req.zone_id, &req.source).await {
         Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
         Err(e) => (
             StatusCode::BAD_REQUEST,
@@ -851,45 +1353,73 @@ pub async fn upnp_control_handler(
     }
 }
 
-// =============================================================================
-// Configuration handlers
-// =============================================================================
-
-/// LMS configuration request
+/// OpenHome standby request
 #[derive(Deserialize)]
-pub struct LmsConfigRequest {
-    pub host: String,
+pub struct OpenHomeStandbyRequest {
+    pub zone_id: String,
+    /// Omit to toggle
     #[serde(default)]
-    pub port: Option<u16>,
-    pub username: Option<String>,
-    pub password: Option<String>,
+    pub standby: Option<bool>,
 }
 
-/// POST /lms/configure - Configure LMS connection
-pub async fn lms_configure_handler(
+/// POST /openhome/standby - Put a device in or out of standby
+pub async fn openhome_standby_handler(
     State(state): State<AppState>,
-    Json(req): Json<LmsConfigRequest>,
+    Json(req): Json<OpenHomeStandbyRequest>,
 ) -> impl IntoResponse {
-    // Stop existing connection if any
-    state.lms.stop().await;
+    match state.openhome.set_standby(&req.zone_id, req.standby).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
 
-    // Configure new connection
-    state
-        .lms
-        .configure(req.host.clone(), req.port, req.username, req.password)
-        .await;
+/// Generic channels response wrapper - {channels: [...]}
+#[derive(Serialize)]
+pub struct ChannelsWrapper<T: Serialize> {
+    pub channels: Vec<T>,
+}
 
-    // Start the adapter
-    match state.lms.start().await {
-        Ok(()) => (
-            StatusCode::OK,
-            Json(serde_json::json!({
-                "ok": true,
-                "host": req.host,
-                "port": req.port.unwrap_or(9000)
-            })),
+/// GET /openhome/radio - List a device's radio presets
+pub async fn openhome_radio_handler(
+    State(state): State<AppState>,
+    Query(params): Query<OpenHomeZoneQuery>,
+) -> impl IntoResponse {
+    match state.openhome.get_radio_channels(&params.zone_id).await {
+        Ok(channels) => (StatusCode::OK, Json(ChannelsWrapper { channels })).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
         )
             .into_response(),
+    }
+}
+
+/// OpenHome radio preset request
+#[derive(Deserialize)]
+pub struct OpenHomeChannelRequest {
+    pub zone_id: String,
+    pub channel_id: u32,
+}
+
+/// POST /openhome/radio - Tune a radio preset and play it
+pub async fn openhome_channel_handler(
+    State(state): State<AppState>,
+    Json(req): Json<OpenHomeChannelRequest>,
+) -> impl IntoResponse {
+    match state
+        .openhome
+        .set_radio_channel(&req.zone_id, req.channel_id)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
         Err(e) => (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -900,268 +1430,2227 @@ pub async fn lms_configure_handler(
     }
 }
 
-/// HQPlayer configuration request
+/// Radio favorites response wrapper - {favorites: [...]}
+#[derive(Serialize)]
+pub struct FavoritesWrapper<T: Serialize> {
+    pub favorites: Vec<T>,
+}
+
+/// GET /radio/favorites - List internet radio favorites
+pub async fn radio_favorites_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let favorites = state.radio_favorites.list().await;
+    Json(FavoritesWrapper { favorites })
+}
+
+/// Radio favorite creation request
 #[derive(Deserialize)]
-pub struct HqpConfigRequest {
-    pub host: String,
-    #[serde(default)]
-    pub port: Option<u16>,
+pub struct RadioFavoriteRequest {
+    pub name: String,
+    pub url: String,
     #[serde(default)]
-    pub web_port: Option<u16>,
-    pub username: Option<String>,
-    pub password: Option<String>,
+    pub image: Option<String>,
 }
 
-/// POST /hqplayer/configure - Configure HQPlayer connection
-pub async fn hqp_configure_handler(
+/// POST /radio/favorites - Add an internet radio favorite
+pub async fn radio_add_favorite_handler(
     State(state): State<AppState>,
-    Json(req): Json<HqpConfigRequest>,
+    Json(req): Json<RadioFavoriteRequest>,
 ) -> impl IntoResponse {
-    // Configure the adapter
-    state
-        .hqplayer
-        .configure(
-            req.host.clone(),
-            req.port,
-            req.web_port,
-            req.username,
-            req.password,
+    match state
+        .radio_favorites
+        .add(&req.name, &req.url, req.image)
+        .await
+    {
+        Ok(favorite) => (StatusCode::OK, Json(favorite)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
         )
-        .await;
-
-    // Save to instance manager for persistence
-    state.hqp_instances.save_to_config().await;
+            .into_response(),
+    }
+}
 
-    // Test connection by attempting to get pipeline status (this establishes connection)
-    let connected = match state.hqplayer.get_pipeline_status().await {
-        Ok(_) => true,
-        Err(e) => {
-            tracing::warn!("HQPlayer connection test failed: {}", e);
-            false
-        }
-    };
+/// DELETE /radio/favorites/:id - Remove an internet radio favorite
+pub async fn radio_remove_favorite_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if state.radio_favorites.remove(&id).await {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "removed": id})),
+        )
+            .into_response()
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Favorite not found: {}", id),
+            }),
+        )
+            .into_response()
+    }
+}
 
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({
-            "ok": true,
-            "host": req.host,
-            "port": req.port.unwrap_or(4321),
-            "web_port": req.web_port.unwrap_or(8088),
-            "connected": connected
-        })),
-    )
-        .into_response()
+/// Radio favorite playback request
+#[derive(Deserialize)]
+pub struct RadioPlayRequest {
+    pub zone_id: String,
+    /// Favorite ID or name
+    pub favorite: String,
 }
 
-/// GET /lms/config - Get current LMS configuration
-pub async fn lms_config_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let status = state.lms.get_status().await;
-    Json(serde_json::json!({
-        "configured": status.host.is_some(),
-        "connected": status.connected,
-        "host": status.host,
-        "port": status.port
-    }))
+/// POST /radio/play - Play a favorite on any zone
+pub async fn radio_play_handler(
+    State(state): State<AppState>,
+    Json(req): Json<RadioPlayRequest>,
+) -> impl IntoResponse {
+    match state
+        .radio_favorites
+        .play(&req.zone_id, &req.favorite)
+        .await
+    {
+        Ok(favorite) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "playing": favorite})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
 }
 
-/// GET /hqplayer/config - Get current HQPlayer configuration
-pub async fn hqp_config_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let status = state.hqplayer.get_status().await;
-    let has_web_creds = state.hqplayer.has_web_credentials().await;
-    Json(serde_json::json!({
-        "configured": status.host.is_some(),
-        "connected": status.connected,
-        "host": status.host,
-        "port": status.port,
-        "web_port": status.web_port,
-        "has_web_credentials": has_web_creds
-    }))
+/// Queue query parameters (shared by sources with queue endpoints)
+#[derive(Deserialize)]
+pub struct QueueQuery {
+    pub zone_id: String,
 }
 
-/// HQPlayer detect request body
+/// Queue entry request (shared by sources with queue endpoints)
 #[derive(Deserialize)]
-pub struct HqpDetectRequest {
-    pub host: String,
-    #[serde(default = "default_hqp_port")]
-    pub port: u16,
+pub struct QueueIndexRequest {
+    pub zone_id: String,
+    pub index: usize,
 }
 
-fn default_hqp_port() -> u16 {
-    4321
+/// GET /openhome/queue - Read a device's Playlist
+pub async fn openhome_queue_handler(
+    State(state): State<AppState>,
+    Query(params): Query<QueueQuery>,
+) -> impl IntoResponse {
+    match state.openhome.get_queue(&params.zone_id).await {
+        Ok(queue) => (StatusCode::OK, Json(queue)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
 }
 
-/// POST /hqp/detect - Detect HQPlayer at a given host
-pub async fn hqp_detect_handler(Json(req): Json<HqpDetectRequest>) -> impl IntoResponse {
-    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-    use tokio::time::{timeout, Duration};
+/// DELETE /openhome/queue - Clear a device's Playlist
+pub async fn openhome_clear_queue_handler(
+    State(state): State<AppState>,
+    Query(params): Query<QueueQuery>,
+) -> impl IntoResponse {
+    match state.openhome.clear_queue(&params.zone_id).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
 
-    // Try to connect to HQPlayer's native protocol port
-    let addr = format!("{}:{}", req.host, req.port);
+/// OpenHome Playlist insert request
+#[derive(Deserialize)]
+pub struct OpenHomeInsertRequest {
+    pub zone_id: String,
+    pub uri: String,
+    /// DIDL-Lite metadata
+    #[serde(default)]
+    pub metadata: Option<String>,
+    /// Insert after this track ID (0 = at the start; omit to append)
+    #[serde(default)]
+    pub after_id: Option<u32>,
+}
 
-    let stream = match timeout(
-        Duration::from_secs(5),
-        tokio::net::TcpStream::connect(&addr),
-    )
-    .await
+/// POST /openhome/queue - Insert a track into a device's Playlist
+pub async fn openhome_queue_insert_handler(
+    State(state): State<AppState>,
+    Json(req): Json<OpenHomeInsertRequest>,
+) -> impl IntoResponse {
+    match state
+        .openhome
+        .insert_track(
+            &req.zone_id,
+            req.after_id,
+            &req.uri,
+            req.metadata.as_deref(),
+        )
+        .await
     {
-        Ok(Ok(stream)) => stream,
-        Ok(Err(_)) | Err(_) => {
-            return Json(serde_json::json!({
-                "reachable": false,
-                "error": "Cannot connect to HQPlayer at this address"
-            }));
+        Ok(id) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "id": id})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /openhome/queue/play - Jump to a Playlist track
+pub async fn openhome_seek_handler(
+    State(state): State<AppState>,
+    Json(req): Json<QueueIndexRequest>,
+) -> impl IntoResponse {
+    let result = match state.openhome.playlist_id_at(&req.zone_id, req.index).await {
+        Ok(id) => state.openhome.seek_id(&req.zone_id, id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /openhome/queue/remove - Remove a Playlist track
+pub async fn openhome_remove_handler(
+    State(state): State<AppState>,
+    Json(req): Json<QueueIndexRequest>,
+) -> impl IntoResponse {
+    let result = match state.openhome.playlist_id_at(&req.zone_id, req.index).await {
+        Ok(id) => state.openhome.delete_track(&req.zone_id, id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Queue shuffle/repeat request
+#[derive(Deserialize)]
+pub struct QueueModeRequest {
+    pub zone_id: String,
+    #[serde(default)]
+    pub shuffle: Option<bool>,
+    #[serde(default)]
+    pub repeat: Option<bool>,
+}
+
+/// POST /openhome/queue/mode - Set Playlist shuffle/repeat
+pub async fn openhome_mode_handler(
+    State(state): State<AppState>,
+    Json(req): Json<QueueModeRequest>,
+) -> impl IntoResponse {
+    match state
+        .openhome
+        .set_queue_mode(&req.zone_id, req.shuffle, req.repeat)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// UPnP handlers
+// =============================================================================
+
+/// GET /upnp/status - UPnP discovery status
+pub async fn upnp_status_handler(
+    State(state): State<AppState>,
+) -> Json<crate::adapters::upnp::UPnPStatus> {
+    Json(state.upnp.get_status().await)
+}
+
+/// GET /upnp/zones - List all discovered UPnP renderers
+pub async fn upnp_zones_handler(
+    State(state): State<AppState>,
+) -> Json<ZonesWrapper<crate::adapters::upnp::UPnPZone>> {
+    Json(ZonesWrapper {
+        zones: state.upnp.get_zones().await,
+    })
+}
+
+/// GET /upnp/zone/:zone_id/now_playing - Get now playing for renderer
+pub async fn upnp_now_playing_handler(
+    State(state): State<AppState>,
+    Path(zone_id): Path<String>,
+) -> impl IntoResponse {
+    match state.upnp.get_now_playing(&zone_id).await {
+        Some(np) => (StatusCode::OK, Json(np)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Renderer not found: {}", zone_id),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// UPnP control request
+#[derive(Deserialize)]
+pub struct UPnPControlRequest {
+    pub zone_id: String,
+    pub action: String,
+    #[serde(default)]
+    pub value: Option<i32>,
+}
+
+/// POST /upnp/control - Control UPnP renderer
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
+pub async fn upnp_control_handler(
+    State(state): State<AppState>,
+    Json(req): Json<UPnPControlRequest>,
+) -> impl IntoResponse {
+    let zone_key = prefixed_zone_id("upnp", &req.zone_id);
+    if let Some(response) =
+        delegated_response(&state, &zone_key, &req.action, req.value.map(|v| v as f32)).await
+    {
+        return response;
+    }
+    match state
+        .upnp
+        .control(&req.zone_id, &req.action, req.value)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /upnp/servers - List discovered media servers
+pub async fn upnp_servers_handler(
+    State(state): State<AppState>,
+) -> Json<ServersWrapper<crate::adapters::upnp::UPnPMediaServer>> {
+    Json(ServersWrapper {
+        servers: state.upnp.get_media_servers().await,
+    })
+}
+
+/// Add media server request
+#[derive(Deserialize)]
+pub struct UPnPAddServerRequest {
+    /// Device description URL
+    pub location: String,
+}
+
+/// POST /upnp/servers - Add a media server SSDP can't reach
+pub async fn upnp_add_server_handler(
+    State(state): State<AppState>,
+    Json(req): Json<UPnPAddServerRequest>,
+) -> impl IntoResponse {
+    match state.upnp.add_media_server(&req.location).await {
+        Ok(server) => (StatusCode::OK, Json(server)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// UPnP browse query parameters
+#[derive(Deserialize)]
+pub struct UPnPBrowseQuery {
+    pub server_id: String,
+    /// Container to browse into (omit for the root)
+    #[serde(default)]
+    pub item_key: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+/// GET /upnp/browse - Browse a media server's ContentDirectory
+pub async fn upnp_browse_handler(
+    State(state): State<AppState>,
+    Query(params): Query<UPnPBrowseQuery>,
+) -> impl IntoResponse {
+    let count = params
+        .count
+        .unwrap_or(crate::adapters::upnp::BROWSE_PAGE_SIZE);
+    match state
+        .upnp
+        .browse(
+            &params.server_id,
+            params.item_key.as_deref(),
+            params.offset,
+            count,
+        )
+        .await
+    {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// UPnP search query parameters
+#[derive(Deserialize)]
+pub struct UPnPSearchQuery {
+    pub server_id: String,
+    pub query: String,
+    /// Container to search within (omit for the whole server)
+    #[serde(default)]
+    pub container_id: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+/// GET /upnp/search - Search a media server for tracks
+pub async fn upnp_search_handler(
+    State(state): State<AppState>,
+    Query(params): Query<UPnPSearchQuery>,
+) -> impl IntoResponse {
+    let count = params
+        .count
+        .unwrap_or(crate::adapters::upnp::BROWSE_PAGE_SIZE);
+    match state
+        .upnp
+        .search(
+            &params.server_id,
+            &params.query,
+            params.container_id.as_deref(),
+            params.offset,
+            count,
+        )
+        .await
+    {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// UPnP play request
+#[derive(Deserialize)]
+pub struct UPnPPlayRequest {
+    /// Renderer to play on
+    pub zone_id: String,
+    pub server_id: String,
+    /// ContentDirectory object ID of a track or container
+    pub item_key: String,
+    /// Replace the queue and play, or append to it
+    #[serde(default)]
+    pub mode: PlayMode,
+}
+
+/// POST /upnp/play - Play or queue media server items on a renderer
+pub async fn upnp_play_handler(
+    State(state): State<AppState>,
+    Json(req): Json<UPnPPlayRequest>,
+) -> impl IntoResponse {
+    match state
+        .upnp
+        .play_item(&req.zone_id, &req.server_id, &req.item_key, req.mode)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /upnp/queue - Get a renderer's queue
+pub async fn upnp_queue_handler(
+    State(state): State<AppState>,
+    Query(params): Query<QueueQuery>,
+) -> Json<crate::adapters::upnp::UPnPQueue> {
+    Json(state.upnp.get_queue(&params.zone_id).await)
+}
+
+/// DELETE /upnp/queue - Clear a renderer's queue
+pub async fn upnp_clear_queue_handler(
+    State(state): State<AppState>,
+    Query(params): Query<QueueQuery>,
+) -> Json<serde_json::Value> {
+    state.upnp.clear_queue(&params.zone_id).await;
+    Json(serde_json::json!({"ok": true}))
+}
+
+/// POST /upnp/queue/play - Jump to a queued track
+pub async fn upnp_queue_play_handler(
+    State(state): State<AppState>,
+    Json(req): Json<QueueIndexRequest>,
+) -> impl IntoResponse {
+    match state.upnp.play_queue_index(&req.zone_id, req.index).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /upnp/queue/remove - Remove a queued track
+pub async fn upnp_queue_remove_handler(
+    State(state): State<AppState>,
+    Json(req): Json<QueueIndexRequest>,
+) -> impl IntoResponse {
+    match state.upnp.remove_queue_item(&req.zone_id, req.index).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// AirPlay handlers
+// =============================================================================
+
+/// GET /airplay/status - AirPlay adapter status
+pub async fn airplay_status_handler(
+    State(state): State<AppState>,
+) -> Json<crate::adapters::airplay::AirPlayStatus> {
+    Json(state.airplay.get_status().await)
+}
+
+/// GET /airplay/zones - List AirPlay receivers
+pub async fn airplay_zones_handler(
+    State(state): State<AppState>,
+) -> Json<ZonesWrapper<crate::adapters::airplay::AirPlayZone>> {
+    Json(ZonesWrapper {
+        zones: state.airplay.get_zones().await,
+    })
+}
+
+/// GET /airplay/zone/:zone_id/now_playing - Get now playing for receiver
+pub async fn airplay_now_playing_handler(
+    State(state): State<AppState>,
+    Path(zone_id): Path<String>,
+) -> impl IntoResponse {
+    match state.airplay.get_now_playing(&zone_id).await {
+        Some(np) => (StatusCode::OK, Json(np)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Receiver not found: {}", zone_id),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// AirPlay control request
+#[derive(Deserialize)]
+pub struct AirPlayControlRequest {
+    pub zone_id: String,
+    pub action: String,
+}
+
+/// POST /airplay/control - Send a remote command to a receiver
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
+pub async fn airplay_control_handler(
+    State(state): State<AppState>,
+    Json(req): Json<AirPlayControlRequest>,
+) -> impl IntoResponse {
+    let zone_key = prefixed_zone_id("airplay", &req.zone_id);
+    if let Some(response) = delegated_response(&state, &zone_key, &req.action, None).await {
+        return response;
+    }
+    match state.airplay.control(&req.zone_id, &req.action).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// Spotify Connect handlers
+// =============================================================================
+
+/// GET /spotify/status - Spotify adapter status
+pub async fn spotify_status_handler(
+    State(state): State<AppState>,
+) -> Json<crate::adapters::spotify::SpotifyStatus> {
+    Json(state.spotify.get_status().await)
+}
+
+/// GET /spotify/zones - List Spotify Connect receivers
+pub async fn spotify_zones_handler(
+    State(state): State<AppState>,
+) -> Json<ZonesWrapper<crate::adapters::spotify::SpotifyZone>> {
+    Json(ZonesWrapper {
+        zones: state.spotify.get_zones().await,
+    })
+}
+
+/// GET /spotify/zone/:zone_id/now_playing - Get now playing for receiver
+pub async fn spotify_now_playing_handler(
+    State(state): State<AppState>,
+    Path(zone_id): Path<String>,
+) -> impl IntoResponse {
+    match state.spotify.get_now_playing(&zone_id).await {
+        Some(np) => (StatusCode::OK, Json(np)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Receiver not found: {}", zone_id),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Spotify control request
+#[derive(Deserialize)]
+pub struct SpotifyControlRequest {
+    pub zone_id: String,
+    pub action: String,
+    #[serde(default)]
+    pub value: Option<f64>,
+}
+
+/// POST /spotify/control - Control a go-librespot receiver
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
+pub async fn spotify_control_handler(
+    State(state): State<AppState>,
+    Json(req): Json<SpotifyControlRequest>,
+) -> impl IntoResponse {
+    let zone_key = prefixed_zone_id("spotify", &req.zone_id);
+    let value = req.value.map(|v| v as f32);
+    if let Some(response) = delegated_response(&state, &zone_key, &req.action, value).await {
+        return response;
+    }
+    match state
+        .spotify
+        .control(&req.zone_id, &req.action, req.value)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Query params for the librespot event hook
+#[derive(Deserialize)]
+pub struct SpotifyOnEventQuery {
+    pub receiver: String,
+}
+
+/// POST /spotify/onevent?receiver=<name> - librespot `--onevent` hook
+///
+/// Body is the hook's environment as `KEY=VALUE` lines (e.g. `env | curl --data-binary @-`).
+pub async fn spotify_onevent_handler(
+    State(state): State<AppState>,
+    Query(params): Query<SpotifyOnEventQuery>,
+    body: String,
+) -> impl IntoResponse {
+    match state.spotify.handle_onevent(&params.receiver, &body).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// Configuration handlers
+// =============================================================================
+
+/// LMS configuration request
+#[derive(Deserialize)]
+pub struct LmsConfigRequest {
+    /// LMS instance (omit for the default instance)
+    #[serde(default)]
+    pub instance: Option<String>,
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// POST /lms/configure - Configure LMS connection
+pub async fn lms_configure_handler(
+    State(state): State<AppState>,
+    Json(req): Json<LmsConfigRequest>,
+) -> impl IntoResponse {
+    let name = req.instance.as_deref().unwrap_or(DEFAULT_INSTANCE);
+    let lms = match state.lms_instances.get_or_create(name).await {
+        Ok(lms) => lms,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    // Stop existing connection if any
+    lms.stop().await;
+
+    // Configure new connection
+    lms.configure(req.host.clone(), req.port, req.username, req.password)
+        .await;
+
+    // Start the adapter
+    match lms.start().await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "ok": true,
+                "host": req.host,
+                "port": req.port.unwrap_or(9000)
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// AirPlay configuration request
+#[derive(Deserialize)]
+pub struct AirPlayConfigRequest {
+    pub receivers: Vec<crate::adapters::airplay::ShairportReceiverConfig>,
+}
+
+/// POST /airplay/configure - Configure shairport-sync receivers
+pub async fn airplay_configure_handler(
+    State(state): State<AppState>,
+    Json(req): Json<AirPlayConfigRequest>,
+) -> impl IntoResponse {
+    let receiver_count = req.receivers.len();
+    if let Err(e) = state.airplay.configure(req.receivers).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    // Restart so readers pick up the new receivers
+    state.airplay.stop().await;
+    if state.coordinator.is_enabled("airplay").await && state.airplay.can_start().await {
+        if let Err(e) = state.airplay.start().await {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+                .into_response();
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "ok": true,
+            "receivers": receiver_count
+        })),
+    )
+        .into_response()
+}
+
+/// Spotify configuration request
+#[derive(Deserialize)]
+pub struct SpotifyConfigRequest {
+    pub receivers: Vec<crate::adapters::spotify::SpotifyReceiverConfig>,
+}
+
+/// POST /spotify/configure - Configure Spotify Connect receivers
+pub async fn spotify_configure_handler(
+    State(state): State<AppState>,
+    Json(req): Json<SpotifyConfigRequest>,
+) -> impl IntoResponse {
+    let receiver_count = req.receivers.len();
+    if let Err(e) = state.spotify.configure(req.receivers).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    // Restart so the new receivers are picked up
+    state.spotify.stop().await;
+    if state.coordinator.is_enabled("spotify").await && state.spotify.can_start().await {
+        if let Err(e) = state.spotify.start().await {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+                .into_response();
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "ok": true,
+            "receivers": receiver_count
+        })),
+    )
+        .into_response()
+}
+
+// =============================================================================
+// Remote bridge handlers
+// =============================================================================
+
+/// GET /remote/status - Remote bridge connection status
+pub async fn remote_status_handler(
+    State(state): State<AppState>,
+) -> Json<crate::adapters::remote::RemoteStatus> {
+    Json(state.remote.get_status().await)
+}
+
+/// GET /remote/zones - List zones federated from remote bridges
+pub async fn remote_zones_handler(
+    State(state): State<AppState>,
+) -> Json<ZonesWrapper<crate::adapters::remote::RemoteZoneSummary>> {
+    Json(ZonesWrapper {
+        zones: state.remote.get_zones().await,
+    })
+}
+
+/// Remote bridge configuration request
+#[derive(Deserialize)]
+pub struct RemoteConfigRequest {
+    pub bridges: Vec<RemoteBridgeConfig>,
+}
+
+/// POST /remote/configure - Configure remote bridges
+pub async fn remote_configure_handler(
+    State(state): State<AppState>,
+    Json(req): Json<RemoteConfigRequest>,
+) -> impl IntoResponse {
+    let bridge_count = req.bridges.len();
+    if let Err(e) = state.remote.configure(req.bridges).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    // Restart so the new bridges are followed
+    state.remote.stop().await;
+    if state.coordinator.is_enabled("remote").await && state.remote.can_start().await {
+        if let Err(e) = state.remote.start().await {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+                .into_response();
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "ok": true,
+            "bridges": bridge_count
+        })),
+    )
+        .into_response()
+}
+
+// =============================================================================
+// External adapter handlers
+// =============================================================================
+
+/// GET /external/adapters - List external adapters with runtime status
+pub async fn external_adapters_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(AdaptersWrapper {
+        adapters: state.external_adapters.list_adapters().await,
+    })
+}
+
+/// POST /external/adapters - Add or update an external adapter (restarts it)
+pub async fn external_add_adapter_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ExternalAdapterConfig>,
+) -> impl IntoResponse {
+    let name = req.name.clone();
+    if let Err(e) = state.external_adapters.add_adapter(req).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"ok": true, "name": name})),
+    )
+        .into_response()
+}
+
+/// DELETE /external/adapters/:name - Stop and remove an external adapter
+pub async fn external_remove_adapter_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if state.external_adapters.remove_adapter(&name).await {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "removed": name})),
+        )
+            .into_response()
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("External adapter not found: {}", name),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// GET /external/zones - List zones announced by external adapters
+pub async fn external_zones_handler(
+    State(state): State<AppState>,
+) -> Json<ZonesWrapper<crate::bus::Zone>> {
+    Json(ZonesWrapper {
+        zones: state.external_adapters.get_zones().await,
+    })
+}
+
+/// External adapter control request
+#[derive(Deserialize)]
+pub struct ExternalControlRequest {
+    pub zone_id: String,
+    pub command: crate::bus::Command,
+}
+
+/// POST /external/control - Send a command to an external adapter zone
+///
+/// Volume and mute for zones with a volume delegate go to the delegate.
+pub async fn external_control_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ExternalControlRequest>,
+) -> impl IntoResponse {
+    use crate::bus::Command;
+
+    let volume = match &req.command {
+        Command::VolumeAbsolute { value, .. } => Some(("vol_abs", Some(*value))),
+        Command::VolumeRelative { delta, .. } => Some(("vol_rel", Some(*delta))),
+        Command::Mute { muted: true, .. } => Some(("mute", None)),
+        Command::Mute { muted: false, .. } => Some(("unmute", None)),
+        _ => None,
+    };
+    if let Some((action, value)) = volume {
+        if let Some(response) = delegated_response(&state, &req.zone_id, action, value).await {
+            return response;
+        }
+    }
+    match state
+        .external_adapters
+        .send_command(&req.zone_id, req.command)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// HQPlayer configuration request
+#[derive(Deserialize)]
+pub struct HqpConfigRequest {
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub web_port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// POST /hqplayer/configure - Configure HQPlayer connection
+pub async fn hqp_configure_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HqpConfigRequest>,
+) -> impl IntoResponse {
+    // Configure the adapter
+    state
+        .hqplayer
+        .configure(
+            req.host.clone(),
+            req.port,
+            req.web_port,
+            req.username,
+            req.password,
+        )
+        .await;
+
+    // Save to instance manager for persistence
+    state.hqp_instances.save_to_config().await;
+
+    // Test connection by attempting to get pipeline status (this establishes connection)
+    let connected = match state.hqplayer.get_pipeline_status().await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::warn!("HQPlayer connection test failed: {}", e);
+            false
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "ok": true,
+            "host": req.host,
+            "port": req.port.unwrap_or(4321),
+            "web_port": req.web_port.unwrap_or(8088),
+            "connected": connected
+        })),
+    )
+        .into_response()
+}
+
+/// GET /lms/config - Get current LMS configuration
+pub async fn lms_config_handler(
+    State(state): State<AppState>,
+    Query(params): Query<LmsInstanceQuery>,
+) -> impl IntoResponse {
+    let lms = match lms_instance(&state, params.instance.as_deref()).await {
+        Ok(lms) => lms,
+        Err(response) => return response,
+    };
+    let status = lms.get_status().await;
+    Json(serde_json::json!({
+        "instance": status.instance,
+        "configured": status.host.is_some(),
+        "connected": status.connected,
+        "host": status.host,
+        "port": status.port
+    }))
+    .into_response()
+}
+
+/// GET /airplay/config - Get configured shairport-sync receivers
+pub async fn airplay_config_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "receivers": state.airplay.get_config().await
+    }))
+}
+
+/// GET /spotify/config - Get configured Spotify Connect receivers
+pub async fn spotify_config_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "receivers": state.spotify.get_config().await
+    }))
+}
+
+/// GET /remote/config - Get configured remote bridges
+pub async fn remote_config_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "bridges": state.remote.get_config().await
+    }))
+}
+
+/// GET /hqplayer/config - Get current HQPlayer configuration
+pub async fn hqp_config_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let status = state.hqplayer.get_status().await;
+    let has_web_creds = state.hqplayer.has_web_credentials().await;
+    Json(serde_json::json!({
+        "configured": status.host.is_some(),
+        "connected": status.connected,
+        "host": status.host,
+        "port": status.port,
+        "web_port": status.web_port,
+        "has_web_credentials": has_web_creds
+    }))
+}
+
+/// HQPlayer detect request body
+#[derive(Deserialize)]
+pub struct HqpDetectRequest {
+    pub host: String,
+    #[serde(default = "default_hqp_port")]
+    pub port: u16,
+}
+
+fn default_hqp_port() -> u16 {
+    4321
+}
+
+/// POST /hqp/detect - Detect HQPlayer at a given host
+pub async fn hqp_detect_handler(Json(req): Json<HqpDetectRequest>) -> impl IntoResponse {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::time::{timeout, Duration};
+
+    // Try to connect to HQPlayer's native protocol port
+    let addr = format!("{}:{}", req.host, req.port);
+
+    let stream = match timeout(
+        Duration::from_secs(5),
+        tokio::net::TcpStream::connect(&addr),
+    )
+    .await
+    {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(_)) | Err(_) => {
+            return Json(serde_json::json!({
+                "reachable": false,
+                "error": "Cannot connect to HQPlayer at this address"
+            }));
+        }
+    };
+
+    let (read_half, mut write_half) = stream.into_split();
+    let mut reader = BufReader::new(read_half);
+
+    // Read initial greeting
+    let mut greeting = String::new();
+    if timeout(Duration::from_secs(2), reader.read_line(&mut greeting))
+        .await
+        .is_err()
+    {
+        return Json(serde_json::json!({
+            "reachable": false,
+            "error": "No response from HQPlayer"
+        }));
+    }
+
+    // Send INFO command
+    if write_half
+        .write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?><info/>\n")
+        .await
+        .is_err()
+    {
+        return Json(serde_json::json!({
+            "reachable": false,
+            "error": "Failed to send command to HQPlayer"
+        }));
+    }
+
+    // Read INFO response
+    let mut response = String::new();
+    if timeout(Duration::from_secs(2), reader.read_line(&mut response))
+        .await
+        .is_err()
+    {
+        return Json(serde_json::json!({
+            "reachable": false,
+            "error": "No INFO response from HQPlayer"
+        }));
+    }
+
+    // Parse XML response for product/version
+    let product = extract_xml_attr(&response, "product");
+    let version = extract_xml_attr(&response, "version");
+    let is_embedded = product
+        .as_ref()
+        .map(|p| p.to_lowercase().contains("embedded"))
+        .unwrap_or(false);
+
+    Json(serde_json::json!({
+        "reachable": true,
+        "product": product,
+        "version": version,
+        "isEmbedded": is_embedded
+    }))
+}
+
+/// Extract attribute value from XML string
+fn extract_xml_attr(xml: &str, attr: &str) -> Option<String> {
+    let pattern = format!("{}=\"", attr);
+    if let Some(start) = xml.find(&pattern) {
+        let value_start = start + pattern.len();
+        if let Some(end) = xml[value_start..].find('"') {
+            return Some(xml[value_start..value_start + end].to_string());
+        }
+    }
+    None
+}
+
+// =============================================================================
+// HQPlayer multi-instance handlers
+// =============================================================================
+
+/// GET /hqp/instances - List all HQPlayer instances
+pub async fn hqp_instances_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let instances = state.hqp_instances.list_instances().await;
+    Json(InstancesWrapper { instances })
+}
+
+/// HQPlayer add instance request
+#[derive(Deserialize)]
+pub struct HqpAddInstanceRequest {
+    pub name: String,
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub web_port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// POST /hqp/instances - Add or update an HQPlayer instance
+pub async fn hqp_add_instance_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HqpAddInstanceRequest>,
+) -> impl IntoResponse {
+    if req.name.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Instance name is required".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    if req.host.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Host is required".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let _adapter = state
+        .hqp_instances
+        .add_instance(
+            req.name.clone(),
+            req.host.clone(),
+            req.port,
+            req.web_port,
+            req.username,
+            req.password,
+        )
+        .await;
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "ok": true,
+            "name": req.name,
+            "host": req.host,
+            "port": req.port.unwrap_or(4321)
+        })),
+    )
+        .into_response()
+}
+
+/// DELETE /hqp/instances/:name - Remove an HQPlayer instance
+pub async fn hqp_remove_instance_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    // Remove zone links pointing to this instance first
+    let _links_removed = state.hqp_zone_links.remove_links_for_instance(&name).await;
+
+    if state.hqp_instances.remove_instance(&name).await {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "removed": name})),
+        )
+            .into_response()
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Instance not found: {}", name),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// GET /hqp/instances/:name/profiles - Get profiles for a specific HQPlayer instance
+pub async fn hqp_instance_profiles_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    let adapter = match state.hqp_instances.get(&name).await {
+        Some(a) => a,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Instance not found: {}", name),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    match adapter.fetch_profiles().await {
+        Ok(profiles) => (StatusCode::OK, Json(profiles)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /hqp/instances/:name/profile - Load a profile on a specific HQPlayer instance
+pub async fn hqp_instance_load_profile_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<HqpProfileRequest>,
+) -> impl IntoResponse {
+    let adapter = match state.hqp_instances.get(&name).await {
+        Some(a) => a,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Instance not found: {}", name),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    match adapter.load_profile(&req.profile).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "instance": name, "profile": req.profile})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /hqp/instances/:name/matrix/profiles - Get matrix profiles for a specific instance
+pub async fn hqp_instance_matrix_profiles_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    let adapter = match state.hqp_instances.get(&name).await {
+        Some(a) => a,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Instance not found: {}", name),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    let profiles = adapter.get_matrix_profiles().await;
+    let current = adapter.get_matrix_profile().await;
+
+    match (profiles, current) {
+        (Ok(profiles), Ok(current)) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "instance": name,
+                "profiles": profiles,
+                "current": current
+            })),
+        )
+            .into_response(),
+        (Err(e), _) | (_, Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Matrix profile request for instance
+#[derive(Deserialize)]
+pub struct HqpInstanceMatrixProfileRequest {
+    pub value: u32,
+}
+
+/// POST /hqp/instances/:name/matrix/profile - Set matrix profile on a specific instance
+pub async fn hqp_instance_set_matrix_profile_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<HqpInstanceMatrixProfileRequest>,
+) -> impl IntoResponse {
+    let adapter = match state.hqp_instances.get(&name).await {
+        Some(a) => a,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Instance not found: {}", name),
+                }),
+            )
+                .into_response()
+        }
+    };
+
+    match adapter.set_matrix_profile(req.value).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "instance": name, "value": req.value})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// HQPlayer playlist and library handlers
+// =============================================================================
+
+/// Playlist wrapper
+#[derive(Serialize)]
+struct HqpPlaylistWrapper {
+    items: Vec<HqpPlaylistItem>,
+}
+
+/// Library wrapper
+#[derive(Serialize)]
+struct HqpLibraryWrapper {
+    directories: Vec<HqpLibraryDirectory>,
+}
+
+fn hqp_instance_not_found(name: &str) -> axum::response::Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: format!("Instance not found: {}", name),
+        }),
+    )
+        .into_response()
+}
+
+/// GET /hqp/instances/:name/playlist - Get an instance's playlist
+pub async fn hqp_instance_playlist_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    let Some(adapter) = state.hqp_instances.get(&name).await else {
+        return hqp_instance_not_found(&name);
+    };
+
+    match adapter.get_playlist().await {
+        Ok(items) => (StatusCode::OK, Json(HqpPlaylistWrapper { items })).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Playlist edit request
+#[derive(Deserialize)]
+pub struct HqpPlaylistRequest {
+    /// add, clear, remove or play
+    pub action: String,
+    /// File path or URL (add)
+    pub uri: Option<String>,
+    /// Playlist entry (remove, play)
+    pub index: Option<u32>,
+    /// Add after the playing track instead of at the end
+    #[serde(default)]
+    pub queued: bool,
+    /// Empty the playlist before adding
+    #[serde(default)]
+    pub clear: bool,
+}
+
+/// POST /hqp/instances/:name/playlist - Edit an instance's playlist
+pub async fn hqp_instance_playlist_edit_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<HqpPlaylistRequest>,
+) -> impl IntoResponse {
+    let Some(adapter) = state.hqp_instances.get(&name).await else {
+        return hqp_instance_not_found(&name);
+    };
+
+    let result = match (req.action.as_str(), req.uri.as_deref(), req.index) {
+        ("add", Some(uri), _) => adapter.playlist_add(uri, req.queued, req.clear).await,
+        ("clear", _, _) => adapter.playlist_clear().await,
+        ("remove", _, Some(index)) => adapter.playlist_remove(index).await,
+        ("play", _, Some(index)) => adapter.playlist_play(index).await,
+        ("add", None, _) => Err(anyhow::anyhow!("add needs a uri")),
+        ("remove" | "play", _, None) => Err(anyhow::anyhow!("{} needs an index", req.action)),
+        _ => Err(anyhow::anyhow!("Unknown playlist action: {}", req.action)),
+    };
+
+    match result {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "instance": name, "action": req.action})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Library query
+#[derive(Deserialize)]
+pub struct HqpLibraryQuery {
+    /// Only directories whose path, artist or album contains this (case-insensitive)
+    pub query: Option<String>,
+}
+
+/// GET /hqp/instances/:name/library - Browse an instance's library
+pub async fn hqp_instance_library_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<HqpLibraryQuery>,
+) -> impl IntoResponse {
+    let Some(adapter) = state.hqp_instances.get(&name).await else {
+        return hqp_instance_not_found(&name);
+    };
+
+    match adapter.get_library().await {
+        Ok(mut directories) => {
+            if let Some(query) = params.query.filter(|q| !q.is_empty()) {
+                let query = query.to_lowercase();
+                directories.retain(|d| {
+                    [&d.path, &d.artist, &d.album]
+                        .iter()
+                        .any(|field| field.to_lowercase().contains(&query))
+                });
+            }
+            (StatusCode::OK, Json(HqpLibraryWrapper { directories })).into_response()
         }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Library load request
+#[derive(Deserialize)]
+pub struct HqpLibraryLoadRequest {
+    pub path: String,
+    /// Add after the playing track instead of replacing the playlist
+    #[serde(default)]
+    pub queued: bool,
+}
+
+/// POST /hqp/instances/:name/library/load - Load a library directory into the playlist
+pub async fn hqp_instance_library_load_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<HqpLibraryLoadRequest>,
+) -> impl IntoResponse {
+    let Some(adapter) = state.hqp_instances.get(&name).await else {
+        return hqp_instance_not_found(&name);
     };
 
-    let (read_half, mut write_half) = stream.into_split();
-    let mut reader = BufReader::new(read_half);
+    match adapter.library_load(&req.path, req.queued).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "instance": name, "path": req.path})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// HQPlayer pipeline preset handlers
+// =============================================================================
+
+/// Presets list wrapper
+#[derive(Serialize)]
+struct PresetsWrapper {
+    presets: Vec<HqpPreset>,
+}
+
+/// GET /hqp/presets - List saved pipeline presets
+pub async fn hqp_presets_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let presets = state.hqp_instances.list_presets().await;
+    Json(PresetsWrapper { presets })
+}
+
+/// Preset request (save from / apply to an instance)
+#[derive(Deserialize)]
+pub struct HqpPresetRequest {
+    pub name: String,
+    pub instance: String,
+}
+
+/// POST /hqp/presets - Snapshot an instance's pipeline as a preset
+pub async fn hqp_save_preset_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HqpPresetRequest>,
+) -> impl IntoResponse {
+    match state
+        .hqp_instances
+        .save_preset(&req.name, &req.instance)
+        .await
+    {
+        Ok(preset) => (StatusCode::OK, Json(preset)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// DELETE /hqp/presets/{name} - Delete a preset
+pub async fn hqp_delete_preset_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if state.hqp_instances.delete_preset(&name).await {
+        (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response()
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Preset not found: {}", name),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// POST /hqp/presets/apply - Apply a preset to an instance
+pub async fn hqp_apply_preset_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HqpPresetRequest>,
+) -> impl IntoResponse {
+    match state
+        .hqp_instances
+        .apply_preset(&req.name, &req.instance)
+        .await
+    {
+        Ok(preset) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "preset": preset.name, "instance": req.instance})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Pipeline copy request
+#[derive(Deserialize)]
+pub struct HqpCopyPipelineRequest {
+    pub from: String,
+    pub to: String,
+}
+
+/// POST /hqp/pipeline/copy - Copy one instance's pipeline to another
+pub async fn hqp_copy_pipeline_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HqpCopyPipelineRequest>,
+) -> impl IntoResponse {
+    match state.hqp_instances.copy_pipeline(&req.from, &req.to).await {
+        Ok(settings) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"ok": true, "settings": settings})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+// =============================================================================
+// HQPlayer A/B/X handlers
+// =============================================================================
+
+/// A/B/X session start request
+#[derive(Deserialize)]
+pub struct AbxStartRequest {
+    pub instance: String,
+    pub presets: Vec<String>,
+}
+
+/// A/B/X option request ("A", "B", ... or "X")
+#[derive(Deserialize)]
+pub struct AbxLabelRequest {
+    pub label: String,
+}
+
+fn abx_response<T: Serialize>(result: anyhow::Result<T>) -> axum::response::Response {
+    match result {
+        Ok(value) => (StatusCode::OK, Json(value)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /hqp/abx - Current A/B/X session (without revealing X)
+pub async fn hqp_abx_status_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.abx.status().await)
+}
+
+/// POST /hqp/abx/start - Start a blind comparison of presets on an instance
+pub async fn hqp_abx_start_handler(
+    State(state): State<AppState>,
+    Json(req): Json<AbxStartRequest>,
+) -> impl IntoResponse {
+    abx_response(state.abx.start(&req.instance, &req.presets).await)
+}
+
+/// POST /hqp/abx/select - Switch to an option or to X
+pub async fn hqp_abx_select_handler(
+    State(state): State<AppState>,
+    Json(req): Json<AbxLabelRequest>,
+) -> impl IntoResponse {
+    abx_response(state.abx.select(&req.label).await)
+}
+
+/// POST /hqp/abx/guess - Guess which option X is
+pub async fn hqp_abx_guess_handler(
+    State(state): State<AppState>,
+    Json(req): Json<AbxLabelRequest>,
+) -> impl IntoResponse {
+    abx_response(state.abx.guess(&req.label).await)
+}
+
+/// POST /hqp/abx/finish - End the session and reveal the results
+pub async fn hqp_abx_finish_handler(State(state): State<AppState>) -> impl IntoResponse {
+    abx_response(state.abx.finish().await)
+}
+
+// =============================================================================
+// HQPlayer zone linking handlers
+// =============================================================================
+
+/// GET /hqp/zones/links - Get all zone links
+pub async fn hqp_zone_links_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let links = state.hqp_zone_links.get_links().await;
+    Json(serde_json::json!({ "links": links }))
+}
+
+/// Zone link request
+#[derive(Deserialize)]
+pub struct ZoneLinkRequest {
+    pub zone_id: String,
+    pub instance: String,
+}
+
+/// POST /hqp/zones/link - Link a zone to an HQPlayer instance
+pub async fn hqp_zone_link_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ZoneLinkRequest>,
+) -> impl IntoResponse {
+    if req.zone_id.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "zone_id is required".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    if req.instance.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "instance is required".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    match state
+        .hqp_zone_links
+        .link_zone(req.zone_id.clone(), req.instance.clone())
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "ok": true,
+                "zone_id": req.zone_id,
+                "instance": req.instance
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Zone unlink request
+#[derive(Deserialize)]
+pub struct ZoneUnlinkRequest {
+    pub zone_id: String,
+}
 
-    // Read initial greeting
-    let mut greeting = String::new();
-    if timeout(Duration::from_secs(2), reader.read_line(&mut greeting))
+/// POST /hqp/zones/unlink - Unlink a zone from HQPlayer
+pub async fn hqp_zone_unlink_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ZoneUnlinkRequest>,
+) -> impl IntoResponse {
+    if req.zone_id.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "zone_id is required".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let was_linked = state.hqp_zone_links.unlink_zone(&req.zone_id).await;
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "ok": true,
+            "zone_id": req.zone_id,
+            "was_linked": was_linked
+        })),
+    )
+        .into_response()
+}
+
+/// Zone fallbacks request
+#[derive(Deserialize)]
+pub struct ZoneFallbacksRequest {
+    pub zone_id: String,
+    #[serde(default)]
+    pub fallbacks: Vec<String>,
+}
+
+/// POST /hqp/zones/fallbacks - Set the instances a linked zone fails over to
+pub async fn hqp_zone_fallbacks_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ZoneFallbacksRequest>,
+) -> impl IntoResponse {
+    match state
+        .hqp_zone_links
+        .set_fallbacks(&req.zone_id, req.fallbacks.clone())
         .await
-        .is_err()
     {
-        return Json(serde_json::json!({
-            "reachable": false,
-            "error": "No response from HQPlayer"
-        }));
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "ok": true,
+                "zone_id": req.zone_id,
+                "fallbacks": req.fallbacks
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
     }
+}
 
-    // Send INFO command
-    if write_half
-        .write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?><info/>\n")
+/// Zone volume routing request
+#[derive(Deserialize)]
+pub struct ZoneVolumeRoutingRequest {
+    pub zone_id: String,
+    pub enabled: bool,
+}
+
+/// POST /hqp/zones/volume - Route a linked zone's volume to HQPlayer (or back)
+pub async fn hqp_zone_volume_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ZoneVolumeRoutingRequest>,
+) -> impl IntoResponse {
+    match state
+        .hqp_zone_links
+        .set_volume_routing(&req.zone_id, req.enabled)
         .await
-        .is_err()
     {
-        return Json(serde_json::json!({
-            "reachable": false,
-            "error": "Failed to send command to HQPlayer"
-        }));
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "ok": true,
+                "zone_id": req.zone_id,
+                "enabled": req.enabled
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
     }
+}
 
-    // Read INFO response
-    let mut response = String::new();
-    if timeout(Duration::from_secs(2), reader.read_line(&mut response))
+/// GET /hqp/zones/:zone_id/pipeline - Get HQP pipeline for a linked zone
+pub async fn hqp_zone_pipeline_handler(
+    State(state): State<AppState>,
+    Path(zone_id): Path<String>,
+) -> impl IntoResponse {
+    match state.hqp_zone_links.get_pipeline_for_zone(&zone_id).await {
+        Some(pipeline) => (StatusCode::OK, Json(pipeline)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!(
+                    "Zone {} not linked to HQPlayer or HQPlayer not configured",
+                    zone_id
+                ),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /hqp/zones/rules - Format rules of all linked zones
+pub async fn hqp_zone_rules_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let rules = state.hqp_zone_links.get_all_rules().await;
+    Json(serde_json::json!({ "rules": rules }))
+}
+
+/// Format rules request
+#[derive(Deserialize)]
+pub struct ZoneRulesRequest {
+    pub zone_id: String,
+    #[serde(default)]
+    pub rules: Vec<HqpFormatRule>,
+}
+
+/// POST /hqp/zones/rules - Replace a linked zone's format rules
+pub async fn hqp_zone_set_rules_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ZoneRulesRequest>,
+) -> impl IntoResponse {
+    let count = req.rules.len();
+    match state
+        .hqp_zone_links
+        .set_rules(&req.zone_id, req.rules)
         .await
-        .is_err()
     {
-        return Json(serde_json::json!({
-            "reachable": false,
-            "error": "No INFO response from HQPlayer"
-        }));
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "ok": true,
+                "zone_id": req.zone_id,
+                "rules": count
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+            .into_response(),
     }
+}
 
-    // Parse XML response for product/version
-    let product = extract_xml_attr(&response, "product");
-    let version = extract_xml_attr(&response, "version");
-    let is_embedded = product
-        .as_ref()
-        .map(|p| p.to_lowercase().contains("embedded"))
-        .unwrap_or(false);
+// =============================================================================
+// HQPlayer discovery handler
+// =============================================================================
 
-    Json(serde_json::json!({
-        "reachable": true,
-        "product": product,
-        "version": version,
-        "isEmbedded": is_embedded
-    }))
+/// HQP discovery request
+#[derive(Deserialize)]
+pub struct HqpDiscoverRequest {
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
 }
 
-/// Extract attribute value from XML string
-fn extract_xml_attr(xml: &str, attr: &str) -> Option<String> {
-    let pattern = format!("{}=\"", attr);
-    if let Some(start) = xml.find(&pattern) {
-        let value_start = start + pattern.len();
-        if let Some(end) = xml[value_start..].find('"') {
-            return Some(xml[value_start..value_start + end].to_string());
-        }
+/// GET /hqp/discover - Discover HQPlayer instances on the network via UDP multicast
+pub async fn hqp_discover_handler(Query(params): Query<HqpDiscoverRequest>) -> impl IntoResponse {
+    use crate::adapters::hqplayer::discover_hqplayers;
+
+    match discover_hqplayers(params.timeout_ms).await {
+        Ok(instances) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "discovered": instances })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Discovery failed: {}", e),
+            }),
+        )
+            .into_response(),
     }
-    None
+}
+
+/// GET /hqp/discovery - Background discovery settings and instances answering
+pub async fn hqp_discovery_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.hqp_instances.discovery_status().await)
+}
+
+/// Background discovery settings request
+#[derive(Deserialize)]
+pub struct HqpDiscoverySettingsRequest {
+    pub auto_register: bool,
+}
+
+/// POST /hqp/discovery - Turn auto-registration of discovered instances on or off
+pub async fn hqp_discovery_settings_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HqpDiscoverySettingsRequest>,
+) -> impl IntoResponse {
+    state
+        .hqp_instances
+        .set_auto_register(req.auto_register)
+        .await;
+    Json(serde_json::json!({"ok": true, "auto_register": req.auto_register}))
 }
 
 // =============================================================================
-// HQPlayer multi-instance handlers
+// LMS discovery handler
 // =============================================================================
 
-/// GET /hqp/instances - List all HQPlayer instances
-pub async fn hqp_instances_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let instances = state.hqp_instances.list_instances().await;
-    Json(InstancesWrapper { instances })
+/// LMS discovery request
+#[derive(Deserialize)]
+pub struct LmsDiscoverRequest {
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// GET /lms/discover - Discover LMS servers on the network via UDP broadcast
+pub async fn lms_discover_handler(Query(params): Query<LmsDiscoverRequest>) -> impl IntoResponse {
+    use crate::adapters::lms::discover_lms_servers;
+
+    match discover_lms_servers(params.timeout_ms).await {
+        Ok(servers) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "discovered": servers })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Discovery failed: {}", e),
+            }),
+        )
+            .into_response(),
+    }
 }
 
-/// HQPlayer add instance request
-#[derive(Deserialize)]
-pub struct HqpAddInstanceRequest {
-    pub name: String,
-    pub host: String,
-    #[serde(default)]
-    pub port: Option<u16>,
-    #[serde(default)]
-    pub web_port: Option<u16>,
-    pub username: Option<String>,
-    pub password: Option<String>,
+// =============================================================================
+// Volume output handlers
+// =============================================================================
+
+/// GET /volume/outputs - List configured volume outputs
+pub async fn volume_outputs_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(OutputsWrapper {
+        outputs: state.volume_outputs.list_outputs().await,
+    })
 }
 
-/// POST /hqp/instances - Add or update an HQPlayer instance
-pub async fn hqp_add_instance_handler(
+/// POST /volume/outputs - Add or update a volume output
+pub async fn volume_add_output_handler(
     State(state): State<AppState>,
-    Json(req): Json<HqpAddInstanceRequest>,
+    Json(req): Json<VolumeOutputConfig>,
 ) -> impl IntoResponse {
     if req.name.is_empty() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
-                error: "Instance name is required".to_string(),
+                error: "Output name is required".to_string(),
             }),
         )
             .into_response();
     }
 
-    if req.host.is_empty() {
+    let name = req.name.clone();
+    if let Err(e) = state.volume_outputs.add_output(req).await {
         return (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
-                error: "Host is required".to_string(),
+                error: e.to_string(),
             }),
         )
             .into_response();
     }
 
-    let _adapter = state
-        .hqp_instances
-        .add_instance(
-            req.name.clone(),
-            req.host.clone(),
-            req.port,
-            req.web_port,
-            req.username,
-            req.password,
-        )
-        .await;
-
     (
         StatusCode::OK,
-        Json(serde_json::json!({
-            "ok": true,
-            "name": req.name,
-            "host": req.host,
-            "port": req.port.unwrap_or(4321)
-        })),
+        Json(serde_json::json!({"ok": true, "name": name})),
     )
         .into_response()
 }
 
-/// DELETE /hqp/instances/:name - Remove an HQPlayer instance
-pub async fn hqp_remove_instance_handler(
+/// DELETE /volume/outputs/:name - Remove a volume output
+pub async fn volume_remove_output_handler(
     State(state): State<AppState>,
     Path(name): Path<String>,
 ) -> impl IntoResponse {
-    // Remove zone links pointing to this instance first
-    let _links_removed = state.hqp_zone_links.remove_links_for_instance(&name).await;
-
-    if state.hqp_instances.remove_instance(&name).await {
+    if state.volume_outputs.remove_output(&name).await {
+        state
+            .volume_delegates
+            .remove_delegates_for_output(&name)
+            .await;
         (
             StatusCode::OK,
             Json(serde_json::json!({"ok": true, "removed": name})),
@@ -1171,111 +3660,22 @@ pub async fn hqp_remove_instance_handler(
         (
             StatusCode::NOT_FOUND,
             Json(ErrorResponse {
-                error: format!("Instance not found: {}", name),
+                error: format!("Volume output not found: {}", name),
             }),
         )
             .into_response()
     }
 }
 
-/// GET /hqp/instances/:name/profiles - Get profiles for a specific HQPlayer instance
-pub async fn hqp_instance_profiles_handler(
-    State(state): State<AppState>,
-    Path(name): Path<String>,
-) -> impl IntoResponse {
-    let adapter = match state.hqp_instances.get(&name).await {
-        Some(a) => a,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Instance not found: {}", name),
-                }),
-            )
-                .into_response()
-        }
-    };
-
-    match adapter.fetch_profiles().await {
-        Ok(profiles) => (StatusCode::OK, Json(profiles)).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: e.to_string(),
-            }),
-        )
-            .into_response(),
-    }
-}
-
-/// POST /hqp/instances/:name/profile - Load a profile on a specific HQPlayer instance
-pub async fn hqp_instance_load_profile_handler(
+/// GET /volume/outputs/:name/state - Query current output state
+pub async fn volume_output_state_handler(
     State(state): State<AppState>,
     Path(name): Path<String>,
-    Json(req): Json<HqpProfileRequest>,
 ) -> impl IntoResponse {
-    let adapter = match state.hqp_instances.get(&name).await {
-        Some(a) => a,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Instance not found: {}", name),
-                }),
-            )
-                .into_response()
-        }
-    };
-
-    match adapter.load_profile(&req.profile).await {
-        Ok(()) => (
-            StatusCode::OK,
-            Json(serde_json::json!({"ok": true, "instance": name, "profile": req.profile})),
-        )
-            .into_response(),
+    match state.volume_outputs.get_state(&name).await {
+        Ok(output_state) => (StatusCode::OK, Json(output_state)).into_response(),
         Err(e) => (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: e.to_string(),
-            }),
-        )
-            .into_response(),
-    }
-}
-
-/// GET /hqp/instances/:name/matrix/profiles - Get matrix profiles for a specific instance
-pub async fn hqp_instance_matrix_profiles_handler(
-    State(state): State<AppState>,
-    Path(name): Path<String>,
-) -> impl IntoResponse {
-    let adapter = match state.hqp_instances.get(&name).await {
-        Some(a) => a,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Instance not found: {}", name),
-                }),
-            )
-                .into_response()
-        }
-    };
-
-    let profiles = adapter.get_matrix_profiles().await;
-    let current = adapter.get_matrix_profile().await;
-
-    match (profiles, current) {
-        (Ok(profiles), Ok(current)) => (
-            StatusCode::OK,
-            Json(serde_json::json!({
-                "instance": name,
-                "profiles": profiles,
-                "current": current
-            })),
-        )
-            .into_response(),
-        (Err(e), _) | (_, Err(e)) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_GATEWAY,
             Json(ErrorResponse {
                 error: e.to_string(),
             }),
@@ -1284,37 +3684,28 @@ pub async fn hqp_instance_matrix_profiles_handler(
     }
 }
 
-/// Matrix profile request for instance
+/// Volume output control request
 #[derive(Deserialize)]
-pub struct HqpInstanceMatrixProfileRequest {
-    pub value: u32,
+pub struct VolumeOutputControlRequest {
+    pub output: String,
+    pub action: String,
+    #[serde(default)]
+    pub value: Option<f32>,
+    #[serde(default)]
+    pub input: Option<String>,
 }
 
-/// POST /hqp/instances/:name/matrix/profile - Set matrix profile on a specific instance
-pub async fn hqp_instance_set_matrix_profile_handler(
+/// POST /volume/control - Control a volume output (volume, mute, input, power)
+pub async fn volume_control_handler(
     State(state): State<AppState>,
-    Path(name): Path<String>,
-    Json(req): Json<HqpInstanceMatrixProfileRequest>,
+    Json(req): Json<VolumeOutputControlRequest>,
 ) -> impl IntoResponse {
-    let adapter = match state.hqp_instances.get(&name).await {
-        Some(a) => a,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Instance not found: {}", name),
-                }),
-            )
-                .into_response()
-        }
-    };
-
-    match adapter.set_matrix_profile(req.value).await {
-        Ok(()) => (
-            StatusCode::OK,
-            Json(serde_json::json!({"ok": true, "instance": name, "value": req.value})),
-        )
-            .into_response(),
+    match state
+        .volume_outputs
+        .control(&req.output, &req.action, req.value, req.input.as_deref())
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
         Err(e) => (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -1325,27 +3716,23 @@ pub async fn hqp_instance_set_matrix_profile_handler(
     }
 }
 
-// =============================================================================
-// HQPlayer zone linking handlers
-// =============================================================================
-
-/// GET /hqp/zones/links - Get all zone links
-pub async fn hqp_zone_links_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let links = state.hqp_zone_links.get_links().await;
-    Json(serde_json::json!({ "links": links }))
+/// GET /volume/zones/delegates - Get all zone volume delegates
+pub async fn volume_zone_delegates_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let delegates = state.volume_delegates.get_delegates().await;
+    Json(serde_json::json!({ "delegates": delegates }))
 }
 
-/// Zone link request
+/// Zone volume delegate request
 #[derive(Deserialize)]
-pub struct ZoneLinkRequest {
+pub struct VolumeDelegateRequest {
     pub zone_id: String,
-    pub instance: String,
+    pub output: String,
 }
 
-/// POST /hqp/zones/link - Link a zone to an HQPlayer instance
-pub async fn hqp_zone_link_handler(
+/// POST /volume/zones/delegate - Delegate a zone's volume to a volume output
+pub async fn volume_zone_delegate_handler(
     State(state): State<AppState>,
-    Json(req): Json<ZoneLinkRequest>,
+    Json(req): Json<VolumeDelegateRequest>,
 ) -> impl IntoResponse {
     if req.zone_id.is_empty() {
         return (
@@ -1357,19 +3744,19 @@ pub async fn hqp_zone_link_handler(
             .into_response();
     }
 
-    if req.instance.is_empty() {
+    if req.output.is_empty() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
-                error: "instance is required".to_string(),
+                error: "output is required".to_string(),
             }),
         )
             .into_response();
     }
 
     match state
-        .hqp_zone_links
-        .link_zone(req.zone_id.clone(), req.instance.clone())
+        .volume_delegates
+        .delegate_zone(req.zone_id.clone(), req.output.clone())
         .await
     {
         Ok(()) => (
@@ -1377,7 +3764,7 @@ pub async fn hqp_zone_link_handler(
             Json(serde_json::json!({
                 "ok": true,
                 "zone_id": req.zone_id,
-                "instance": req.instance
+                "output": req.output
             })),
         )
             .into_response(),
@@ -1391,14 +3778,8 @@ pub async fn hqp_zone_link_handler(
     }
 }
 
-/// Zone unlink request
-#[derive(Deserialize)]
-pub struct ZoneUnlinkRequest {
-    pub zone_id: String,
-}
-
-/// POST /hqp/zones/unlink - Unlink a zone from HQPlayer
-pub async fn hqp_zone_unlink_handler(
+/// POST /volume/zones/undelegate - Return a zone's volume to its source
+pub async fn volume_zone_undelegate_handler(
     State(state): State<AppState>,
     Json(req): Json<ZoneUnlinkRequest>,
 ) -> impl IntoResponse {
@@ -1412,70 +3793,19 @@ pub async fn hqp_zone_unlink_handler(
             .into_response();
     }
 
-    let was_linked = state.hqp_zone_links.unlink_zone(&req.zone_id).await;
+    let was_delegated = state.volume_delegates.remove_delegate(&req.zone_id).await;
 
     (
         StatusCode::OK,
         Json(serde_json::json!({
             "ok": true,
             "zone_id": req.zone_id,
-            "was_linked": was_linked
+            "was_delegated": was_delegated
         })),
     )
         .into_response()
 }
 
-/// GET /hqp/zones/:zone_id/pipeline - Get HQP pipeline for a linked zone
-pub async fn hqp_zone_pipeline_handler(
-    State(state): State<AppState>,
-    Path(zone_id): Path<String>,
-) -> impl IntoResponse {
-    match state.hqp_zone_links.get_pipeline_for_zone(&zone_id).await {
-        Some(pipeline) => (StatusCode::OK, Json(pipeline)).into_response(),
-        None => (
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: format!(
-                    "Zone {} not linked to HQPlayer or HQPlayer not configured",
-                    zone_id
-                ),
-            }),
-        )
-            .into_response(),
-    }
-}
-
-// =============================================================================
-// HQPlayer discovery handler
-// =============================================================================
-
-/// HQP discovery request
-#[derive(Deserialize)]
-pub struct HqpDiscoverRequest {
-    #[serde(default)]
-    pub timeout_ms: Option<u64>,
-}
-
-/// GET /hqp/discover - Discover HQPlayer instances on the network via UDP multicast
-pub async fn hqp_discover_handler(Query(params): Query<HqpDiscoverRequest>) -> impl IntoResponse {
-    use crate::adapters::hqplayer::discover_hqplayers;
-
-    match discover_hqplayers(params.timeout_ms).await {
-        Ok(instances) => (
-            StatusCode::OK,
-            Json(serde_json::json!({ "discovered": instances })),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Discovery failed: {}", e),
-            }),
-        )
-            .into_response(),
-    }
-}
-
 // =============================================================================
 // App settings handlers
 // =============================================================================
@@ -1504,6 +3834,12 @@ pub struct AdapterSettings {
     pub openhome: bool,
     #[serde(default)]
     pub lms: bool,
+    #[serde(default)]
+    pub airplay: bool,
+    #[serde(default)]
+    pub spotify: bool,
+    #[serde(default)]
+    pub remote: bool,
 }
 
 fn default_true() -> bool {
@@ -1521,6 +3857,9 @@ impl Default for AppSettings {
                 upnp: false,
                 openhome: false,
                 lms: false,
+                airplay: false,
+                spotify: false,
+                remote: false,
             },
         }
     }
@@ -1615,6 +3954,9 @@ pub async fn api_settings_post_handler(
         ("lms", old_adapters.lms != new_adapters.lms),
         ("openhome", old_adapters.openhome != new_adapters.openhome),
         ("upnp", old_adapters.upnp != new_adapters.upnp),
+        ("airplay", old_adapters.airplay != new_adapters.airplay),
+        ("spotify", old_adapters.spotify != new_adapters.spotify),
+        ("remote", old_adapters.remote != new_adapters.remote),
     ];
 
     for (name, changed) in adapter_changes {
@@ -1628,6 +3970,9 @@ pub async fn api_settings_post_handler(
             "lms" => new_adapters.lms,
             "openhome" => new_adapters.openhome,
             "upnp" => new_adapters.upnp,
+            "airplay" => new_adapters.airplay,
+            "spotify" => new_adapters.spotify,
+            "remote" => new_adapters.remote,
             _ => continue,
         };
 
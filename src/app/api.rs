@@ -47,6 +47,9 @@ pub struct AdapterSettings {
     pub lms: bool,
     pub openhome: bool,
     pub upnp: bool,
+    pub airplay: bool,
+    pub spotify: bool,
+    pub remote: bool,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
@@ -111,6 +114,26 @@ pub struct LmsPlayer {
     pub volume: i32,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LmsSyncGroup {
+    pub group_id: String,
+    pub name: String,
+    pub volume: i32,
+    pub members: Vec<LmsSyncMember>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LmsSyncMember {
+    pub playerid: String,
+    pub name: String,
+    pub volume: i32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LmsGroupsResponse {
+    pub groups: Vec<LmsSyncGroup>,
+}
+
 // =============================================================================
 // HQPlayer Types
 // =============================================================================
@@ -342,3 +365,28 @@ pub async fn post_json_no_response<T: Serialize>(url: &str, body: &T) -> Result<
 pub async fn post_json_no_response<T: Serialize>(_url: &str, _body: &T) -> Result<(), String> {
     Err("post_json_no_response is only available in browser".to_string())
 }
+
+/// Send a DELETE request, ignoring the response body
+#[cfg(target_arch = "wasm32")]
+pub async fn delete_request(url: &str) -> Result<(), String> {
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Request, RequestInit};
+
+    let window = web_sys::window().ok_or("No window")?;
+    let opts = RequestInit::new();
+    opts.set_method("DELETE");
+
+    let request = Request::new_with_str_and_init(url, &opts).map_err(|e| format!("{:?}", e))?;
+
+    JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    Ok(())
+}
+
+/// SSR stub - returns error (should not be called during SSR)
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn delete_request(_url: &str) -> Result<(), String> {
+    Err("delete_request is only available in browser".to_string())
+}
@@ -4,7 +4,7 @@
 
 use dioxus::prelude::*;
 
-use crate::app::api::{AppSettings, LmsConfig, LmsPlayer};
+use crate::app::api::{AppSettings, LmsConfig, LmsGroupsResponse, LmsPlayer, LmsSyncGroup};
 use crate::app::components::Layout;
 use crate::app::sse::use_sse;
 
@@ -19,6 +19,44 @@ struct LmsConfigureRequest {
     password: Option<String>,
 }
 
+/// Server found by /lms/discover
+#[derive(Clone, Debug, Default, serde::Deserialize, PartialEq)]
+struct DiscoveredLms {
+    host: String,
+    port: u16,
+    name: String,
+}
+
+/// LMS discovery response
+#[derive(Clone, Debug, Default, serde::Deserialize, PartialEq)]
+struct LmsDiscoverResponse {
+    discovered: Vec<DiscoveredLms>,
+}
+
+/// LMS server instance from /lms/instances
+#[derive(Clone, Debug, Default, serde::Deserialize, PartialEq)]
+struct LmsInstance {
+    name: String,
+    host: Option<String>,
+    port: u16,
+    connected: bool,
+    player_count: usize,
+}
+
+/// LMS instances response
+#[derive(Clone, Debug, Default, serde::Deserialize, PartialEq)]
+struct LmsInstancesResponse {
+    instances: Vec<LmsInstance>,
+}
+
+/// Add LMS instance request
+#[derive(Clone, serde::Serialize)]
+struct LmsAddInstanceRequest {
+    name: String,
+    host: String,
+    port: u16,
+}
+
 /// LMS control request
 #[derive(Clone, serde::Serialize)]
 struct LmsControlRequest {
@@ -26,6 +64,28 @@ struct LmsControlRequest {
     action: String,
 }
 
+/// LMS sync request
+#[derive(Clone, serde::Serialize)]
+struct LmsSyncRequest {
+    player_id: String,
+    target_id: String,
+}
+
+/// LMS unsync request
+#[derive(Clone, serde::Serialize)]
+struct LmsUnsyncRequest {
+    player_id: String,
+}
+
+/// LMS group volume request
+#[derive(Clone, serde::Serialize)]
+struct LmsGroupVolumeRequest {
+    player_id: String,
+    value: i32,
+    relative: bool,
+    group: bool,
+}
+
 /// LMS page component.
 #[component]
 pub fn Lms() -> Element {
@@ -41,6 +101,16 @@ pub fn Lms() -> Element {
     let mut username = use_signal(String::new);
     let mut password = use_signal(String::new);
 
+    // Discovery state
+    let mut discovering = use_signal(|| false);
+    let mut discovered = use_signal(|| None::<Vec<DiscoveredLms>>);
+
+    // Additional server form fields
+    let mut instance_name = use_signal(String::new);
+    let mut instance_host = use_signal(String::new);
+    let mut instance_port = use_signal(|| 9000u16);
+    let mut instance_status = use_signal(|| None::<String>);
+
     // Load config resource
     let mut config = use_resource(|| async {
         crate::app::api::fetch_json::<LmsConfig>("/lms/config")
@@ -55,6 +125,20 @@ pub fn Lms() -> Element {
             .ok()
     });
 
+    // Load sync groups resource
+    let mut groups = use_resource(|| async {
+        crate::app::api::fetch_json::<LmsGroupsResponse>("/lms/groups")
+            .await
+            .ok()
+    });
+
+    // Load server instances resource
+    let mut instances = use_resource(|| async {
+        crate::app::api::fetch_json::<LmsInstancesResponse>("/lms/instances")
+            .await
+            .ok()
+    });
+
     // Check if LMS is enabled
     let settings = use_resource(|| async {
         crate::app::api::fetch_json::<AppSettings>("/api/settings")
@@ -79,6 +163,8 @@ pub fn Lms() -> Element {
         if sse.should_refresh_lms() {
             config.restart();
             players.restart();
+            groups.restart();
+            instances.restart();
         }
     });
 
@@ -118,6 +204,56 @@ pub fn Lms() -> Element {
         });
     };
 
+    // Additional server handlers
+    let add_instance = move |_| {
+        let name = instance_name();
+        let h = instance_host();
+        if name.is_empty() || h.is_empty() {
+            instance_status.set(Some("Name and host are required".to_string()));
+            return;
+        }
+        instance_status.set(Some("Adding...".to_string()));
+        spawn(async move {
+            let req = LmsAddInstanceRequest {
+                name,
+                host: h,
+                port: instance_port(),
+            };
+            match crate::app::api::post_json::<_, serde_json::Value>("/lms/instances", &req).await {
+                Ok(resp) if resp.get("error").is_some() => {
+                    instance_status.set(Some(format!("Error: {}", resp["error"])));
+                }
+                Ok(_) => {
+                    instance_status.set(None);
+                    instance_name.set(String::new());
+                    instance_host.set(String::new());
+                    instances.restart();
+                }
+                Err(e) => instance_status.set(Some(format!("Error: {}", e))),
+            }
+        });
+    };
+    let remove_instance = move |name: String| {
+        spawn(async move {
+            let url = format!("/lms/instances/{}", name);
+            let _ = crate::app::api::delete_request(&url).await;
+            instances.restart();
+        });
+    };
+
+    // Discover servers on the local network
+    let discover = move |_| {
+        discovering.set(true);
+        spawn(async move {
+            let servers = crate::app::api::fetch_json::<LmsDiscoverResponse>("/lms/discover")
+                .await
+                .map(|r| r.discovered)
+                .unwrap_or_default();
+            discovered.set(Some(servers));
+            discovering.set(false);
+        });
+    };
+
     // Control handler
     let control = move |(player_id, action): (String, String)| {
         spawn(async move {
@@ -126,11 +262,63 @@ pub fn Lms() -> Element {
         });
     };
 
+    // Sync handlers
+    let sync_player = move |(player_id, target_id): (String, String)| {
+        spawn(async move {
+            let req = LmsSyncRequest {
+                player_id,
+                target_id,
+            };
+            let _ = crate::app::api::post_json_no_response("/lms/sync", &req).await;
+            players.restart();
+            groups.restart();
+        });
+    };
+    let unsync_player = move |player_id: String| {
+        spawn(async move {
+            let req = LmsUnsyncRequest { player_id };
+            let _ = crate::app::api::post_json_no_response("/lms/unsync", &req).await;
+            players.restart();
+            groups.restart();
+        });
+    };
+    let group_volume = move |(player_id, delta): (String, i32)| {
+        spawn(async move {
+            let req = LmsGroupVolumeRequest {
+                player_id,
+                value: delta,
+                relative: true,
+                group: true,
+            };
+            let _ = crate::app::api::post_json_no_response("/lms/volume", &req).await;
+            groups.restart();
+        });
+    };
+
     let cfg = config.read().clone().flatten();
     let settings_loading = settings.read().is_none();
     let lms_enabled = settings.read().clone().flatten().map(|s| s.adapters.lms);
     let players_list = players.read().clone().flatten().unwrap_or_default();
+    let groups_list = groups
+        .read()
+        .clone()
+        .flatten()
+        .map(|g| g.groups)
+        .unwrap_or_default();
+    let player_names: Vec<(String, String)> = players_list
+        .iter()
+        .map(|p| (p.player_id.clone(), p.name.clone()))
+        .collect();
     let is_loading = config.read().is_none();
+    let extra_instances: Vec<LmsInstance> = instances
+        .read()
+        .clone()
+        .flatten()
+        .map(|r| r.instances)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|i| i.name != "default")
+        .collect();
 
     rsx! {
         Layout {
@@ -168,6 +356,36 @@ pub fn Lms() -> Element {
                     // Config form (shown when not configured or reconfiguring)
                     if show_form() || cfg.as_ref().map(|c| !c.configured).unwrap_or(true) {
                         div { class: "mt-4",
+                            // Servers found via UDP broadcast
+                            div { class: "mb-4",
+                                button {
+                                    class: "btn btn-outline",
+                                    disabled: discovering(),
+                                    onclick: discover,
+                                    if discovering() { "Searching..." } else { "Discover Servers" }
+                                }
+                                if let Some(servers) = discovered() {
+                                    if servers.is_empty() {
+                                        p { class: "mt-2 text-sm text-gray-400", "No servers found on the local network" }
+                                    } else {
+                                        div { class: "mt-2 flex flex-wrap gap-2",
+                                            for server in servers {
+                                                button {
+                                                    class: "btn btn-ghost",
+                                                    onclick: {
+                                                        let server = server.clone();
+                                                        move |_| {
+                                                            host.set(server.host.clone());
+                                                            port.set(server.port);
+                                                        }
+                                                    },
+                                                    "{server.name} ({server.host}:{server.port})"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                             div { class: "form-grid mb-4",
                                 div {
                                     label { class: "block text-sm font-medium mb-1", "Host" }
@@ -243,6 +461,92 @@ pub fn Lms() -> Element {
                 }
             }
 
+            // Additional servers section
+            section { id: "lms-instances", class: "mb-8",
+                div { class: "mb-4",
+                    h2 { class: "text-xl font-semibold", "Additional Servers" }
+                    p { class: "text-gray-400 text-sm",
+                        "Other Squeezebox servers to control alongside the main one. Their zones are named after the server."
+                    }
+                }
+                div { class: "card p-6",
+                    if !extra_instances.is_empty() {
+                        div { class: "mb-4 space-y-2",
+                            for instance in extra_instances {
+                                div { class: "flex items-center justify-between",
+                                    div {
+                                        span { class: "font-medium", "{instance.name}" }
+                                        span { class: "text-gray-400 text-sm ml-2",
+                                            "{instance.host.as_deref().unwrap_or(\"\")}:{instance.port} · {instance.player_count} players"
+                                        }
+                                        if instance.connected {
+                                            span { class: "status-ok ml-2", "✓" }
+                                        } else {
+                                            span { class: "status-err ml-2", "✗" }
+                                        }
+                                    }
+                                    button {
+                                        class: "btn btn-ghost",
+                                        onclick: {
+                                            let name = instance.name.clone();
+                                            move |_| remove_instance(name.clone())
+                                        },
+                                        "Remove"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "form-grid mb-4",
+                        div {
+                            label { class: "block text-sm font-medium mb-1", "Name" }
+                            input {
+                                class: "input",
+                                r#type: "text",
+                                placeholder: "workshop",
+                                value: "{instance_name}",
+                                oninput: move |evt| instance_name.set(evt.value())
+                            }
+                        }
+                        div {
+                            label { class: "block text-sm font-medium mb-1", "Host" }
+                            input {
+                                class: "input",
+                                r#type: "text",
+                                placeholder: "192.168.1.x or hostname",
+                                value: "{instance_host}",
+                                oninput: move |evt| instance_host.set(evt.value())
+                            }
+                        }
+                        div {
+                            label { class: "block text-sm font-medium mb-1", "Port" }
+                            input {
+                                class: "input",
+                                r#type: "number",
+                                min: "1",
+                                max: "65535",
+                                value: "{instance_port}",
+                                oninput: move |evt| {
+                                    if let Ok(p) = evt.value().parse() {
+                                        instance_port.set(p);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        button { class: "btn btn-outline", onclick: add_instance, "Add Server" }
+                        if let Some(ref status) = instance_status() {
+                            if status.starts_with("Error") || status.contains("required") {
+                                span { class: "status-err", "{status}" }
+                            } else {
+                                span { class: "text-gray-400", "{status}" }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Players section
             section { id: "lms-players", class: "mb-8",
                 div { class: "mb-4",
@@ -271,7 +575,32 @@ pub fn Lms() -> Element {
                         for player in players_list {
                             PlayerCard {
                                 player: player.clone(),
+                                others: player_names
+                                    .iter()
+                                    .filter(|(id, _)| *id != player.player_id)
+                                    .cloned()
+                                    .collect::<Vec<_>>(),
                                 on_control: control,
+                                on_sync: sync_player,
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Sync groups section
+            if !groups_list.is_empty() {
+                section { id: "lms-groups", class: "mb-8",
+                    div { class: "mb-4",
+                        h2 { class: "text-xl font-semibold", "Sync Groups" }
+                        p { class: "text-gray-400 text-sm", "Synchronized players appear as one zone" }
+                    }
+                    div { class: "zone-grid",
+                        for group in groups_list {
+                            GroupCard {
+                                group: group.clone(),
+                                on_unsync: unsync_player,
+                                on_volume: group_volume,
                             }
                         }
                     }
@@ -281,10 +610,65 @@ pub fn Lms() -> Element {
     }
 }
 
+/// Sync group card component
+#[component]
+fn GroupCard(
+    group: LmsSyncGroup,
+    on_unsync: EventHandler<String>,
+    on_volume: EventHandler<(String, i32)>,
+) -> Element {
+    let group_id_down = group.group_id.clone();
+    let group_id_up = group.group_id.clone();
+
+    rsx! {
+        div { class: "card p-4",
+            div { class: "flex items-center gap-2 mb-3",
+                span { class: "font-semibold text-lg", "{group.name}" }
+            }
+
+            // Members
+            div { class: "mb-4",
+                for member in group.members.clone() {
+                    div { class: "flex items-center gap-2 text-sm",
+                        span { class: "truncate", "{member.name}" }
+                        span { class: "text-gray-400", "{member.volume}%" }
+                        button {
+                            class: "btn btn-ghost ml-auto",
+                            onclick: move |_| on_unsync.call(member.playerid.clone()),
+                            "Unsync"
+                        }
+                    }
+                }
+            }
+
+            // Group volume
+            div { class: "flex items-center gap-2",
+                button {
+                    class: "btn btn-ghost",
+                    onclick: move |_| on_volume.call((group_id_down.clone(), -5)),
+                    "−"
+                }
+                span { class: "text-sm text-gray-400", "Group volume: {group.volume}%" }
+                button {
+                    class: "btn btn-ghost",
+                    onclick: move |_| on_volume.call((group_id_up.clone(), 5)),
+                    "+"
+                }
+            }
+        }
+    }
+}
+
 /// Player card component
 #[component]
-fn PlayerCard(player: LmsPlayer, on_control: EventHandler<(String, String)>) -> Element {
+fn PlayerCard(
+    player: LmsPlayer,
+    others: Vec<(String, String)>,
+    on_control: EventHandler<(String, String)>,
+    on_sync: EventHandler<(String, String)>,
+) -> Element {
     let player_id = player.player_id.clone();
+    let player_id_sync = player_id.clone();
     let player_id_prev = player_id.clone();
     let player_id_play = player_id.clone();
     let player_id_next = player_id.clone();
@@ -334,6 +718,23 @@ fn PlayerCard(player: LmsPlayer, on_control: EventHandler<(String, String)>) ->
                 }
                 span { class: "ml-auto text-sm text-gray-400", "Volume: {player.volume}%" }
             }
+
+            // Sync with another player
+            if !others.is_empty() {
+                select {
+                    class: "input mt-3",
+                    onchange: move |evt| {
+                        let target = evt.value();
+                        if !target.is_empty() {
+                            on_sync.call((player_id_sync.clone(), target));
+                        }
+                    },
+                    option { value: "", "Sync with…" }
+                    for (id, name) in others {
+                        option { value: "{id}", "{name}" }
+                    }
+                }
+            }
         }
     }
 }
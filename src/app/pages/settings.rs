@@ -30,6 +30,9 @@ pub fn Settings() -> Element {
     let mut lms_enabled = use_signal(|| false);
     let mut openhome_enabled = use_signal(|| false);
     let mut upnp_enabled = use_signal(|| false);
+    let mut airplay_enabled = use_signal(|| false);
+    let mut spotify_enabled = use_signal(|| false);
+    let mut remote_enabled = use_signal(|| false);
 
     // Load settings resource
     let settings = use_resource(|| async {
@@ -45,6 +48,9 @@ pub fn Settings() -> Element {
             lms_enabled.set(s.adapters.lms);
             openhome_enabled.set(s.adapters.openhome);
             upnp_enabled.set(s.adapters.upnp);
+            airplay_enabled.set(s.adapters.airplay);
+            spotify_enabled.set(s.adapters.spotify);
+            remote_enabled.set(s.adapters.remote);
         }
     });
 
@@ -84,6 +90,9 @@ pub fn Settings() -> Element {
                 lms: lms_enabled(),
                 openhome: openhome_enabled(),
                 upnp: upnp_enabled(),
+                airplay: airplay_enabled(),
+                spotify: spotify_enabled(),
+                remote: remote_enabled(),
             },
         };
         spawn(async move {
@@ -159,6 +168,42 @@ pub fn Settings() -> Element {
                             }
                             "UPnP/DLNA"
                         }
+                        label { class: "flex items-center gap-2",
+                            input {
+                                r#type: "checkbox",
+                                class: "checkbox",
+                                checked: airplay_enabled(),
+                                onchange: move |_| {
+                                    airplay_enabled.toggle();
+                                    save_settings();
+                                }
+                            }
+                            "AirPlay (shairport-sync)"
+                        }
+                        label { class: "flex items-center gap-2",
+                            input {
+                                r#type: "checkbox",
+                                class: "checkbox",
+                                checked: spotify_enabled(),
+                                onchange: move |_| {
+                                    spotify_enabled.toggle();
+                                    save_settings();
+                                }
+                            }
+                            "Spotify Connect (librespot)"
+                        }
+                        label { class: "flex items-center gap-2",
+                            input {
+                                r#type: "checkbox",
+                                class: "checkbox",
+                                checked: remote_enabled(),
+                                onchange: move |_| {
+                                    remote_enabled.toggle();
+                                    save_settings();
+                                }
+                            }
+                            "Remote bridges (federation)"
+                        }
                     }
                     p { class: "mt-3 text-sm text-gray-400",
                         "Changes take effect immediately. Disabled adapters won't contribute zones."
@@ -54,6 +54,8 @@ pub enum PlaybackState {
     Loading,
     /// Buffering (used by streaming sources)
     Buffering,
+    /// Device in standby (e.g. OpenHome Product standby)
+    Standby,
     /// Unknown/unavailable state
     #[default]
     Unknown,
@@ -67,6 +69,7 @@ impl std::fmt::Display for PlaybackState {
             Self::Stopped => write!(f, "stopped"),
             Self::Loading => write!(f, "loading"),
             Self::Buffering => write!(f, "buffering"),
+            Self::Standby => write!(f, "standby"),
             Self::Unknown => write!(f, "unknown"),
         }
     }
@@ -80,6 +83,7 @@ impl From<&str> for PlaybackState {
             "stopped" | "stop" => Self::Stopped,
             "loading" => Self::Loading,
             "buffering" => Self::Buffering,
+            "standby" => Self::Standby,
             _ => Self::Unknown,
         }
     }
@@ -369,6 +373,24 @@ pub enum BusEvent {
         is_muted: bool,
     },
 
+    /// A zone's volume was delegated to (or returned from) a volume output
+    ZoneVolumeDelegated {
+        /// Zone identifier
+        zone_id: String,
+        /// Delegate's volume control (None when delegation is removed)
+        volume_control: Option<VolumeControl>,
+    },
+
+    /// A zone's HQPlayer link moved to another instance (failover or recovery)
+    HqpLinkFailover {
+        /// Linked zone identifier
+        zone_id: String,
+        /// Instance the link was using
+        from: String,
+        /// Instance the link uses now
+        to: String,
+    },
+
     // =========================================================================
     // Command Events
     // =========================================================================
@@ -431,6 +453,24 @@ pub enum BusEvent {
         reason: Option<String>,
     },
 
+    /// An HQPlayer instance started answering network discovery
+    HqpDiscovered {
+        /// Name HQPlayer announces
+        name: String,
+        /// Address it answered from
+        host: String,
+        /// HQPlayer version
+        version: String,
+    },
+
+    /// A discovered HQPlayer instance stopped answering network discovery
+    HqpLost {
+        /// Name HQPlayer announced
+        name: String,
+        /// Last address it answered from
+        host: String,
+    },
+
     // =========================================================================
     // System Events
     // =========================================================================
@@ -499,6 +539,8 @@ impl BusEvent {
             Self::NowPlayingChanged { .. } => "now_playing_changed",
             Self::SeekPositionChanged { .. } => "seek_position_changed",
             Self::VolumeChanged { .. } => "volume_changed",
+            Self::ZoneVolumeDelegated { .. } => "zone_volume_delegated",
+            Self::HqpLinkFailover { .. } => "hqp_link_failover",
             Self::CommandReceived { .. } => "command_received",
             Self::CommandResult { .. } => "command_result",
             Self::AdapterStopping { .. } => "adapter_stopping",
@@ -506,6 +548,8 @@ impl BusEvent {
             Self::ZonesFlushed { .. } => "zones_flushed",
             Self::AdapterConnected { .. } => "adapter_connected",
             Self::AdapterDisconnected { .. } => "adapter_disconnected",
+            Self::HqpDiscovered { .. } => "hqp_discovered",
+            Self::HqpLost { .. } => "hqp_lost",
             Self::ShuttingDown { .. } => "shutting_down",
             Self::HealthCheck { .. } => "health_check",
             Self::RoonConnected { .. } => "roon_connected",
@@ -539,6 +583,7 @@ impl BusEvent {
             Self::NowPlayingChanged { .. }
                 | Self::SeekPositionChanged { .. }
                 | Self::VolumeChanged { .. }
+                | Self::ZoneVolumeDelegated { .. }
         )
     }
 
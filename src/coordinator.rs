@@ -19,7 +19,9 @@ use std::sync::Arc;
 
 /// All available adapters in the system.
 /// This is the single source of truth for what adapters exist.
-pub const AVAILABLE_ADAPTERS: &[&str] = &["roon", "lms", "openhome", "upnp"];
+pub const AVAILABLE_ADAPTERS: &[&str] = &[
+    "roon", "lms", "openhome", "upnp", "airplay", "spotify", "remote",
+];
 
 /// Registered adapter with its spawn function
 struct RegisteredAdapter {
@@ -76,6 +78,9 @@ impl AdapterCoordinator {
                 "lms" => settings.lms,
                 "openhome" => settings.openhome,
                 "upnp" => settings.upnp,
+                "airplay" => settings.airplay,
+                "spotify" => settings.spotify,
+                "remote" => settings.remote,
                 _ => false,
             };
             self.register(name, enabled).await;
@@ -130,6 +135,13 @@ impl AdapterCoordinator {
         debug!("Registered adapter: {} (enabled: {})", prefix, enabled);
     }
 
+    /// Remove a registered adapter (stop it first)
+    pub async fn unregister(&self, prefix: &str) {
+        if self.adapters.write().await.remove(prefix).is_some() {
+            debug!("Unregistered adapter: {}", prefix);
+        }
+    }
+
     /// Start an adapter with the given spawn function
     /// The spawn function receives (bus, cancel_token) and should spawn the adapter task
     pub async fn start_adapter<F, Fut>(&self, prefix: &str, spawn_fn: F) -> Result<()>
@@ -487,4 +499,16 @@ mod tests {
         assert!(!status["a"].running);
         assert!(!status["b"].enabled);
     }
+
+    #[tokio::test]
+    async fn test_unregister_removes_adapter() {
+        let bus = create_bus();
+        let coord = AdapterCoordinator::new(bus);
+
+        coord.register("external:test", true).await;
+        coord.unregister("external:test").await;
+
+        assert!(coord.adapter_status().await.is_empty());
+        assert!(!coord.is_enabled("external:test").await);
+    }
 }
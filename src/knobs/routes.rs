@@ -18,7 +18,9 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::api::AppState;
+use crate::adapters::lms::LmsAdapter;
+use crate::api::{delegated_control, AppState};
+use crate::bus::VolumeScale;
 use crate::knobs::image::placeholder_svg;
 use crate::knobs::store::{KnobConfigUpdate, KnobStatusUpdate};
 
@@ -125,22 +127,16 @@ pub async fn get_all_zones_internal(state: &AppState) -> Vec<ZoneInfo> {
         }
     }
 
-    // LMS players (prefixed with lms:)
+    // LMS players from every server (prefixed with lms:, sync groups listed
+    // once under the master)
     if adapters.lms {
-        for p in state.lms.get_cached_players().await {
-            let zone_id = format!("lms:{}", p.playerid);
+        for z in state.lms_instances.get_cached_zones().await {
             zones.push(ZoneInfo {
-                dsp: get_dsp(&zone_id),
-                zone_id,
-                zone_name: p.name,
+                dsp: get_dsp(&z.zone_id),
+                zone_id: z.zone_id,
+                zone_name: z.zone_name,
                 source: "lms".to_string(),
-                state: if p.mode == "play" {
-                    "playing".to_string()
-                } else if p.mode == "pause" {
-                    "paused".to_string()
-                } else {
-                    "stopped".to_string()
-                },
+                state: z.state.to_string(),
             });
         }
     }
@@ -173,6 +169,69 @@ pub async fn get_all_zones_internal(state: &AppState) -> Vec<ZoneInfo> {
         }
     }
 
+    // AirPlay receivers (prefixed with airplay:)
+    if adapters.airplay {
+        for z in state.airplay.get_zones().await {
+            let zone_id = format!("airplay:{}", z.zone_id);
+            zones.push(ZoneInfo {
+                dsp: get_dsp(&zone_id),
+                zone_id,
+                zone_name: z.zone_name,
+                source: "airplay".to_string(),
+                state: z.state,
+            });
+        }
+    }
+
+    // Spotify Connect receivers (prefixed with spotify:)
+    if adapters.spotify {
+        for z in state.spotify.get_zones().await {
+            let zone_id = format!("spotify:{}", z.zone_id);
+            zones.push(ZoneInfo {
+                dsp: get_dsp(&zone_id),
+                zone_id,
+                zone_name: z.zone_name,
+                source: "spotify".to_string(),
+                state: z.state,
+            });
+        }
+    }
+
+    // HQPlayer instances playing on their own (prefixed with hqplayer:)
+    for z in state.hqp_instances.get_zones().await {
+        zones.push(ZoneInfo {
+            dsp: None,
+            zone_id: z.zone_id,
+            zone_name: z.zone_name,
+            source: "hqplayer".to_string(),
+            state: z.state.to_string(),
+        });
+    }
+
+    // Zones federated from remote bridges (prefixed with remote:<bridge>:)
+    if adapters.remote {
+        for z in state.remote.get_zones().await {
+            zones.push(ZoneInfo {
+                dsp: get_dsp(&z.zone_id),
+                zone_id: z.zone_id,
+                zone_name: z.zone_name,
+                source: "remote".to_string(),
+                state: z.state,
+            });
+        }
+    }
+
+    // External adapter zones (already prefixed with external:<adapter>:)
+    for z in state.external_adapters.get_zones().await {
+        zones.push(ZoneInfo {
+            dsp: get_dsp(&z.zone_id),
+            zone_id: z.zone_id,
+            zone_name: z.zone_name,
+            source: z.source,
+            state: z.state.to_string(),
+        });
+    }
+
     zones
 }
 
@@ -267,11 +326,10 @@ pub async fn knob_now_playing_handler(
     let zone_infos = get_zone_infos(&state).await;
 
     // Route based on zone_id prefix
-    if zone_id.starts_with("lms:") {
-        // LMS player
-        let player_id = zone_id.trim_start_matches("lms:");
-        let player = match state.lms.get_cached_player(player_id).await {
-            Some(p) => p,
+    let Json(mut response) = if zone_id.starts_with("lms:") {
+        // LMS player (on whichever server the zone ID names)
+        let (lms, player) = match state.lms_instances.get_cached_player(&zone_id).await {
+            Some(found) => found,
             None => {
                 return Err((
                     StatusCode::NOT_FOUND,
@@ -294,6 +352,11 @@ pub async fn knob_now_playing_handler(
 
         // Node.js format: line1/line2/line3/is_playing with volume info
         let is_playing = state_str == "playing";
+        // Synced players report the group volume
+        let volume = match lms.get_sync_group(&player.playerid).await {
+            Some(group) => group.volume,
+            None => player.volume,
+        };
 
         Ok(Json(NowPlayingResponse {
             zone_id: zone_id.clone(),
@@ -309,7 +372,7 @@ pub async fn knob_now_playing_handler(
                 Some(player.album)
             },
             is_playing,
-            volume: Some(player.volume as f64),
+            volume: Some(volume as f64),
             volume_type: Some("number".to_string()),
             volume_min: Some(0.0),
             volume_max: Some(100.0),
@@ -431,6 +494,253 @@ pub async fn knob_now_playing_handler(
             zones: zone_infos,
             config_sha,
         }))
+    } else if zone_id.starts_with("airplay:") {
+        // AirPlay receiver - zone_id_part is the receiver name
+        let zone_id_part = zone_id.trim_start_matches("airplay:");
+        let np = match state.airplay.get_now_playing(zone_id_part).await {
+            Some(np) => np,
+            None => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({
+                        "error": "zone not found",
+                        "error_code": "ZONE_NOT_FOUND",
+                        "zones": zone_infos
+                    })),
+                ));
+            }
+        };
+        // Transport only works through the shairport-sync remote interface
+        let controllable = state
+            .airplay
+            .get_zones()
+            .await
+            .iter()
+            .any(|z| z.zone_id == zone_id_part && z.is_controllable);
+
+        Ok(Json(NowPlayingResponse {
+            zone_id: zone_id.clone(),
+            line1: np.line1,
+            line2: np.line2,
+            line3: if np.line3.is_empty() {
+                None
+            } else {
+                Some(np.line3)
+            },
+            is_playing: np.is_playing,
+            volume: np.volume.map(|v| v as f64),
+            volume_type: Some("db".to_string()),
+            volume_min: Some(np.volume_min as f64),
+            volume_max: Some(np.volume_max as f64),
+            volume_step: Some(1.0),
+            image_url: Some(image_url),
+            image_key: np.image_key,
+            seek_position: np.seek_position,
+            length: np.length,
+            is_play_allowed: controllable && !np.is_playing,
+            is_pause_allowed: controllable && np.is_playing,
+            is_next_allowed: controllable,
+            is_previous_allowed: controllable,
+            zones: zone_infos,
+            config_sha,
+        }))
+    } else if zone_id.starts_with("spotify:") {
+        // Spotify Connect receiver - zone_id_part is the receiver name
+        let zone_id_part = zone_id.trim_start_matches("spotify:");
+        let np = match state.spotify.get_now_playing(zone_id_part).await {
+            Some(np) => np,
+            None => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({
+                        "error": "zone not found",
+                        "error_code": "ZONE_NOT_FOUND",
+                        "zones": zone_infos
+                    })),
+                ));
+            }
+        };
+        // Only go-librespot receivers accept commands
+        let controllable = state
+            .spotify
+            .get_zones()
+            .await
+            .iter()
+            .any(|z| z.zone_id == zone_id_part && z.is_controllable);
+
+        Ok(Json(NowPlayingResponse {
+            zone_id: zone_id.clone(),
+            line1: np.line1,
+            line2: np.line2,
+            line3: if np.line3.is_empty() {
+                None
+            } else {
+                Some(np.line3)
+            },
+            is_playing: np.is_playing,
+            volume: np.volume.map(|v| v as f64),
+            volume_type: Some("number".to_string()),
+            volume_min: Some(np.volume_min as f64),
+            volume_max: Some(np.volume_max as f64),
+            volume_step: Some(1.0),
+            image_url: Some(image_url),
+            image_key: np.image_key,
+            seek_position: np.seek_position,
+            length: np.length,
+            is_play_allowed: controllable && !np.is_playing,
+            is_pause_allowed: controllable && np.is_playing,
+            is_next_allowed: controllable,
+            is_previous_allowed: controllable,
+            zones: zone_infos,
+            config_sha,
+        }))
+    } else if let Some(instance) = zone_id.strip_prefix("hqplayer:") {
+        // Standalone HQPlayer - zone_id_part is the instance name
+        let zone = match state.hqp_instances.get(instance).await {
+            Some(adapter) => adapter.get_zone().await,
+            None => None,
+        };
+        let Some(zone) = zone else {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "error": "zone not found",
+                    "error_code": "ZONE_NOT_FOUND",
+                    "zones": zone_infos
+                })),
+            ));
+        };
+        let np = zone.now_playing.as_ref();
+        let vol = zone.volume_control.as_ref();
+        let is_playing = zone.state == crate::bus::PlaybackState::Playing;
+
+        Ok(Json(NowPlayingResponse {
+            zone_id: zone.zone_id.clone(),
+            line1: np
+                .map(|n| n.title.clone())
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| "Idle".to_string()),
+            line2: np.map(|n| n.artist.clone()).unwrap_or_default(),
+            line3: np.and_then(|n| {
+                if n.album.is_empty() {
+                    None
+                } else {
+                    Some(n.album.clone())
+                }
+            }),
+            is_playing,
+            volume: vol.map(|v| v.value as f64),
+            volume_type: vol.map(|_| "db".to_string()),
+            volume_min: vol.map(|v| v.min as f64),
+            volume_max: vol.map(|v| v.max as f64),
+            volume_step: Some(vol.map(|v| v.step as f64).unwrap_or(1.0)),
+            image_url: Some(image_url),
+            image_key: None,
+            seek_position: np.and_then(|n| n.seek_position).map(|p| p as i64),
+            length: np.and_then(|n| n.duration).map(|d| d as u32),
+            is_play_allowed: !is_playing,
+            is_pause_allowed: is_playing,
+            is_next_allowed: true,
+            is_previous_allowed: true,
+            zones: zone_infos,
+            config_sha,
+        }))
+    } else if zone_id.starts_with("remote:") {
+        // Remote bridge zone - proxied to the owning bridge
+        if !state.remote.has_zone(&zone_id).await {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "error": "zone not found",
+                    "error_code": "ZONE_NOT_FOUND",
+                    "zones": zone_infos
+                })),
+            ));
+        }
+        let np = state.remote.get_now_playing(&zone_id).await.map_err(|e| {
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        })?;
+
+        Ok(Json(NowPlayingResponse {
+            zone_id: zone_id.clone(),
+            line1: np.line1,
+            line2: np.line2,
+            line3: np.line3,
+            is_playing: np.is_playing,
+            volume: np.volume,
+            volume_type: np.volume_type,
+            volume_min: np.volume_min,
+            volume_max: np.volume_max,
+            volume_step: np.volume_step,
+            image_url: Some(image_url),
+            image_key: np.image_key,
+            seek_position: np.seek_position,
+            length: np.length,
+            is_play_allowed: np.is_play_allowed,
+            is_pause_allowed: np.is_pause_allowed,
+            is_next_allowed: np.is_next_allowed,
+            is_previous_allowed: np.is_previous_allowed,
+            zones: zone_infos,
+            config_sha,
+        }))
+    } else if zone_id.starts_with("external:") {
+        // External adapter zone - full zone_id is the lookup key
+        let zone = match state.external_adapters.get_zone(&zone_id).await {
+            Some(z) => z,
+            None => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({
+                        "error": "zone not found",
+                        "error_code": "ZONE_NOT_FOUND",
+                        "zones": zone_infos
+                    })),
+                ));
+            }
+        };
+        let np = zone.now_playing.as_ref();
+        let vol = zone.volume_control.as_ref();
+        let is_playing = zone.state == crate::bus::PlaybackState::Playing;
+
+        Ok(Json(NowPlayingResponse {
+            zone_id: zone.zone_id.clone(),
+            line1: np
+                .map(|n| n.title.clone())
+                .unwrap_or_else(|| zone.zone_name.clone()),
+            line2: np.map(|n| n.artist.clone()).unwrap_or_default(),
+            line3: np.and_then(|n| {
+                if n.album.is_empty() {
+                    None
+                } else {
+                    Some(n.album.clone())
+                }
+            }),
+            is_playing,
+            volume: vol.map(|v| v.value as f64),
+            volume_type: vol.map(|v| {
+                if v.scale == VolumeScale::Decibel {
+                    "db".to_string()
+                } else {
+                    "number".to_string()
+                }
+            }),
+            volume_min: vol.map(|v| v.min as f64),
+            volume_max: vol.map(|v| v.max as f64),
+            volume_step: Some(vol.map(|v| v.step as f64).unwrap_or(1.0)),
+            image_url: Some(image_url),
+            image_key: np.and_then(|n| n.image_key.clone()),
+            seek_position: np.and_then(|n| n.seek_position).map(|p| p as i64),
+            length: np.and_then(|n| n.duration).map(|d| d as u32),
+            is_play_allowed: zone.is_controllable && !is_playing,
+            is_pause_allowed: zone.is_controllable && is_playing,
+            is_next_allowed: zone.is_controllable,
+            is_previous_allowed: zone.is_controllable,
+            zones: zone_infos,
+            config_sha,
+        }))
     } else {
         // Roon zone (or legacy zone_id without prefix)
         let roon_zone_id = if zone_id.starts_with("roon:") {
@@ -495,7 +805,28 @@ pub async fn knob_now_playing_handler(
             zones: zone_infos,
             config_sha,
         }))
+    }?;
+
+    // Zones with a volume delegate (or volume routed to a linked HQPlayer)
+    // report the delegate's volume and range. Delegated volume is cached by
+    // the aggregator so polling never queries the device.
+    let zone_key = delegate_zone_key(&zone_id);
+    if let Some(vc) = state.aggregator.get_volume_delegate(&zone_key).await {
+        response.volume = Some(vc.value as f64);
+        response.volume_type = Some(
+            if vc.scale == VolumeScale::Decibel {
+                "db"
+            } else {
+                "number"
+            }
+            .to_string(),
+        );
+        response.volume_min = Some(vc.min as f64);
+        response.volume_max = Some(vc.max as f64);
+        response.volume_step = Some(vc.step as f64);
     }
+
+    Ok(Json(response))
 }
 
 /// Query params for image endpoint
@@ -552,8 +883,7 @@ pub async fn knob_image_handler(
     // Route based on zone_id prefix
     if params.zone_id.starts_with("lms:") {
         // LMS zone
-        let player_id = params.zone_id.trim_start_matches("lms:");
-        let player = match state.lms.get_cached_player(player_id).await {
+        let (lms, player) = match state.lms_instances.get_cached_player(&params.zone_id).await {
             Some(p) => p,
             None => {
                 let svg = placeholder_svg(target_width, target_height);
@@ -583,9 +913,8 @@ pub async fn knob_image_handler(
             }
         };
 
-        // Fetch artwork from LMS
-        match state
-            .lms
+        // Fetch artwork from the zone's LMS server
+        match lms
             .get_artwork(&image_key, Some(target_width), Some(target_height))
             .await
         {
@@ -647,6 +976,51 @@ pub async fn knob_image_handler(
                     .unwrap()
             }
         }
+    } else if params.zone_id.starts_with("airplay:") {
+        // AirPlay receiver - cover art arrives with the metadata
+        let name = params.zone_id.trim_start_matches("airplay:");
+        match state.airplay.get_image(name).await {
+            Some(image) => maybe_convert(image.content_type, image.data),
+            None => {
+                let svg = placeholder_svg(target_width, target_height);
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "image/svg+xml")
+                    .body(Body::from(svg))
+                    .unwrap()
+            }
+        }
+    } else if params.zone_id.starts_with("remote:") {
+        // Remote bridge zone - artwork from the owning bridge
+        match state
+            .remote
+            .get_image(&params.zone_id, target_width, target_height)
+            .await
+        {
+            Ok((content_type, body)) => maybe_convert(content_type, body),
+            Err(_) => {
+                let svg = placeholder_svg(target_width, target_height);
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "image/svg+xml")
+                    .body(Body::from(svg))
+                    .unwrap()
+            }
+        }
+    } else if params.zone_id.starts_with("spotify:") {
+        // Spotify Connect receiver - cover art from the Spotify CDN
+        let name = params.zone_id.trim_start_matches("spotify:");
+        match state.spotify.get_image(name).await {
+            Ok((content_type, body)) => maybe_convert(content_type, body),
+            Err(_) => {
+                let svg = placeholder_svg(target_width, target_height);
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "image/svg+xml")
+                    .body(Body::from(svg))
+                    .unwrap()
+            }
+        }
     } else {
         // Unknown zone type - return placeholder
         let svg = placeholder_svg(target_width, target_height);
@@ -672,19 +1046,103 @@ pub async fn knob_control_handler(
     _headers: HeaderMap,
     Json(req): Json<KnobControlRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    // Volume/mute for zones with a volume delegate (or volume routed to a
+    // linked HQPlayer) go there instead of the source
+    let zone_key = delegate_zone_key(&req.zone_id);
+    let value = req
+        .value
+        .as_ref()
+        .and_then(|v| v.as_f64())
+        .map(|v| v as f32);
+    if let Some(result) = delegated_control(&state, &zone_key, &req.action, value).await {
+        return match result {
+            Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )),
+        };
+    }
+
+    // Radio favorites play on any zone, whatever its source
+    if req.action == "radio" {
+        let Some(favorite) = req.value.as_ref().and_then(|v| v.as_str()) else {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "radio needs a favorite ID or name"})),
+            ));
+        };
+        return match state.radio_favorites.play(&req.zone_id, favorite).await {
+            Ok(_) => Ok(Json(serde_json::json!({"ok": true}))),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )),
+        };
+    }
+
+    // A/B/X switching and guessing act on the running session, whatever zone
+    // the knob is on
+    if req.action == "abx" || req.action == "abx_guess" {
+        let Some(label) = req.value.as_ref().and_then(|v| v.as_str()) else {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "abx needs an option label"})),
+            ));
+        };
+        let result = if req.action == "abx" {
+            state.abx.select(label).await
+        } else {
+            state.abx.guess(label).await
+        };
+        return match result {
+            Ok(status) => Ok(Json(serde_json::json!({"ok": true, "abx": status}))),
+            Err(e) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )),
+        };
+    }
+
     // Route based on zone_id prefix
-    if req.zone_id.starts_with("lms:") {
-        // LMS player control
-        let player_id = req.zone_id.trim_start_matches("lms:");
-        return control_lms(&state, player_id, &req.action, req.value.as_ref()).await;
+    if let Some((lms, player_id)) = state.lms_instances.resolve_zone(&req.zone_id).await {
+        // LMS player control (on whichever server the zone ID names)
+        return control_lms(&lms, &player_id, &req.action, req.value.as_ref()).await;
     } else if req.zone_id.starts_with("openhome:") {
         // OpenHome zone control
         let udn = req.zone_id.trim_start_matches("openhome:");
-        return control_openhome(&state, udn, &req.action).await;
+        return control_openhome(&state, udn, &req.action, req.value.as_ref()).await;
     } else if req.zone_id.starts_with("upnp:") {
         // UPnP zone control
         let udn = req.zone_id.trim_start_matches("upnp:");
         return control_upnp(&state, udn, &req.action).await;
+    } else if req.zone_id.starts_with("airplay:") {
+        // AirPlay receiver control (via shairport-sync remote)
+        let name = req.zone_id.trim_start_matches("airplay:");
+        return control_airplay(&state, name, &req.action).await;
+    } else if req.zone_id.starts_with("spotify:") {
+        // Spotify Connect receiver control (go-librespot only)
+        let name = req.zone_id.trim_start_matches("spotify:");
+        return control_spotify(&state, name, &req.action, req.value.as_ref()).await;
+    } else if let Some(instance) = req.zone_id.strip_prefix("hqplayer:") {
+        // Standalone HQPlayer control
+        return control_hqplayer(&state, instance, &req.action, req.value.as_ref()).await;
+    } else if req.zone_id.starts_with("remote:") {
+        // Remote bridge zone control (proxied to the owning bridge)
+        return match state
+            .remote
+            .control(&req.zone_id, &req.action, req.value.clone())
+            .await
+        {
+            Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+            Err(e) => Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )),
+        };
+    } else if req.zone_id.starts_with("external:") {
+        // External adapter zone control
+        return control_external(&state, &req.zone_id, &req.action, req.value.as_ref()).await;
     }
 
     // Roon zone (or legacy zone_id without prefix)
@@ -796,7 +1254,7 @@ async fn control_roon(
 
 /// Control LMS player
 async fn control_lms(
-    state: &AppState,
+    lms: &LmsAdapter,
     player_id: &str,
     action: &str,
     value: Option<&serde_json::Value>,
@@ -811,9 +1269,7 @@ async fn control_lms(
         "vol_up" | "volume_up" => {
             // Use as_f64() which handles both JSON integers and floats
             let step = value.and_then(|v| v.as_f64()).unwrap_or(5.0) as i32;
-            state
-                .lms
-                .change_volume(player_id, step, true)
+            lms.change_zone_volume(player_id, step, true)
                 .await
                 .map_err(|e| {
                     (
@@ -826,9 +1282,7 @@ async fn control_lms(
         "vol_down" | "volume_down" => {
             // Use as_f64() which handles both JSON integers and floats
             let step = value.and_then(|v| v.as_f64()).unwrap_or(5.0) as i32;
-            state
-                .lms
-                .change_volume(player_id, -step, true)
+            lms.change_zone_volume(player_id, -step, true)
                 .await
                 .map_err(|e| {
                     (
@@ -841,9 +1295,7 @@ async fn control_lms(
         "vol_abs" | "volume" => {
             // Use as_f64() which handles both JSON integers and floats
             let vol = value.and_then(|v| v.as_f64()).unwrap_or(50.0) as i32;
-            state
-                .lms
-                .change_volume(player_id, vol, false)
+            lms.change_zone_volume(player_id, vol, false)
                 .await
                 .map_err(|e| {
                     (
@@ -861,7 +1313,7 @@ async fn control_lms(
         }
     };
 
-    match state.lms.control(player_id, lms_action, None).await {
+    match lms.control(player_id, lms_action, None).await {
         Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -875,7 +1327,41 @@ async fn control_openhome(
     state: &AppState,
     zone_id: &str,
     action: &str,
+    value: Option<&serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    // Source by name or index (e.g. "TV"), standby on/off or toggle
+    let product = match action {
+        "source" => {
+            let source = match value {
+                Some(serde_json::Value::String(name)) => name.clone(),
+                Some(serde_json::Value::Number(index)) => index.to_string(),
+                _ => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({"error": "source needs a name or index"})),
+                    ));
+                }
+            };
+            Some(state.openhome.set_source(zone_id, &source).await)
+        }
+        "standby" => Some(
+            state
+                .openhome
+                .set_standby(zone_id, value.and_then(|v| v.as_bool()))
+                .await,
+        ),
+        _ => None,
+    };
+    if let Some(result) = product {
+        return match result {
+            Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )),
+        };
+    }
+
     let oh_action = match action {
         "play" => "play",
         "pause" => "pause",
@@ -930,6 +1416,183 @@ async fn control_upnp(
     }
 }
 
+/// Control AirPlay receiver
+async fn control_airplay(
+    state: &AppState,
+    name: &str,
+    action: &str,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    if crate::adapters::airplay::remote_command(action).is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Unknown action: {}", action)})),
+        ));
+    }
+
+    match state.airplay.control(name, action).await {
+        Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )),
+    }
+}
+
+/// Control Spotify Connect receiver
+async fn control_spotify(
+    state: &AppState,
+    name: &str,
+    action: &str,
+    value: Option<&serde_json::Value>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let spotify_action = match action {
+        "play" => "play",
+        "pause" => "pause",
+        "play_pause" | "playpause" => "play_pause",
+        "next" => "next",
+        "previous" | "prev" => "previous",
+        "vol_up" | "volume_up" => "vol_up",
+        "vol_down" | "volume_down" => "vol_down",
+        "vol_abs" | "volume" => "vol_abs",
+        "seek" => "seek",
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Unknown action: {}", action)})),
+            ));
+        }
+    };
+
+    // Use as_f64() which handles both JSON integers and floats
+    let value = value.and_then(|v| v.as_f64());
+    match state.spotify.control(name, spotify_action, value).await {
+        Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )),
+    }
+}
+
+/// Control standalone HQPlayer instance (volume in dB)
+async fn control_hqplayer(
+    state: &AppState,
+    instance: &str,
+    action: &str,
+    value: Option<&serde_json::Value>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let Some(hqp) = state.hqp_instances.get(instance).await else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Unknown HQPlayer instance: {}", instance)})),
+        ));
+    };
+
+    let result = match action {
+        "play" => hqp.play().await,
+        "pause" => hqp.pause().await,
+        "play_pause" | "playpause" => match hqp.get_zone().await {
+            Some(zone) if zone.state == crate::bus::PlaybackState::Playing => hqp.pause().await,
+            _ => hqp.play().await,
+        },
+        "next" => hqp.next().await,
+        "previous" | "prev" => hqp.previous().await,
+        "stop" => hqp.stop().await,
+        // HQPlayer steps by its own VolumeRange step
+        "vol_up" | "volume_up" => hqp.volume_up().await,
+        "vol_down" | "volume_down" => hqp.volume_down().await,
+        "mute_toggle" | "toggle_mute" => hqp.volume_mute().await,
+        "vol_abs" | "volume" => {
+            // Use as_f64() which handles both JSON integers and floats
+            let Some(db) = value.and_then(|v| v.as_f64()) else {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "volume needs a dB value"})),
+                ));
+            };
+            hqp.set_volume(db.round() as i32).await
+        }
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Unknown action: {}", action)})),
+            ));
+        }
+    };
+
+    match result {
+        Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )),
+    }
+}
+
+/// Control external adapter zone
+async fn control_external(
+    state: &AppState,
+    zone_id: &str,
+    action: &str,
+    value: Option<&serde_json::Value>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    use crate::bus::Command;
+
+    // Use as_f64() which handles both JSON integers and floats
+    let value = value.and_then(|v| v.as_f64());
+    let command = match action {
+        "play" => Command::Play,
+        "pause" => Command::Pause,
+        "play_pause" | "playpause" => Command::PlayPause,
+        "stop" => Command::Stop,
+        "next" => Command::Next,
+        "previous" | "prev" => Command::Previous,
+        "vol_up" | "volume_up" => Command::VolumeRelative {
+            delta: value.unwrap_or(1.0) as f32,
+            output_id: None,
+        },
+        "vol_down" | "volume_down" => Command::VolumeRelative {
+            delta: -value.unwrap_or(1.0) as f32,
+            output_id: None,
+        },
+        "vol_abs" | "volume" => match value {
+            Some(v) => Command::VolumeAbsolute {
+                value: v as f32,
+                output_id: None,
+            },
+            None => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "vol_abs requires a value"})),
+                ));
+            }
+        },
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Unknown action: {}", action)})),
+            ));
+        }
+    };
+
+    match state.external_adapters.send_command(zone_id, command).await {
+        Ok(()) => Ok(Json(serde_json::json!({"ok": true}))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )),
+    }
+}
+
+/// Zone key used for volume delegation (legacy unprefixed IDs are Roon zones)
+fn delegate_zone_key(zone_id: &str) -> String {
+    if zone_id.contains(':') {
+        zone_id.to_string()
+    } else {
+        format!("roon:{}", zone_id)
+    }
+}
+
 /// Helper to get first output ID for a Roon zone (for volume control)
 async fn get_first_output_id(state: &AppState, zone_id: &str) -> Option<String> {
     let zone = state.roon.get_zone(zone_id).await?;
@@ -6,6 +6,7 @@
 //! - Roon audio system control
 //! - HQPlayer upsampling engine control
 //! - Logitech Media Server (LMS) control
+//! - Amplifier/preamp volume control (Denon/Marantz, Yamaha, RS-232 serial)
 //! - MQTT integration for Home Assistant
 //! - Server-Sent Events for real-time updates
 //! - Web UI (Dioxus + Tailwind CSS + DioxusLabs components)
@@ -35,3 +36,5 @@ pub mod firmware;
 pub mod knobs;
 #[cfg(feature = "server")]
 pub mod mdns;
+#[cfg(feature = "server")]
+pub mod volume;
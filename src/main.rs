@@ -6,7 +6,7 @@
 #[cfg(feature = "server")]
 mod server {
     use unified_hifi_control::{
-        adapters, aggregator, api, app, bus, config, coordinator, firmware, knobs, mdns,
+        adapters, aggregator, api, app, bus, config, coordinator, firmware, knobs, mdns, volume,
     };
 
     // Import Startable trait for adapter lifecycle methods
@@ -166,11 +166,17 @@ mod server {
             }
         }
 
+        // Keep instances refreshed so pipeline changes made in HQPlayer are published
+        hqp_instances.start_monitor();
+        hqp_instances.clone().start_discovery();
+
         // HQP zone link service
         let hqp_zone_links = Arc::new(adapters::hqplayer::HqpZoneLinkService::new(
             hqp_instances.clone(),
         ));
         hqp_zone_links.auto_correct_links().await;
+        hqp_zone_links.clone().start_format_rules();
+        hqp_zone_links.clone().start_failover();
         let link_count = hqp_zone_links.get_links().await.len();
         if link_count > 0 {
             tracing::info!("HQPlayer: {} zone link(s) active", link_count);
@@ -187,6 +193,15 @@ mod server {
             )
             .await;
         }
+        let lms_instances = Arc::new(adapters::lms::LmsInstanceManager::new(
+            bus.clone(),
+            lms.clone(),
+        ));
+        lms_instances.load_from_config().await;
+        let lms_instance_count = lms_instances.list_instances().await.len();
+        if lms_instance_count > 1 {
+            tracing::info!("LMS: {} server instances configured", lms_instance_count);
+        }
 
         // OpenHome adapter
         let openhome = Arc::new(adapters::openhome::OpenHomeAdapter::new(bus.clone()));
@@ -194,16 +209,51 @@ mod server {
         // UPnP adapter
         let upnp = Arc::new(adapters::upnp::UPnPAdapter::new(bus.clone()));
 
+        // AirPlay adapter (shairport-sync metadata)
+        let airplay = Arc::new(adapters::airplay::AirPlayAdapter::new(bus.clone()));
+
+        // Spotify Connect adapter (librespot / go-librespot)
+        let spotify = Arc::new(adapters::spotify::SpotifyAdapter::new(bus.clone()));
+
+        // Remote adapter (zones federated from other bridges)
+        let remote = Arc::new(adapters::remote::RemoteAdapter::new(bus.clone()));
+
+        // External adapters (supervised subprocesses speaking JSON lines)
+        let external_adapters = Arc::new(adapters::external::ExternalAdapterManager::new(
+            coord.clone(),
+        ));
+        external_adapters.load_from_config().await;
+
+        // Volume outputs (network amplifiers/preamps)
+        let volume_outputs = Arc::new(volume::VolumeOutputManager::new(bus.clone()));
+        volume_outputs.load_from_config().await;
+        let output_count = volume_outputs.list_outputs().await.len();
+        if output_count > 0 {
+            tracing::info!("Volume outputs: {} configured", output_count);
+        }
+        let volume_delegates = Arc::new(volume::VolumeDelegationService::new(
+            volume_outputs.clone(),
+            bus.clone(),
+        ));
+
         // =========================================================================
         // Start enabled adapters (single codepath using coordinator)
         // =========================================================================
 
         // Build list of startable adapters
-        let startable_adapters: Vec<Arc<dyn adapters::Startable>> =
-            vec![roon.clone(), lms.clone(), openhome.clone(), upnp.clone()];
+        let startable_adapters: Vec<Arc<dyn adapters::Startable>> = vec![
+            roon.clone(),
+            lms_instances.clone(),
+            openhome.clone(),
+            upnp.clone(),
+            airplay.clone(),
+            spotify.clone(),
+            remote.clone(),
+        ];
 
         // Single loop to start all enabled adapters
         coord.start_all_enabled(&startable_adapters).await;
+        external_adapters.start_all().await;
 
         // Initialize ZoneAggregator for unified zone state
         let zone_aggregator = Arc::new(aggregator::ZoneAggregator::new(bus.clone()));
@@ -213,6 +263,14 @@ mod server {
         });
         tracing::info!("ZoneAggregator started");
 
+        // Publish delegated zone volume once outputs have been queried
+        let delegates_for_spawn = volume_delegates.clone();
+        let hqp_links_for_spawn = hqp_zone_links.clone();
+        tokio::spawn(async move {
+            delegates_for_spawn.refresh().await;
+            hqp_links_for_spawn.refresh_volume().await;
+        });
+
         // Initialize Knob device store
         let data_dir = config::get_data_dir();
         let knob_store = knobs::KnobStore::new(data_dir);
@@ -228,11 +286,18 @@ mod server {
         let state = api::AppState::new(
             roon,
             hqplayer,
-            hqp_instances,
+            hqp_instances.clone(),
             hqp_zone_links,
             lms.clone(),
+            lms_instances.clone(),
             openhome.clone(),
             upnp.clone(),
+            airplay.clone(),
+            spotify.clone(),
+            remote.clone(),
+            external_adapters.clone(),
+            volume_outputs,
+            volume_delegates,
             knob_store,
             bus.clone(),
             zone_aggregator,
@@ -310,16 +375,56 @@ mod server {
                 "/hqp/instances/{name}/matrix/profile",
                 post(api::hqp_instance_set_matrix_profile_handler),
             )
+            // HQPlayer playlist and library (native TCP protocol)
+            .route(
+                "/hqp/instances/{name}/playlist",
+                get(api::hqp_instance_playlist_handler),
+            )
+            .route(
+                "/hqp/instances/{name}/playlist",
+                post(api::hqp_instance_playlist_edit_handler),
+            )
+            .route(
+                "/hqp/instances/{name}/library",
+                get(api::hqp_instance_library_handler),
+            )
+            .route(
+                "/hqp/instances/{name}/library/load",
+                post(api::hqp_instance_library_load_handler),
+            )
+            // HQPlayer pipeline presets
+            .route("/hqp/presets", get(api::hqp_presets_handler))
+            .route("/hqp/presets", post(api::hqp_save_preset_handler))
+            .route(
+                "/hqp/presets/{name}",
+                delete(api::hqp_delete_preset_handler),
+            )
+            .route("/hqp/presets/apply", post(api::hqp_apply_preset_handler))
+            .route("/hqp/pipeline/copy", post(api::hqp_copy_pipeline_handler))
+            .route("/hqp/abx", get(api::hqp_abx_status_handler))
+            .route("/hqp/abx/start", post(api::hqp_abx_start_handler))
+            .route("/hqp/abx/select", post(api::hqp_abx_select_handler))
+            .route("/hqp/abx/guess", post(api::hqp_abx_guess_handler))
+            .route("/hqp/abx/finish", post(api::hqp_abx_finish_handler))
             // HQPlayer zone linking routes
             .route("/hqp/zones/links", get(api::hqp_zone_links_handler))
             .route("/hqp/zones/link", post(api::hqp_zone_link_handler))
             .route("/hqp/zones/unlink", post(api::hqp_zone_unlink_handler))
+            .route(
+                "/hqp/zones/fallbacks",
+                post(api::hqp_zone_fallbacks_handler),
+            )
+            .route("/hqp/zones/volume", post(api::hqp_zone_volume_handler))
+            .route("/hqp/zones/rules", get(api::hqp_zone_rules_handler))
+            .route("/hqp/zones/rules", post(api::hqp_zone_set_rules_handler))
             .route(
                 "/hqp/zones/{zone_id}/pipeline",
                 get(api::hqp_zone_pipeline_handler),
             )
             // HQPlayer network discovery
             .route("/hqp/discover", get(api::hqp_discover_handler))
+            .route("/hqp/discovery", get(api::hqp_discovery_handler))
+            .route("/hqp/discovery", post(api::hqp_discovery_settings_handler))
             // LMS routes
             .route("/lms/status", get(api::lms_status_handler))
             .route("/lms/config", get(api::lms_config_handler))
@@ -328,6 +433,20 @@ mod server {
             .route("/lms/player/{player_id}", get(api::lms_player_handler))
             .route("/lms/control", post(api::lms_control_handler))
             .route("/lms/volume", post(api::lms_volume_handler))
+            .route("/lms/groups", get(api::lms_groups_handler))
+            .route("/lms/sync", post(api::lms_sync_handler))
+            .route("/lms/unsync", post(api::lms_unsync_handler))
+            .route("/lms/browse", get(api::lms_browse_handler))
+            .route("/lms/play", post(api::lms_play_handler))
+            // LMS network discovery
+            .route("/lms/discover", get(api::lms_discover_handler))
+            // LMS multi-instance routes (other /lms routes take an `instance` selector)
+            .route("/lms/instances", get(api::lms_instances_handler))
+            .route("/lms/instances", post(api::lms_add_instance_handler))
+            .route(
+                "/lms/instances/{name}",
+                delete(api::lms_remove_instance_handler),
+            )
             // OpenHome routes
             .route("/openhome/status", get(api::openhome_status_handler))
             .route("/openhome/zones", get(api::openhome_zones_handler))
@@ -336,6 +455,17 @@ mod server {
                 get(api::openhome_now_playing_handler),
             )
             .route("/openhome/control", post(api::openhome_control_handler))
+            .route("/openhome/queue", get(api::openhome_queue_handler))
+            .route("/openhome/queue", post(api::openhome_queue_insert_handler))
+            .route("/openhome/queue", delete(api::openhome_clear_queue_handler))
+            .route("/openhome/queue/play", post(api::openhome_seek_handler))
+            .route("/openhome/queue/remove", post(api::openhome_remove_handler))
+            .route("/openhome/queue/mode", post(api::openhome_mode_handler))
+            .route("/openhome/sources", get(api::openhome_sources_handler))
+            .route("/openhome/source", post(api::openhome_source_handler))
+            .route("/openhome/standby", post(api::openhome_standby_handler))
+            .route("/openhome/radio", get(api::openhome_radio_handler))
+            .route("/openhome/radio", post(api::openhome_channel_handler))
             // UPnP routes
             .route("/upnp/status", get(api::upnp_status_handler))
             .route("/upnp/zones", get(api::upnp_zones_handler))
@@ -344,6 +474,85 @@ mod server {
                 get(api::upnp_now_playing_handler),
             )
             .route("/upnp/control", post(api::upnp_control_handler))
+            .route("/upnp/servers", get(api::upnp_servers_handler))
+            .route("/upnp/servers", post(api::upnp_add_server_handler))
+            .route("/upnp/browse", get(api::upnp_browse_handler))
+            .route("/upnp/search", get(api::upnp_search_handler))
+            .route("/upnp/play", post(api::upnp_play_handler))
+            .route("/upnp/queue", get(api::upnp_queue_handler))
+            .route("/upnp/queue", delete(api::upnp_clear_queue_handler))
+            .route("/upnp/queue/play", post(api::upnp_queue_play_handler))
+            .route("/upnp/queue/remove", post(api::upnp_queue_remove_handler))
+            // Radio favorites (playable on any zone)
+            .route("/radio/favorites", get(api::radio_favorites_handler))
+            .route("/radio/favorites", post(api::radio_add_favorite_handler))
+            .route(
+                "/radio/favorites/{id}",
+                delete(api::radio_remove_favorite_handler),
+            )
+            .route("/radio/play", post(api::radio_play_handler))
+            // AirPlay routes (shairport-sync)
+            .route("/airplay/status", get(api::airplay_status_handler))
+            .route("/airplay/zones", get(api::airplay_zones_handler))
+            .route(
+                "/airplay/zone/{zone_id}/now_playing",
+                get(api::airplay_now_playing_handler),
+            )
+            .route("/airplay/control", post(api::airplay_control_handler))
+            .route("/airplay/config", get(api::airplay_config_handler))
+            .route("/airplay/configure", post(api::airplay_configure_handler))
+            // Spotify Connect routes (librespot / go-librespot)
+            .route("/spotify/status", get(api::spotify_status_handler))
+            .route("/spotify/zones", get(api::spotify_zones_handler))
+            .route(
+                "/spotify/zone/{zone_id}/now_playing",
+                get(api::spotify_now_playing_handler),
+            )
+            .route("/spotify/control", post(api::spotify_control_handler))
+            .route("/spotify/onevent", post(api::spotify_onevent_handler))
+            .route("/spotify/config", get(api::spotify_config_handler))
+            .route("/spotify/configure", post(api::spotify_configure_handler))
+            // Remote bridge routes (federation)
+            .route("/remote/status", get(api::remote_status_handler))
+            .route("/remote/zones", get(api::remote_zones_handler))
+            .route("/remote/config", get(api::remote_config_handler))
+            .route("/remote/configure", post(api::remote_configure_handler))
+            // External adapter routes (subprocess JSON lines protocol)
+            .route("/external/adapters", get(api::external_adapters_handler))
+            .route(
+                "/external/adapters",
+                post(api::external_add_adapter_handler),
+            )
+            .route(
+                "/external/adapters/{name}",
+                delete(api::external_remove_adapter_handler),
+            )
+            .route("/external/zones", get(api::external_zones_handler))
+            .route("/external/control", post(api::external_control_handler))
+            // Volume output routes (network amplifiers/preamps)
+            .route("/volume/outputs", get(api::volume_outputs_handler))
+            .route("/volume/outputs", post(api::volume_add_output_handler))
+            .route(
+                "/volume/outputs/{name}",
+                delete(api::volume_remove_output_handler),
+            )
+            .route(
+                "/volume/outputs/{name}/state",
+                get(api::volume_output_state_handler),
+            )
+            .route("/volume/control", post(api::volume_control_handler))
+            .route(
+                "/volume/zones/delegates",
+                get(api::volume_zone_delegates_handler),
+            )
+            .route(
+                "/volume/zones/delegate",
+                post(api::volume_zone_delegate_handler),
+            )
+            .route(
+                "/volume/zones/undelegate",
+                post(api::volume_zone_undelegate_handler),
+            )
             // App settings API
             .route("/api/settings", get(api::api_settings_get_handler))
             .route("/api/settings", post(api::api_settings_post_handler))
@@ -470,9 +679,14 @@ mod server {
         if let Some(ref fw) = firmware_service {
             fw.stop();
         }
-        lms.stop().await;
+        hqp_instances.stop();
+        lms_instances.stop().await;
         openhome.stop().await;
         upnp.stop().await;
+        airplay.stop().await;
+        spotify.stop().await;
+        remote.stop().await;
+        external_adapters.stop_all().await;
         tracing::info!("Shutdown complete");
 
         Ok(())
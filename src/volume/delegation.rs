@@ -0,0 +1,232 @@
+//! Zone volume delegation
+//!
+//! "Volume for zone X is handled by output Y": when a zone plays into a
+//! fixed-volume output feeding a preamp or AV receiver, volume and mute
+//! commands for that zone are sent to the volume output instead of the
+//! source adapter. Transport commands still go to the source.
+//!
+//! Delegations are keyed by prefixed zone_id (e.g. "roon:1601...") and
+//! persisted in the config dir.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::{VolumeOutputManager, OUTPUT_ID_PREFIX};
+use crate::bus::{BusEvent, SharedBus};
+use crate::config::get_config_dir;
+
+const ZONE_VOLUME_DELEGATES_FILE: &str = "zone-volume-delegates.json";
+
+fn delegates_path() -> PathBuf {
+    get_config_dir().join(ZONE_VOLUME_DELEGATES_FILE)
+}
+
+/// Map a surface action to the volume output action it delegates to
+///
+/// Returns `None` for transport actions, which stay with the source adapter.
+pub fn delegated_action(action: &str) -> Option<&'static str> {
+    match action {
+        "vol_up" | "volume_up" => Some("vol_up"),
+        "vol_down" | "volume_down" => Some("vol_down"),
+        "vol_abs" | "volume" => Some("vol_abs"),
+        "mute" => Some("mute"),
+        "unmute" => Some("unmute"),
+        "mute_toggle" | "toggle_mute" => Some("mute_toggle"),
+        _ => None,
+    }
+}
+
+/// Zone delegation info for API responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeDelegate {
+    pub zone_id: String,
+    pub output: String,
+}
+
+/// Service for managing zone-to-volume-output delegations
+pub struct VolumeDelegationService {
+    delegates: Arc<RwLock<HashMap<String, String>>>, // zone_id -> output name
+    outputs: Arc<VolumeOutputManager>,
+    bus: SharedBus,
+}
+
+impl VolumeDelegationService {
+    /// Create a new delegation service
+    pub fn new(outputs: Arc<VolumeOutputManager>, bus: SharedBus) -> Self {
+        let service = Self {
+            delegates: Arc::new(RwLock::new(HashMap::new())),
+            outputs,
+            bus,
+        };
+        service.load_delegates_sync();
+        service
+    }
+
+    /// Load delegations from disk synchronously (at startup)
+    fn load_delegates_sync(&self) {
+        let path = delegates_path();
+        if !path.exists() {
+            return;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<HashMap<String, String>>(&content) {
+                Ok(saved) => {
+                    if let Ok(mut delegates) = self.delegates.try_write() {
+                        *delegates = saved;
+                        tracing::info!("Loaded {} zone volume delegates", delegates.len());
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to parse zone volume delegates: {}", e),
+            },
+            Err(e) => tracing::warn!("Failed to read zone volume delegates: {}", e),
+        }
+    }
+
+    /// Save delegations to disk
+    async fn save_delegates(&self) {
+        let delegates = self.delegates.read().await;
+        let path = delegates_path();
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        match serde_json::to_string_pretty(&*delegates) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    tracing::error!("Failed to save zone volume delegates: {}", e);
+                } else {
+                    tracing::debug!("Saved {} zone volume delegates", delegates.len());
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize zone volume delegates: {}", e),
+        }
+    }
+
+    /// Delegate a zone's volume to a volume output
+    pub async fn delegate_zone(&self, zone_id: String, output: String) -> Result<()> {
+        if self.outputs.get(&output).await.is_none() {
+            return Err(anyhow!("Unknown volume output: {}", output));
+        }
+
+        self.delegates
+            .write()
+            .await
+            .insert(zone_id.clone(), output.clone());
+        self.save_delegates().await;
+        tracing::info!("Zone {} volume delegated to {}", zone_id, output);
+
+        self.publish_volume_control(&zone_id, &output).await;
+        Ok(())
+    }
+
+    /// Return a zone's volume to its source adapter
+    pub async fn remove_delegate(&self, zone_id: &str) -> bool {
+        let was_delegated = self.delegates.write().await.remove(zone_id).is_some();
+
+        if was_delegated {
+            self.save_delegates().await;
+            tracing::info!("Zone {} volume no longer delegated", zone_id);
+            self.bus.publish(BusEvent::ZoneVolumeDelegated {
+                zone_id: zone_id.to_string(),
+                volume_control: None,
+            });
+        }
+
+        was_delegated
+    }
+
+    /// Get the volume output name for a zone
+    pub async fn get_output_for_zone(&self, zone_id: &str) -> Option<String> {
+        self.delegates.read().await.get(zone_id).cloned()
+    }
+
+    /// Get all delegations (sorted by zone_id)
+    pub async fn get_delegates(&self) -> Vec<VolumeDelegate> {
+        let delegates = self.delegates.read().await;
+        let mut list: Vec<_> = delegates
+            .iter()
+            .map(|(zone_id, output)| VolumeDelegate {
+                zone_id: zone_id.clone(),
+                output: output.clone(),
+            })
+            .collect();
+        list.sort_by(|a, b| a.zone_id.cmp(&b.zone_id));
+        list
+    }
+
+    /// Remove all delegations pointing to a specific output
+    pub async fn remove_delegates_for_output(&self, output: &str) -> usize {
+        let removed: Vec<String> = {
+            let mut delegates = self.delegates.write().await;
+            let zone_ids: Vec<String> = delegates
+                .iter()
+                .filter(|(_, o)| *o == output)
+                .map(|(zone_id, _)| zone_id.clone())
+                .collect();
+            for zone_id in &zone_ids {
+                delegates.remove(zone_id);
+            }
+            zone_ids
+        };
+
+        if !removed.is_empty() {
+            self.save_delegates().await;
+            tracing::info!(
+                "Removed {} zone volume delegates for deleted output {}",
+                removed.len(),
+                output
+            );
+            for zone_id in &removed {
+                self.bus.publish(BusEvent::ZoneVolumeDelegated {
+                    zone_id: zone_id.clone(),
+                    volume_control: None,
+                });
+            }
+        }
+
+        removed.len()
+    }
+
+    /// Send a volume/mute action for a delegated zone to its output
+    ///
+    /// `action` is a surface action (see [`delegated_action`]). For steps,
+    /// `value` is the number of device steps (one if omitted).
+    pub async fn control(&self, zone_id: &str, action: &str, value: Option<f32>) -> Result<()> {
+        let output = self
+            .get_output_for_zone(zone_id)
+            .await
+            .ok_or_else(|| anyhow!("Zone volume is not delegated: {}", zone_id))?;
+        let action =
+            delegated_action(action).ok_or_else(|| anyhow!("Not a volume action: {}", action))?;
+        self.outputs.control(&output, action, value, None).await
+    }
+
+    /// Publish the current volume control of every delegated zone
+    ///
+    /// Called at startup so zone state reflects delegates before the first
+    /// volume change.
+    pub async fn refresh(&self) {
+        for delegate in self.get_delegates().await {
+            self.publish_volume_control(&delegate.zone_id, &delegate.output)
+                .await;
+        }
+    }
+
+    async fn publish_volume_control(&self, zone_id: &str, output: &str) {
+        match self.outputs.get_state(output).await {
+            Ok(state) => self.bus.publish(BusEvent::ZoneVolumeDelegated {
+                zone_id: zone_id.to_string(),
+                volume_control: Some(
+                    state.volume_control(&format!("{}{}", OUTPUT_ID_PREFIX, output)),
+                ),
+            }),
+            Err(e) => tracing::warn!("Volume delegate {} unreachable: {}", output, e),
+        }
+    }
+}
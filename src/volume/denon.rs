@@ -0,0 +1,247 @@
+//! Denon/Marantz telnet control
+//!
+//! Commands are ASCII terminated by CR, e.g. `MV?` → `MV455` (45.5),
+//! `MU?` → `MUOFF`, `SI?` → `SICD`, `PW?` → `PWON`.
+//!
+//! Master volume runs 0-98 in 0.5 steps where 80 is the 0 dB reference,
+//! so values are exposed in dB (-80..+18).
+//!
+//! Receivers only accept one telnet client at a time, so each operation
+//! opens a short-lived connection guarded by a mutex.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio::time::timeout;
+
+use super::{OutputState, VolumeOutput};
+use crate::bus::VolumeScale;
+
+const DEFAULT_PORT: u16 = 23;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(2);
+const ECHO_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Offset between raw master volume and dB (raw 80 = 0 dB)
+const DB_OFFSET: f32 = 80.0;
+const MIN_DB: f32 = -80.0;
+const MAX_DB: f32 = 18.0;
+
+// =============================================================================
+// SAFETY CRITICAL: Volume ceiling
+// =============================================================================
+//
+// Receivers go up to +18 dB. Unless configured otherwise we cap absolute
+// volume at the 0 dB reference level so a bad request can't drive the amp
+// into clipping. See tests/adapter_integration.rs for regression protection.
+
+/// Default upper limit in dB
+const DEFAULT_MAX_VOLUME_DB: f32 = 0.0;
+
+/// Denon/Marantz network receiver
+pub struct DenonOutput {
+    host: String,
+    port: u16,
+    max_volume_db: f32,
+    lock: Mutex<()>,
+}
+
+impl DenonOutput {
+    pub fn new(host: String, port: Option<u16>, max_volume_db: Option<f32>) -> Self {
+        Self {
+            host,
+            port: port.unwrap_or(DEFAULT_PORT),
+            max_volume_db: max_volume_db
+                .unwrap_or(DEFAULT_MAX_VOLUME_DB)
+                .clamp(MIN_DB, MAX_DB),
+            lock: Mutex::new(()),
+        }
+    }
+
+    async fn connect(&self) -> Result<TcpStream> {
+        let addr = format!("{}:{}", self.host, self.port);
+        timeout(CONNECT_TIMEOUT, TcpStream::connect(&addr))
+            .await
+            .map_err(|_| anyhow!("Connection timeout to {}", addr))?
+            .map_err(|e| anyhow!("Failed to connect to {}: {}", addr, e))
+    }
+
+    /// Send a command, giving the receiver a moment to acknowledge it
+    ///
+    /// Set commands are echoed back as status lines. Waiting for the echo
+    /// keeps a following query from racing ahead of the change, but not
+    /// every command is echoed, so a missing reply is not an error.
+    async fn send(&self, command: &str) -> Result<()> {
+        self.exchange(command, |_| true, ECHO_TIMEOUT).await?;
+        Ok(())
+    }
+
+    /// Send a query and return the first reply accepted by `matches`
+    async fn query<F>(&self, command: &str, matches: F) -> Result<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.exchange(command, matches, RESPONSE_TIMEOUT)
+            .await?
+            .ok_or_else(|| anyhow!("No response to {}", command))
+    }
+
+    /// Write a command and wait up to `deadline` for a matching reply
+    ///
+    /// Receivers also push unsolicited status lines, so non-matching
+    /// replies are skipped. Returns `None` if nothing matched in time.
+    async fn exchange<F>(
+        &self,
+        command: &str,
+        matches: F,
+        deadline: Duration,
+    ) -> Result<Option<String>>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let _guard = self.lock.lock().await;
+        let stream = self.connect().await?;
+        let (read_half, mut write_half) = stream.into_split();
+        let mut reader = BufReader::new(read_half);
+
+        write_half.write_all(command.as_bytes()).await?;
+        write_half.write_all(b"\r").await?;
+        write_half.flush().await?;
+
+        let reply = timeout(deadline, async {
+            let mut buf = Vec::new();
+            loop {
+                buf.clear();
+                let n = reader.read_until(b'\r', &mut buf).await?;
+                if n == 0 {
+                    return Err(anyhow!("Connection closed"));
+                }
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim();
+                if matches(line) {
+                    return Ok(line.to_string());
+                }
+            }
+        })
+        .await;
+
+        match reply {
+            Ok(line) => line.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    async fn get_volume(&self) -> Result<f32> {
+        let line = self
+            .query("MV?", |l| {
+                l.strip_prefix("MV")
+                    .is_some_and(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+            })
+            .await?;
+        parse_master_volume(&line[2..])
+            .map(|raw| raw - DB_OFFSET)
+            .ok_or_else(|| anyhow!("Invalid volume response: {}", line))
+    }
+
+    async fn get_mute(&self) -> Result<bool> {
+        let line = self.query("MU?", |l| l.starts_with("MU")).await?;
+        Ok(line == "MUON")
+    }
+
+    async fn get_power(&self) -> Result<bool> {
+        let line = self.query("PW?", |l| l.starts_with("PW")).await?;
+        Ok(line == "PWON")
+    }
+
+    async fn get_input(&self) -> Result<String> {
+        let line = self.query("SI?", |l| l.starts_with("SI")).await?;
+        Ok(line[2..].to_string())
+    }
+}
+
+/// Parse a master volume value: "45" → 45.0, "455" → 45.5, "005" → 0.5
+pub fn parse_master_volume(value: &str) -> Option<f32> {
+    if !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    match value.len() {
+        2 => value.parse::<f32>().ok(),
+        3 => value.parse::<f32>().ok().map(|v| v / 10.0),
+        _ => None,
+    }
+}
+
+/// Format a raw master volume for the MV command (0.5 steps)
+pub fn format_master_volume(raw: f32) -> String {
+    let half_steps = (raw * 2.0).round() as i32;
+    if half_steps % 2 == 0 {
+        format!("{:02}", half_steps / 2)
+    } else {
+        format!("{:02}5", half_steps / 2)
+    }
+}
+
+#[async_trait]
+impl VolumeOutput for DenonOutput {
+    fn driver(&self) -> &'static str {
+        "denon"
+    }
+
+    async fn get_state(&self) -> Result<OutputState> {
+        let power = self.get_power().await?;
+        let volume = self.get_volume().await?;
+        let is_muted = self.get_mute().await?;
+        let input = self.get_input().await.ok();
+
+        Ok(OutputState {
+            power,
+            volume,
+            min: MIN_DB,
+            max: self.max_volume_db,
+            step: 0.5,
+            scale: VolumeScale::Decibel,
+            is_muted,
+            input,
+        })
+    }
+
+    async fn set_volume(&self, value: f32) -> Result<()> {
+        if value.is_nan() {
+            return Err(anyhow!("Invalid volume value"));
+        }
+        let db = value.clamp(MIN_DB, self.max_volume_db);
+        self.send(&format!("MV{}", format_master_volume(db + DB_OFFSET)))
+            .await
+    }
+
+    async fn step_volume(&self, up: bool) -> Result<()> {
+        if up {
+            // MVUP has no ceiling of its own, so enforce ours
+            let current = self.get_volume().await?;
+            if current + 0.5 > self.max_volume_db {
+                return Ok(());
+            }
+            self.send("MVUP").await
+        } else {
+            self.send("MVDOWN").await
+        }
+    }
+
+    async fn set_mute(&self, muted: bool) -> Result<()> {
+        self.send(if muted { "MUON" } else { "MUOFF" }).await
+    }
+
+    async fn set_input(&self, input: &str) -> Result<()> {
+        if input.is_empty() || input.contains(['\r', '\n']) {
+            return Err(anyhow!("Invalid input name: {:?}", input));
+        }
+        self.send(&format!("SI{}", input.to_uppercase())).await
+    }
+
+    async fn set_power(&self, on: bool) -> Result<()> {
+        self.send(if on { "PWON" } else { "PWSTANDBY" }).await
+    }
+}
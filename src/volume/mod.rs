@@ -0,0 +1,387 @@
+//! Volume outputs (network amplifiers and preamps)
+//!
+//! Many zones feed a preamp or AV receiver whose volume is what actually
+//! matters. This module provides drivers for common IP control protocols:
+//! - Denon/Marantz telnet protocol (port 23)
+//! - Yamaha Extended Control (YXC) HTTP API
+//! - RS-232 serial with a configurable command table
+//!
+//! Each output exposes volume, mute, input selection and power through the
+//! [`VolumeOutput`] trait and is managed by [`VolumeOutputManager`]. Zones
+//! can delegate their volume to an output via [`VolumeDelegationService`].
+
+pub mod delegation;
+pub mod denon;
+pub mod serial;
+pub mod yamaha;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::bus::{BusEvent, SharedBus, VolumeControl, VolumeScale};
+use crate::config::get_config_dir;
+
+pub use delegation::{VolumeDelegate, VolumeDelegationService};
+pub use denon::DenonOutput;
+pub use serial::{SerialOutput, SerialSettings};
+pub use yamaha::YamahaOutput;
+
+const VOLUME_OUTPUTS_CONFIG_FILE: &str = "volume-outputs.json";
+
+/// Upper bound on device steps moved by a single vol_up/vol_down
+const MAX_STEPS_PER_COMMAND: u32 = 20;
+
+/// Prefix for volume output IDs published on the bus
+pub const OUTPUT_ID_PREFIX: &str = "volume:";
+
+// =============================================================================
+// VolumeOutput - common driver interface
+// =============================================================================
+
+/// Current state of a volume output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputState {
+    pub power: bool,
+    pub volume: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+    pub scale: VolumeScale,
+    pub is_muted: bool,
+    pub input: Option<String>,
+}
+
+impl OutputState {
+    /// Convert to a bus VolumeControl for the given output ID
+    pub fn volume_control(&self, output_id: &str) -> VolumeControl {
+        VolumeControl {
+            value: self.volume,
+            min: self.min,
+            max: self.max,
+            step: self.step,
+            is_muted: self.is_muted,
+            scale: self.scale,
+            output_id: Some(output_id.to_string()),
+        }
+    }
+}
+
+/// Trait implemented by every volume output driver
+#[async_trait]
+pub trait VolumeOutput: Send + Sync {
+    /// Driver name (e.g., "denon", "yamaha")
+    fn driver(&self) -> &'static str;
+
+    /// Query the device for its current state
+    async fn get_state(&self) -> Result<OutputState>;
+
+    /// Set absolute volume (in the scale reported by `get_state`)
+    async fn set_volume(&self, value: f32) -> Result<()>;
+
+    /// Step volume up or down by one device step
+    async fn step_volume(&self, up: bool) -> Result<()>;
+
+    /// Mute/unmute
+    async fn set_mute(&self, muted: bool) -> Result<()>;
+
+    /// Select an input by its device-specific name
+    async fn set_input(&self, input: &str) -> Result<()>;
+
+    /// Power on (true) or standby (false)
+    async fn set_power(&self, on: bool) -> Result<()>;
+}
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// Driver-specific settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "driver", rename_all = "lowercase")]
+pub enum DriverConfig {
+    /// Denon/Marantz telnet protocol
+    Denon {
+        host: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+        /// Upper volume limit in dB (defaults to 0 dB reference level)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_volume_db: Option<f32>,
+    },
+    /// Yamaha Extended Control HTTP API
+    Yamaha {
+        host: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+        /// YXC zone name (defaults to "main")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        zone: Option<String>,
+        /// Upper volume limit in raw steps (defaults to 3/4 of the zone's range)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_volume: Option<i32>,
+    },
+    /// RS-232 serial with a configurable command table
+    Serial(Box<SerialSettings>),
+}
+
+/// Named volume output config (persisted)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeOutputConfig {
+    pub name: String,
+    #[serde(flatten)]
+    pub driver: DriverConfig,
+}
+
+impl VolumeOutputConfig {
+    /// Build the driver for this config
+    ///
+    /// Fails if the config is invalid (e.g. a bad serial response regex).
+    pub fn build(&self) -> Result<Arc<dyn VolumeOutput>> {
+        Ok(match &self.driver {
+            DriverConfig::Denon {
+                host,
+                port,
+                max_volume_db,
+            } => Arc::new(DenonOutput::new(host.clone(), *port, *max_volume_db)),
+            DriverConfig::Yamaha {
+                host,
+                port,
+                zone,
+                max_volume,
+            } => Arc::new(YamahaOutput::new(
+                host.clone(),
+                *port,
+                zone.clone(),
+                *max_volume,
+            )),
+            DriverConfig::Serial(settings) => {
+                Arc::new(SerialOutput::new(settings.as_ref().clone())?)
+            }
+        })
+    }
+}
+
+fn config_path() -> PathBuf {
+    get_config_dir().join(VOLUME_OUTPUTS_CONFIG_FILE)
+}
+
+/// Load volume output configs from disk
+pub fn load_volume_output_configs() -> Vec<VolumeOutputConfig> {
+    let path = config_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(configs) => configs,
+            Err(e) => {
+                tracing::warn!("Failed to parse volume outputs config: {}", e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            tracing::warn!("Failed to read volume outputs config: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Save volume output configs to disk
+pub fn save_volume_output_configs(configs: &[VolumeOutputConfig]) -> bool {
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    match serde_json::to_string_pretty(configs) {
+        Ok(json) => match std::fs::write(&path, json) {
+            Ok(()) => {
+                tracing::info!("Saved volume outputs config ({} outputs)", configs.len());
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to save volume outputs config: {}", e);
+                false
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to serialize volume outputs config: {}", e);
+            false
+        }
+    }
+}
+
+// =============================================================================
+// Manager
+// =============================================================================
+
+/// Configured output; `output` is None if the config failed to build
+///
+/// Invalid configs are kept so saving doesn't silently drop them from disk.
+struct OutputEntry {
+    config: VolumeOutputConfig,
+    output: Option<Arc<dyn VolumeOutput>>,
+}
+
+/// Manager for configured volume outputs
+pub struct VolumeOutputManager {
+    outputs: Arc<RwLock<HashMap<String, OutputEntry>>>,
+    bus: SharedBus,
+}
+
+impl VolumeOutputManager {
+    /// Create an empty manager
+    pub fn new(bus: SharedBus) -> Self {
+        Self {
+            outputs: Arc::new(RwLock::new(HashMap::new())),
+            bus,
+        }
+    }
+
+    /// Load outputs from config file
+    pub async fn load_from_config(&self) {
+        let configs = load_volume_output_configs();
+        let mut outputs = self.outputs.write().await;
+        for config in configs {
+            let output = match config.build() {
+                Ok(output) => Some(output),
+                Err(e) => {
+                    tracing::error!("Volume output {} is invalid: {}", config.name, e);
+                    None
+                }
+            };
+            outputs.insert(config.name.clone(), OutputEntry { config, output });
+        }
+    }
+
+    /// Save all outputs to config file
+    pub async fn save_to_config(&self) {
+        let configs = self.list_outputs().await;
+        save_volume_output_configs(&configs);
+    }
+
+    /// List configured outputs (sorted by name)
+    pub async fn list_outputs(&self) -> Vec<VolumeOutputConfig> {
+        let outputs = self.outputs.read().await;
+        let mut configs: Vec<_> = outputs.values().map(|e| e.config.clone()).collect();
+        configs.sort_by(|a, b| a.name.cmp(&b.name));
+        configs
+    }
+
+    /// Get an output driver by name
+    pub async fn get(&self, name: &str) -> Option<Arc<dyn VolumeOutput>> {
+        let outputs = self.outputs.read().await;
+        outputs.get(name).and_then(|e| e.output.clone())
+    }
+
+    /// Add or replace an output without persisting it
+    pub async fn insert(&self, config: VolumeOutputConfig, output: Arc<dyn VolumeOutput>) {
+        let mut outputs = self.outputs.write().await;
+        outputs.insert(
+            config.name.clone(),
+            OutputEntry {
+                config,
+                output: Some(output),
+            },
+        );
+    }
+
+    /// Add or update an output and persist the config
+    pub async fn add_output(&self, config: VolumeOutputConfig) -> Result<()> {
+        let output = config.build()?;
+        self.insert(config, output).await;
+        self.save_to_config().await;
+        Ok(())
+    }
+
+    /// Remove an output by name
+    pub async fn remove_output(&self, name: &str) -> bool {
+        let removed = self.outputs.write().await.remove(name).is_some();
+        if removed {
+            self.save_to_config().await;
+        }
+        removed
+    }
+
+    /// Query the current state of an output
+    pub async fn get_state(&self, name: &str) -> Result<OutputState> {
+        let output = self
+            .get(name)
+            .await
+            .ok_or_else(|| anyhow!("Volume output not found: {}", name))?;
+        output.get_state().await
+    }
+
+    /// Control an output
+    ///
+    /// Actions: vol_abs, vol_up, vol_down, mute, unmute, mute_toggle,
+    /// input, power_on, power_off. For vol_up/vol_down, `value` is the
+    /// number of device steps (one if omitted); each step honours the
+    /// driver's volume ceiling.
+    pub async fn control(
+        &self,
+        name: &str,
+        action: &str,
+        value: Option<f32>,
+        input: Option<&str>,
+    ) -> Result<()> {
+        let output = self
+            .get(name)
+            .await
+            .ok_or_else(|| anyhow!("Volume output not found: {}", name))?;
+
+        match action {
+            "vol_abs" => {
+                let value = value.ok_or_else(|| anyhow!("vol_abs requires a value"))?;
+                output.set_volume(value).await?;
+            }
+            "vol_up" | "vol_down" => {
+                let steps = value
+                    .filter(|v| v.is_finite())
+                    .map_or(1, |v| v.abs().round().max(1.0) as u32)
+                    .min(MAX_STEPS_PER_COMMAND);
+                for _ in 0..steps {
+                    output.step_volume(action == "vol_up").await?;
+                }
+            }
+            "mute" => output.set_mute(true).await?,
+            "unmute" => output.set_mute(false).await?,
+            "mute_toggle" => {
+                let state = output.get_state().await?;
+                output.set_mute(!state.is_muted).await?;
+            }
+            "input" => {
+                let input = input.ok_or_else(|| anyhow!("input action requires an input"))?;
+                output.set_input(input).await?;
+                return Ok(());
+            }
+            "power_on" => {
+                output.set_power(true).await?;
+                return Ok(());
+            }
+            "power_off" => {
+                output.set_power(false).await?;
+                return Ok(());
+            }
+            _ => return Err(anyhow!("Unknown action: {}", action)),
+        }
+
+        // Volume/mute changed - publish the new state
+        match output.get_state().await {
+            Ok(state) => self.bus.publish(BusEvent::VolumeChanged {
+                output_id: format!("{}{}", OUTPUT_ID_PREFIX, name),
+                value: state.volume,
+                is_muted: state.is_muted,
+            }),
+            Err(e) => tracing::debug!("Failed to refresh volume output {}: {}", name, e),
+        }
+
+        Ok(())
+    }
+}
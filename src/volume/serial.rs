@@ -0,0 +1,523 @@
+//! RS-232 control with a configurable command table
+//!
+//! Many reference-grade preamps (Accuphase, McIntosh, Parasound, Rotel) only
+//! speak RS-232, each with its own dialect. Rather than one driver per
+//! model, the protocol is described in config:
+//! - a template string per action (`{value}`, `{value:03}`, `{input}` placeholders)
+//! - optionally, the input names `{input}` may take (otherwise plain names only,
+//!   so a request can't smuggle in a second command)
+//! - a regex per response field (first capture group is the value)
+//! - serial line settings (baud, data bits, parity, stop bits)
+//!
+//! Example (Rotel-style):
+//! ```json
+//! {
+//!   "driver": "serial", "name": "preamp", "port": "/dev/ttyUSB0", "baud_rate": 115200,
+//!   "volume_min": 0, "volume_max": 96,
+//!   "commands": { "volume": "vol_{value:02}!", "query_volume": "volume?",
+//!                 "mute_on": "mute_on!", "mute_off": "mute_off!", "query_mute": "mute?" },
+//!   "responses": { "volume": "volume=(\\d+)", "mute": "mute=(on|off)" }
+//! }
+//! ```
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+use tokio::time::timeout;
+use tokio_serial::{DataBits, Parity, SerialPortBuilderExt, SerialStream, StopBits};
+
+use super::{OutputState, VolumeOutput};
+use crate::bus::VolumeScale;
+
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(2);
+const ECHO_TIMEOUT: Duration = Duration::from_millis(200);
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(20);
+
+/// `{value}` / `{value:0N}` placeholder in command templates
+static VALUE_PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{value(?::0(\d+))?\}").expect("valid placeholder regex"));
+
+/// Serial parity setting
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SerialParity {
+    #[default]
+    None,
+    Odd,
+    Even,
+}
+
+/// Command templates, one per action
+///
+/// Set commands are sent as-is; `query_*` commands are followed by reading
+/// lines until the matching response regex matches.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SerialCommands {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_up: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_down: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mute_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mute_off: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_off: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_volume: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_mute: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_input: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_power: Option<String>,
+}
+
+/// Response regexes, one per state field
+///
+/// The first capture group holds the value. Mute/power captures are true
+/// for "on", "1", "true" or "yes" (case-insensitive).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SerialResponses {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mute: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<String>,
+}
+
+/// Serial output settings (persisted as part of the volume output config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerialSettings {
+    /// Device path, e.g. /dev/ttyUSB0
+    pub port: String,
+    #[serde(default = "default_baud_rate")]
+    pub baud_rate: u32,
+    #[serde(default = "default_data_bits")]
+    pub data_bits: u8,
+    #[serde(default)]
+    pub parity: SerialParity,
+    #[serde(default = "default_stop_bits")]
+    pub stop_bits: u8,
+    /// Appended to every command and used to split responses
+    #[serde(default = "default_line_ending")]
+    pub line_ending: String,
+    /// Volume range in device units (absolute volume is clamped to this)
+    pub volume_min: f32,
+    pub volume_max: f32,
+    #[serde(default = "default_volume_step")]
+    pub volume_step: f32,
+    #[serde(default)]
+    pub volume_scale: VolumeScale,
+    #[serde(default)]
+    pub commands: SerialCommands,
+    #[serde(default)]
+    pub responses: SerialResponses,
+    /// Input names `{input}` accepts (any plain name when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<String>,
+}
+
+fn default_baud_rate() -> u32 {
+    9600
+}
+
+fn default_data_bits() -> u8 {
+    8
+}
+
+fn default_stop_bits() -> u8 {
+    1
+}
+
+fn default_line_ending() -> String {
+    "\r".to_string()
+}
+
+fn default_volume_step() -> f32 {
+    1.0
+}
+
+/// Compiled response regexes
+struct ResponsePatterns {
+    volume: Option<Regex>,
+    mute: Option<Regex>,
+    input: Option<Regex>,
+    power: Option<Regex>,
+}
+
+impl ResponsePatterns {
+    fn compile(responses: &SerialResponses) -> Result<Self> {
+        let compile = |pattern: &Option<String>| -> Result<Option<Regex>> {
+            pattern
+                .as_deref()
+                .map(|p| {
+                    Regex::new(p).map_err(|e| anyhow!("Invalid response regex {:?}: {}", p, e))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            volume: compile(&responses.volume)?,
+            mute: compile(&responses.mute)?,
+            input: compile(&responses.input)?,
+            power: compile(&responses.power)?,
+        })
+    }
+}
+
+/// Serial (RS-232) amplifier with a configurable command table
+pub struct SerialOutput {
+    settings: SerialSettings,
+    patterns: ResponsePatterns,
+    stream: Mutex<Option<SerialStream>>,
+}
+
+impl SerialOutput {
+    /// Create a serial output, validating the response regexes
+    ///
+    /// The port itself is opened lazily on first use.
+    pub fn new(settings: SerialSettings) -> Result<Self> {
+        if settings.volume_min >= settings.volume_max {
+            return Err(anyhow!("volume_min must be below volume_max"));
+        }
+        let patterns = ResponsePatterns::compile(&settings.responses)?;
+        Ok(Self {
+            settings,
+            patterns,
+            stream: Mutex::new(None),
+        })
+    }
+
+    fn open(&self) -> Result<SerialStream> {
+        let data_bits = match self.settings.data_bits {
+            5 => DataBits::Five,
+            6 => DataBits::Six,
+            7 => DataBits::Seven,
+            8 => DataBits::Eight,
+            other => return Err(anyhow!("Unsupported data bits: {}", other)),
+        };
+        let stop_bits = match self.settings.stop_bits {
+            1 => StopBits::One,
+            2 => StopBits::Two,
+            other => return Err(anyhow!("Unsupported stop bits: {}", other)),
+        };
+        let parity = match self.settings.parity {
+            SerialParity::None => Parity::None,
+            SerialParity::Odd => Parity::Odd,
+            SerialParity::Even => Parity::Even,
+        };
+
+        tokio_serial::new(&self.settings.port, self.settings.baud_rate)
+            .data_bits(data_bits)
+            .stop_bits(stop_bits)
+            .parity(parity)
+            .open_native_async()
+            .map_err(|e| anyhow!("Failed to open {}: {}", self.settings.port, e))
+    }
+
+    /// Write a command and wait up to `deadline` for a line matching `pattern`
+    ///
+    /// With no pattern, the first line received (if any) is returned so set
+    /// commands can wait for the device's acknowledgement. Returns `None`
+    /// if nothing matched in time. The port is reopened after I/O errors.
+    async fn exchange(
+        &self,
+        command: &str,
+        pattern: Option<&Regex>,
+        deadline: Duration,
+    ) -> Result<Option<String>> {
+        let mut guard = self.stream.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.open()?);
+        }
+        let stream = guard.as_mut().expect("serial stream just opened");
+
+        let result = exchange_on(
+            stream,
+            command,
+            &self.settings.line_ending,
+            pattern,
+            deadline,
+        )
+        .await;
+        if result.is_err() {
+            *guard = None;
+        }
+        result
+    }
+
+    async fn send(&self, command: &str) -> Result<()> {
+        self.exchange(command, None, ECHO_TIMEOUT).await?;
+        Ok(())
+    }
+
+    /// Run a query and return the first capture group of the response regex
+    async fn query(
+        &self,
+        command: &Option<String>,
+        pattern: &Option<Regex>,
+    ) -> Result<Option<String>> {
+        let (Some(command), Some(pattern)) = (command, pattern) else {
+            return Ok(None);
+        };
+        let line = self
+            .exchange(command, Some(pattern), RESPONSE_TIMEOUT)
+            .await?
+            .ok_or_else(|| anyhow!("No response to {}", command))?;
+        Ok(pattern
+            .captures(&line)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string()))
+    }
+
+    fn template<'a>(&self, template: &'a Option<String>, action: &str) -> Result<&'a str> {
+        template
+            .as_deref()
+            .ok_or_else(|| anyhow!("No '{}' command configured", action))
+    }
+
+    async fn get_volume(&self) -> Result<f32> {
+        let commands = &self.settings.commands;
+        let value = self
+            .query(&commands.query_volume, &self.patterns.volume)
+            .await?
+            .ok_or_else(|| anyhow!("No volume query configured"))?;
+        value
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| anyhow!("Invalid volume response: {}", value))
+    }
+}
+
+async fn exchange_on(
+    stream: &mut SerialStream,
+    command: &str,
+    line_ending: &str,
+    pattern: Option<&Regex>,
+    deadline: Duration,
+) -> Result<Option<String>> {
+    // Discard stale output (unsolicited status, late echoes)
+    let mut scratch = [0u8; 256];
+    while let Ok(Ok(n)) = timeout(DRAIN_TIMEOUT, stream.read(&mut scratch)).await {
+        if n == 0 {
+            break;
+        }
+    }
+
+    stream.write_all(command.as_bytes()).await?;
+    stream.write_all(line_ending.as_bytes()).await?;
+    stream.flush().await?;
+
+    let reply = timeout(deadline, async {
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            let n = stream.read(&mut byte).await?;
+            if n == 0 {
+                return Err(anyhow!("Serial port closed"));
+            }
+            if byte[0] != b'\r' && byte[0] != b'\n' {
+                line.push(byte[0]);
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line).trim().to_string();
+            line.clear();
+            match pattern {
+                Some(re) if !re.is_match(&text) => continue,
+                _ => return Ok(text),
+            }
+        }
+    })
+    .await;
+
+    match reply {
+        Ok(line) => line.map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Render a command template
+///
+/// `{input}` is replaced verbatim (callers check it, see `valid_input`).
+/// `{value}` is replaced by the volume,
+/// with one decimal place only when `step` is fractional; `{value:0N}`
+/// zero-pads to N characters.
+pub fn render_template(
+    template: &str,
+    value: Option<f32>,
+    input: Option<&str>,
+    step: f32,
+) -> String {
+    let mut out = template.to_string();
+    if let Some(input) = input {
+        out = out.replace("{input}", input);
+    }
+    if let Some(value) = value {
+        let formatted = if step.fract() == 0.0 {
+            format!("{}", value.round() as i64)
+        } else {
+            format!("{:.1}", value)
+        };
+        out = VALUE_PLACEHOLDER
+            .replace_all(&out, |caps: &regex::Captures| match caps.get(1) {
+                Some(width) => {
+                    let width: usize = width.as_str().parse().unwrap_or(0);
+                    if let Some(digits) = formatted.strip_prefix('-') {
+                        format!("-{:0>w$}", digits, w = width.saturating_sub(1))
+                    } else {
+                        format!("{:0>w$}", formatted, w = width)
+                    }
+                }
+                None => formatted.clone(),
+            })
+            .into_owned();
+    }
+    out
+}
+
+/// Whether `input` may be sent in place of `{input}`
+///
+/// Input names come from API requests, so anything outside the configured
+/// list (or, without one, anything but letters, digits, `_`, `-` and `.`)
+/// is refused: a terminator or control character would let the name carry
+/// a second command, such as a volume above `volume_max`.
+fn valid_input(input: &str, inputs: &[String]) -> bool {
+    if !inputs.is_empty() {
+        return inputs.iter().any(|name| name == input);
+    }
+    !input.is_empty()
+        && input
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn parse_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "on" | "1" | "true" | "yes"
+    )
+}
+
+#[async_trait]
+impl VolumeOutput for SerialOutput {
+    fn driver(&self) -> &'static str {
+        "serial"
+    }
+
+    async fn get_state(&self) -> Result<OutputState> {
+        let commands = &self.settings.commands;
+        let volume = self.get_volume().await?;
+        let is_muted = self
+            .query(&commands.query_mute, &self.patterns.mute)
+            .await?
+            .map(|v| parse_flag(&v))
+            .unwrap_or(false);
+        let power = self
+            .query(&commands.query_power, &self.patterns.power)
+            .await?
+            .map(|v| parse_flag(&v))
+            .unwrap_or(true);
+        let input = self
+            .query(&commands.query_input, &self.patterns.input)
+            .await?;
+
+        Ok(OutputState {
+            power,
+            volume,
+            min: self.settings.volume_min,
+            max: self.settings.volume_max,
+            step: self.settings.volume_step,
+            scale: self.settings.volume_scale,
+            is_muted,
+            input,
+        })
+    }
+
+    async fn set_volume(&self, value: f32) -> Result<()> {
+        if value.is_nan() {
+            return Err(anyhow!("Invalid volume value"));
+        }
+        let template = self.template(&self.settings.commands.volume, "volume")?;
+        let value = value.clamp(self.settings.volume_min, self.settings.volume_max);
+        let command = render_template(template, Some(value), None, self.settings.volume_step);
+        self.send(&command).await
+    }
+
+    async fn step_volume(&self, up: bool) -> Result<()> {
+        let commands = &self.settings.commands;
+        let template = if up {
+            &commands.volume_up
+        } else {
+            &commands.volume_down
+        };
+        match template {
+            Some(command) => {
+                if up {
+                    // Native step commands have no ceiling of their own, so
+                    // enforce volume_max (refuse if the level can't be read)
+                    let current = self.get_volume().await?;
+                    if current + self.settings.volume_step > self.settings.volume_max {
+                        return Ok(());
+                    }
+                }
+                self.send(command).await
+            }
+            // No native step command: read back and set absolute instead
+            None => {
+                let current = self.get_volume().await?;
+                let delta = if up {
+                    self.settings.volume_step
+                } else {
+                    -self.settings.volume_step
+                };
+                self.set_volume(current + delta).await
+            }
+        }
+    }
+
+    async fn set_mute(&self, muted: bool) -> Result<()> {
+        let commands = &self.settings.commands;
+        let command = if muted {
+            self.template(&commands.mute_on, "mute_on")?
+        } else {
+            self.template(&commands.mute_off, "mute_off")?
+        };
+        self.send(command).await
+    }
+
+    async fn set_input(&self, input: &str) -> Result<()> {
+        if !valid_input(input, &self.settings.inputs) {
+            return Err(anyhow!("Invalid input name: {:?}", input));
+        }
+        let template = self.template(&self.settings.commands.input, "input")?;
+        let command = render_template(template, None, Some(input), self.settings.volume_step);
+        self.send(&command).await
+    }
+
+    async fn set_power(&self, on: bool) -> Result<()> {
+        let commands = &self.settings.commands;
+        let command = if on {
+            self.template(&commands.power_on, "power_on")?
+        } else {
+            self.template(&commands.power_off, "power_off")?
+        };
+        self.send(command).await
+    }
+}
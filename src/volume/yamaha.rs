@@ -0,0 +1,188 @@
+//! Yamaha Extended Control (YXC) HTTP API
+//!
+//! Endpoints live under `/YamahaExtendedControl/v1/{zone}/`:
+//! `getStatus`, `setVolume`, `setMute`, `setInput`, `setPower`.
+//! Every response carries a `response_code` where 0 means success.
+//!
+//! Volume is exposed in raw device steps (0..max_volume from getStatus),
+//! capped at a configurable ceiling below the zone's own maximum.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+use super::{OutputState, VolumeOutput};
+use crate::bus::VolumeScale;
+
+const DEFAULT_PORT: u16 = 80;
+const DEFAULT_ZONE: &str = "main";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+// =============================================================================
+// SAFETY CRITICAL: Volume ceiling
+// =============================================================================
+//
+// A zone's max_volume is full output (well above reference level on most
+// receivers). Unless configured otherwise we cap volume at three quarters
+// of the zone's range so a bad request or repeated steps can't drive the
+// amp to full output. See tests/adapter_integration.rs for regression
+// protection.
+
+/// Default upper limit as a fraction of the zone's max_volume
+const DEFAULT_MAX_VOLUME_FRACTION: f32 = 0.75;
+
+/// Subset of the YXC getStatus response
+#[derive(Debug, Deserialize)]
+struct ZoneStatus {
+    #[serde(default)]
+    power: String,
+    #[serde(default)]
+    volume: i32,
+    #[serde(default)]
+    max_volume: i32,
+    #[serde(default)]
+    mute: bool,
+    #[serde(default)]
+    input: Option<String>,
+}
+
+/// Yamaha network receiver (MusicCast / YXC)
+pub struct YamahaOutput {
+    base_url: String,
+    max_volume: Option<i32>,
+    client: reqwest::Client,
+}
+
+impl YamahaOutput {
+    pub fn new(
+        host: String,
+        port: Option<u16>,
+        zone: Option<String>,
+        max_volume: Option<i32>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            base_url: format!(
+                "http://{}:{}/YamahaExtendedControl/v1/{}",
+                host,
+                port.unwrap_or(DEFAULT_PORT),
+                zone.as_deref().unwrap_or(DEFAULT_ZONE)
+            ),
+            max_volume,
+            client,
+        }
+    }
+
+    /// Call a zone endpoint and check the response code
+    async fn call(&self, endpoint: &str, query: &[(&str, String)]) -> Result<Value> {
+        let url = format!("{}/{}", self.base_url, endpoint);
+        let response: Value = self
+            .client
+            .get(&url)
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        match response.get("response_code").and_then(|c| c.as_i64()) {
+            Some(0) => Ok(response),
+            Some(code) => Err(anyhow!(
+                "Yamaha {} failed (response_code {})",
+                endpoint,
+                code
+            )),
+            None => Err(anyhow!("Yamaha {} returned no response_code", endpoint)),
+        }
+    }
+
+    async fn get_status(&self) -> Result<ZoneStatus> {
+        let response = self.call("getStatus", &[]).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Effective volume ceiling for a zone in raw steps
+    fn ceiling(&self, status: &ZoneStatus) -> i32 {
+        let max = status.max_volume.max(0);
+        match self.max_volume {
+            Some(limit) => limit.clamp(0, max),
+            None => (max as f32 * DEFAULT_MAX_VOLUME_FRACTION) as i32,
+        }
+    }
+}
+
+#[async_trait]
+impl VolumeOutput for YamahaOutput {
+    fn driver(&self) -> &'static str {
+        "yamaha"
+    }
+
+    async fn get_state(&self) -> Result<OutputState> {
+        let status = self.get_status().await?;
+        Ok(OutputState {
+            power: status.power == "on",
+            volume: status.volume as f32,
+            min: 0.0,
+            max: self.ceiling(&status) as f32,
+            step: 1.0,
+            scale: VolumeScale::Unknown,
+            is_muted: status.mute,
+            input: status.input,
+        })
+    }
+
+    async fn set_volume(&self, value: f32) -> Result<()> {
+        if value.is_nan() {
+            return Err(anyhow!("Invalid volume value"));
+        }
+        // Clamp to the ceiling (max_volume varies by model)
+        let status = self.get_status().await?;
+        let volume = (value.round() as i32).clamp(0, self.ceiling(&status));
+        self.call("setVolume", &[("volume", volume.to_string())])
+            .await?;
+        Ok(())
+    }
+
+    async fn step_volume(&self, up: bool) -> Result<()> {
+        if up {
+            // Stepping up has no ceiling of its own on the device, so enforce ours
+            let status = self.get_status().await?;
+            if status.volume + 1 > self.ceiling(&status) {
+                return Ok(());
+            }
+        }
+        let direction = if up { "up" } else { "down" };
+        self.call(
+            "setVolume",
+            &[("volume", direction.to_string()), ("step", "1".to_string())],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn set_mute(&self, muted: bool) -> Result<()> {
+        self.call("setMute", &[("enable", muted.to_string())])
+            .await?;
+        Ok(())
+    }
+
+    async fn set_input(&self, input: &str) -> Result<()> {
+        self.call("setInput", &[("input", input.to_string())])
+            .await?;
+        Ok(())
+    }
+
+    async fn set_power(&self, on: bool) -> Result<()> {
+        let power = if on { "on" } else { "standby" };
+        self.call("setPower", &[("power", power.to_string())])
+            .await?;
+        Ok(())
+    }
+}
@@ -31,9 +31,24 @@ fn extract_routes_from_source() -> BTreeSet<String> {
 
     let mut routes = BTreeSet::new();
 
+    // Join `.route(` calls that rustfmt wrapped over several lines
+    let mut lines = Vec::new();
+    let mut wrapped: Option<String> = None;
     for line in content.lines() {
         let line = line.trim();
+        if let Some(call) = wrapped.as_mut() {
+            call.push_str(line);
+            if line.starts_with(')') {
+                lines.extend(wrapped.take());
+            }
+        } else if line == ".route(" {
+            wrapped = Some(line.to_string());
+        } else {
+            lines.push(line.to_string());
+        }
+    }
 
+    for line in &lines {
         // Skip comments
         if line.starts_with("//") {
             continue;
@@ -38,10 +38,14 @@ use std::{sync::Arc, time::Instant};
 use tokio_util::sync::CancellationToken;
 use tower::ServiceExt;
 
+use unified_hifi_control::adapters::airplay::AirPlayAdapter;
+use unified_hifi_control::adapters::external::ExternalAdapterManager;
 use unified_hifi_control::adapters::hqplayer::{HqpInstanceManager, HqpZoneLinkService};
-use unified_hifi_control::adapters::lms::LmsAdapter;
+use unified_hifi_control::adapters::lms::{LmsAdapter, LmsInstanceManager};
 use unified_hifi_control::adapters::openhome::OpenHomeAdapter;
+use unified_hifi_control::adapters::remote::RemoteAdapter;
 use unified_hifi_control::adapters::roon::RoonAdapter;
+use unified_hifi_control::adapters::spotify::SpotifyAdapter;
 use unified_hifi_control::adapters::upnp::UPnPAdapter;
 use unified_hifi_control::adapters::Startable;
 use unified_hifi_control::aggregator::ZoneAggregator;
@@ -50,6 +54,7 @@ use unified_hifi_control::api::AppState;
 use unified_hifi_control::bus::create_bus;
 use unified_hifi_control::coordinator::AdapterCoordinator;
 use unified_hifi_control::knobs::{self, KnobStore};
+use unified_hifi_control::volume::{VolumeDelegationService, VolumeOutputManager};
 
 // Stub HTML handlers for UI route tests (replacing deleted ui module)
 mod ui_stubs {
@@ -187,13 +192,30 @@ async fn create_test_app() -> Router {
     let hqplayer = hqp_instances.get_default().await;
     let hqp_zone_links = Arc::new(HqpZoneLinkService::new(hqp_instances.clone()));
     let lms = Arc::new(LmsAdapter::new(bus.clone()));
+    let lms_instances = Arc::new(LmsInstanceManager::new(bus.clone(), lms.clone()));
     let openhome = Arc::new(OpenHomeAdapter::new(bus.clone()));
     let upnp = Arc::new(UPnPAdapter::new(bus.clone()));
+    let airplay = Arc::new(AirPlayAdapter::new(bus.clone()));
+    let spotify = Arc::new(SpotifyAdapter::new(bus.clone()));
+    let remote = Arc::new(RemoteAdapter::new(bus.clone()));
+    let external_adapters = Arc::new(ExternalAdapterManager::new(coordinator.clone()));
+    let volume_outputs = Arc::new(VolumeOutputManager::new(bus.clone()));
+    let volume_delegates = Arc::new(VolumeDelegationService::new(
+        volume_outputs.clone(),
+        bus.clone(),
+    ));
     let knob_store = KnobStore::new(std::env::temp_dir());
 
     // Build startable adapters list
-    let startable_adapters: Vec<Arc<dyn Startable>> =
-        vec![roon.clone(), lms.clone(), openhome.clone(), upnp.clone()];
+    let startable_adapters: Vec<Arc<dyn Startable>> = vec![
+        roon.clone(),
+        lms_instances.clone(),
+        openhome.clone(),
+        upnp.clone(),
+        airplay.clone(),
+        spotify.clone(),
+        remote.clone(),
+    ];
 
     let aggregator = Arc::new(ZoneAggregator::new(bus.clone()));
     let state = AppState::new(
@@ -202,8 +224,15 @@ async fn create_test_app() -> Router {
         hqp_instances,
         hqp_zone_links,
         lms,
+        lms_instances,
         openhome,
         upnp,
+        airplay,
+        spotify,
+        remote,
+        external_adapters,
+        volume_outputs,
+        volume_delegates,
         knob_store,
         bus,
         aggregator,
@@ -257,15 +286,47 @@ async fn create_test_app() -> Router {
             "/hqp/instances/{name}/matrix/profiles",
             get(api::hqp_instance_matrix_profiles_handler),
         )
+        .route(
+            "/hqp/instances/{name}/playlist",
+            get(api::hqp_instance_playlist_handler),
+        )
+        .route(
+            "/hqp/instances/{name}/library",
+            get(api::hqp_instance_library_handler),
+        )
+        // HQPlayer pipeline presets
+        .route("/hqp/presets", get(api::hqp_presets_handler))
+        .route("/hqp/presets", post(api::hqp_save_preset_handler))
+        .route(
+            "/hqp/presets/{name}",
+            delete(api::hqp_delete_preset_handler),
+        )
+        .route("/hqp/presets/apply", post(api::hqp_apply_preset_handler))
+        .route("/hqp/pipeline/copy", post(api::hqp_copy_pipeline_handler))
+        .route("/hqp/abx", get(api::hqp_abx_status_handler))
+        .route("/hqp/abx/start", post(api::hqp_abx_start_handler))
+        .route("/hqp/abx/select", post(api::hqp_abx_select_handler))
+        .route("/hqp/abx/guess", post(api::hqp_abx_guess_handler))
+        .route("/hqp/abx/finish", post(api::hqp_abx_finish_handler))
         // HQPlayer zone linking routes
         .route("/hqp/zones/links", get(api::hqp_zone_links_handler))
         .route("/hqp/zones/link", post(api::hqp_zone_link_handler))
         .route("/hqp/zones/unlink", post(api::hqp_zone_unlink_handler))
+        .route(
+            "/hqp/zones/fallbacks",
+            post(api::hqp_zone_fallbacks_handler),
+        )
+        .route("/hqp/zones/volume", post(api::hqp_zone_volume_handler))
+        .route("/hqp/zones/rules", get(api::hqp_zone_rules_handler))
+        .route("/hqp/zones/rules", post(api::hqp_zone_set_rules_handler))
         .route(
             "/hqp/zones/{zone_id}/pipeline",
             get(api::hqp_zone_pipeline_handler),
         )
         .route("/hqp/discover", get(api::hqp_discover_handler))
+        .route("/hqp/discovery", get(api::hqp_discovery_handler))
+        .route("/hqp/discovery", post(api::hqp_discovery_settings_handler))
+        .route("/lms/discover", get(api::lms_discover_handler))
         // LMS routes
         .route("/lms/status", get(api::lms_status_handler))
         .route("/lms/config", get(api::lms_config_handler))
@@ -273,6 +334,9 @@ async fn create_test_app() -> Router {
         .route("/lms/player/{player_id}", get(api::lms_player_handler))
         .route("/lms/control", post(api::lms_control_handler))
         .route("/lms/volume", post(api::lms_volume_handler))
+        .route("/lms/groups", get(api::lms_groups_handler))
+        .route("/lms/browse", get(api::lms_browse_handler))
+        .route("/lms/instances", get(api::lms_instances_handler))
         // OpenHome routes
         .route("/openhome/status", get(api::openhome_status_handler))
         .route("/openhome/zones", get(api::openhome_zones_handler))
@@ -281,14 +345,50 @@ async fn create_test_app() -> Router {
             get(api::openhome_now_playing_handler),
         )
         .route("/openhome/control", post(api::openhome_control_handler))
+        .route("/openhome/queue", get(api::openhome_queue_handler))
+        .route("/openhome/queue", post(api::openhome_queue_insert_handler))
+        .route("/openhome/queue", delete(api::openhome_clear_queue_handler))
+        .route("/openhome/queue/play", post(api::openhome_seek_handler))
+        .route("/openhome/queue/remove", post(api::openhome_remove_handler))
+        .route("/openhome/queue/mode", post(api::openhome_mode_handler))
+        .route("/openhome/sources", get(api::openhome_sources_handler))
+        .route("/openhome/source", post(api::openhome_source_handler))
+        .route("/openhome/standby", post(api::openhome_standby_handler))
+        .route("/openhome/radio", get(api::openhome_radio_handler))
+        .route("/openhome/radio", post(api::openhome_channel_handler))
         // UPnP routes
         .route("/upnp/status", get(api::upnp_status_handler))
         .route("/upnp/zones", get(api::upnp_zones_handler))
+        .route("/airplay/status", get(api::airplay_status_handler))
+        .route("/airplay/zones", get(api::airplay_zones_handler))
+        .route("/spotify/status", get(api::spotify_status_handler))
+        .route("/spotify/zones", get(api::spotify_zones_handler))
+        .route("/remote/status", get(api::remote_status_handler))
+        .route("/remote/zones", get(api::remote_zones_handler))
+        .route("/external/adapters", get(api::external_adapters_handler))
+        .route("/external/zones", get(api::external_zones_handler))
         .route(
             "/upnp/zone/{zone_id}/now_playing",
             get(api::upnp_now_playing_handler),
         )
         .route("/upnp/control", post(api::upnp_control_handler))
+        .route("/upnp/servers", get(api::upnp_servers_handler))
+        .route("/upnp/servers", post(api::upnp_add_server_handler))
+        .route("/upnp/browse", get(api::upnp_browse_handler))
+        .route("/upnp/search", get(api::upnp_search_handler))
+        .route("/upnp/play", post(api::upnp_play_handler))
+        .route("/upnp/queue", get(api::upnp_queue_handler))
+        .route("/upnp/queue", delete(api::upnp_clear_queue_handler))
+        .route("/upnp/queue/play", post(api::upnp_queue_play_handler))
+        .route("/upnp/queue/remove", post(api::upnp_queue_remove_handler))
+        // Radio favorites (playable on any zone)
+        .route("/radio/favorites", get(api::radio_favorites_handler))
+        .route("/radio/favorites", post(api::radio_add_favorite_handler))
+        .route(
+            "/radio/favorites/{id}",
+            delete(api::radio_remove_favorite_handler),
+        )
+        .route("/radio/play", post(api::radio_play_handler))
         // App settings API
         .route("/api/settings", get(api::api_settings_get_handler))
         .route("/api/settings", post(api::api_settings_post_handler))
@@ -999,6 +1099,61 @@ mod shared_endpoints {
         assert!(json.is_object());
     }
 
+    /// Test: GET /lms/groups - LMS sync groups
+    #[tokio::test]
+    async fn get_lms_groups() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/lms/groups").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /lms/groups", &body);
+        assert!(json.get("groups").is_some());
+    }
+
+    /// Test: GET /lms/browse - Fails cleanly without an LMS server
+    #[tokio::test]
+    async fn get_lms_browse_without_server() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/lms/browse").await;
+
+        // May be configured from a saved config; either way the body is JSON
+        assert!(status == StatusCode::OK || status == StatusCode::BAD_REQUEST);
+        assert_json("GET /lms/browse", &body);
+    }
+
+    /// Test: GET /lms/instances - LMS server instances (default always listed)
+    #[tokio::test]
+    async fn get_lms_instances() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/lms/instances").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /lms/instances", &body);
+        let instances = json["instances"].as_array().expect("instances array");
+        assert!(instances.iter().any(|i| i["name"] == "default"));
+    }
+
+    /// Test: GET /lms/players?instance=... - Unknown instance is 404
+    #[tokio::test]
+    async fn get_lms_players_unknown_instance() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/lms/players?instance=nope").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_json("GET /lms/players?instance=nope", &body);
+    }
+
+    /// Test: GET /openhome/queue - Unknown device is a JSON error
+    #[tokio::test]
+    async fn get_openhome_queue_unknown_device() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/openhome/queue?zone_id=nope").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let json = assert_json("GET /openhome/queue", &body);
+        assert!(json.get("error").is_some());
+    }
+
     /// Test: GET /openhome/status - OpenHome adapter status
     #[tokio::test]
     async fn get_openhome_status() {
@@ -1043,6 +1198,72 @@ mod shared_endpoints {
         assert!(json.get("zones").is_some());
     }
 
+    /// Test: GET /upnp/servers - UPnP media servers list
+    #[tokio::test]
+    async fn get_upnp_servers() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/upnp/servers").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /upnp/servers", &body);
+        assert!(json.get("servers").is_some());
+    }
+
+    /// Test: GET /radio/favorites - Internet radio favorites
+    #[tokio::test]
+    async fn get_radio_favorites() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/radio/favorites").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /radio/favorites", &body);
+        assert!(json.get("favorites").is_some_and(|f| f.is_array()));
+    }
+
+    /// Test: GET /airplay/zones - AirPlay receivers list
+    #[tokio::test]
+    async fn get_airplay_zones() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/airplay/zones").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /airplay/zones", &body);
+        assert!(json.get("zones").is_some());
+    }
+
+    /// Test: GET /spotify/zones - Spotify Connect receivers list
+    #[tokio::test]
+    async fn get_spotify_zones() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/spotify/zones").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /spotify/zones", &body);
+        assert!(json.get("zones").is_some());
+    }
+
+    /// Test: GET /remote/zones - Federated remote bridge zones
+    #[tokio::test]
+    async fn get_remote_zones() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/remote/zones").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /remote/zones", &body);
+        assert!(json.get("zones").is_some());
+    }
+
+    /// Test: GET /external/adapters - External adapter list
+    #[tokio::test]
+    async fn get_external_adapters() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/external/adapters").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /external/adapters", &body);
+        assert!(json.get("adapters").is_some());
+    }
+
     /// Test: GET /api/settings - App settings
     #[tokio::test]
     async fn get_api_settings() {
@@ -1087,6 +1308,28 @@ mod shared_endpoints {
         assert!(json.is_object());
     }
 
+    /// Test: GET /hqp/presets - HQPlayer pipeline presets
+    #[tokio::test]
+    async fn get_hqp_presets() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/hqp/presets").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /hqp/presets", &body);
+        assert!(json.get("presets").is_some_and(|p| p.is_array()));
+    }
+
+    /// Test: GET /hqp/abx - A/B/X session status
+    #[tokio::test]
+    async fn get_hqp_abx() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/hqp/abx").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /hqp/abx", &body);
+        assert_eq!(json["active"], false);
+    }
+
     /// Test: GET /hqp/discover - HQPlayer network discovery
     #[tokio::test]
     async fn get_hqp_discover() {
@@ -1097,6 +1340,28 @@ mod shared_endpoints {
         let json = assert_json("GET /hqp/discover", &body);
         assert!(json.is_object());
     }
+
+    /// Test: GET /hqp/discovery - HQPlayer background discovery
+    #[tokio::test]
+    async fn get_hqp_discovery() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/hqp/discovery").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /hqp/discovery", &body);
+        assert!(json.get("instances").is_some_and(|i| i.is_array()));
+    }
+
+    /// Test: GET /lms/discover - LMS network discovery
+    #[tokio::test]
+    async fn get_lms_discover() {
+        let app = create_test_app().await;
+        let (status, body) = get_request(&app, "/lms/discover?timeout_ms=200").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let json = assert_json("GET /lms/discover", &body);
+        assert!(json.get("discovered").is_some());
+    }
 }
 
 // =============================================================================
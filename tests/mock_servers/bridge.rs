@@ -0,0 +1,143 @@
+//! Mock unified-hifi-control bridge for testing federation
+//!
+//! Simulates the bridge API a remote adapter follows: GET /zones, GET /events
+//! (SSE), GET /now_playing and POST /control
+
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+    Json, Router,
+};
+use futures::Stream;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+
+/// Mock bridge state
+#[derive(Debug, Clone, Default)]
+pub struct MockBridgeState {
+    /// Zones returned by /zones (zone_id, zone_name, source, state)
+    pub zones: Vec<Value>,
+    /// Zone IDs requested from /now_playing
+    pub now_playing_requests: Vec<String>,
+    /// Bodies posted to /control
+    pub commands: Vec<Value>,
+}
+
+#[derive(Clone)]
+struct Shared {
+    state: Arc<RwLock<MockBridgeState>>,
+    events: broadcast::Sender<String>,
+}
+
+/// Mock bridge
+pub struct MockBridge {
+    addr: SocketAddr,
+    shared: Shared,
+    handle: JoinHandle<()>,
+}
+
+impl MockBridge {
+    /// Start a mock bridge on a random port serving the given zones
+    pub async fn start(zones: Vec<Value>) -> Self {
+        let (events, _) = broadcast::channel(16);
+        let shared = Shared {
+            state: Arc::new(RwLock::new(MockBridgeState {
+                zones,
+                ..Default::default()
+            })),
+            events,
+        };
+
+        let app = Router::new()
+            .route("/zones", get(get_zones))
+            .route("/events", get(get_events))
+            .route("/now_playing", get(get_now_playing))
+            .route("/control", post(control))
+            .with_state(shared.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            addr,
+            shared,
+            handle,
+        }
+    }
+
+    /// Base URL of the bridge
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Push a bus event (as JSON) to connected /events subscribers
+    pub fn send_event(&self, event: Value) {
+        let _ = self.shared.events.send(event.to_string());
+    }
+
+    /// Get a snapshot of the bridge state
+    pub async fn state(&self) -> MockBridgeState {
+        self.shared.state.read().await.clone()
+    }
+
+    /// Stop the mock server
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+async fn get_zones(State(shared): State<Shared>) -> Json<Value> {
+    let state = shared.state.read().await;
+    Json(json!({ "zones": state.zones }))
+}
+
+async fn get_events(
+    State(shared): State<Shared>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(shared.events.subscribe())
+        .filter_map(|data| data.ok().map(|data| Ok(Event::default().data(data))));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn get_now_playing(
+    State(shared): State<Shared>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let zone_id = params.get("zone_id").cloned().unwrap_or_default();
+    shared
+        .state
+        .write()
+        .await
+        .now_playing_requests
+        .push(zone_id.clone());
+    Json(json!({
+        "zone_id": zone_id,
+        "line1": "So What",
+        "line2": "Miles Davis",
+        "line3": "Kind of Blue",
+        "is_playing": true,
+        "volume": 40.0,
+        "volume_type": "number",
+        "is_play_allowed": false,
+        "is_pause_allowed": true,
+        "is_next_allowed": true,
+        "is_previous_allowed": true,
+    }))
+}
+
+async fn control(State(shared): State<Shared>, Json(body): Json<Value>) -> Json<Value> {
+    shared.state.write().await.commands.push(body);
+    Json(json!({ "ok": true }))
+}
@@ -0,0 +1,176 @@
+//! Mock Denon/Marantz receiver for testing
+//!
+//! Simulates the CR-terminated telnet protocol on port 23
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Mock receiver state
+#[derive(Debug, Clone)]
+pub struct MockDenonState {
+    pub power: bool,
+    /// Raw master volume in half steps (0-196, 160 = 0 dB)
+    pub volume_half_steps: u32,
+    pub muted: bool,
+    pub input: String,
+    /// Every command received, in order
+    pub commands: Vec<String>,
+}
+
+impl Default for MockDenonState {
+    fn default() -> Self {
+        Self {
+            power: true,
+            volume_half_steps: 100, // 50.0 = -30 dB
+            muted: false,
+            input: "CD".to_string(),
+            commands: Vec::new(),
+        }
+    }
+}
+
+/// Mock Denon receiver
+pub struct MockDenonReceiver {
+    addr: SocketAddr,
+    state: Arc<RwLock<MockDenonState>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockDenonReceiver {
+    /// Start a mock receiver on a random port
+    pub async fn start() -> Self {
+        let state = Arc::new(RwLock::new(MockDenonState::default()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let state_clone = state.clone();
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = state_clone.clone();
+                tokio::spawn(async move {
+                    handle_connection(stream, state).await;
+                });
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            handle,
+        }
+    }
+
+    /// Get the server address
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Get a snapshot of the receiver state
+    pub async fn state(&self) -> MockDenonState {
+        self.state.read().await.clone()
+    }
+
+    /// Set raw master volume (e.g. 50.0 = -30 dB)
+    pub async fn set_volume(&self, raw: f32) {
+        self.state.write().await.volume_half_steps = (raw * 2.0) as u32;
+    }
+
+    /// Stop the mock server
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+/// Handle a single telnet connection
+async fn handle_connection(stream: TcpStream, state: Arc<RwLock<MockDenonState>>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        match reader.read_until(b'\r', &mut buf).await {
+            Ok(0) => break,
+            Ok(_) => {
+                let command = String::from_utf8_lossy(&buf).trim().to_string();
+                for reply in process_command(&command, &state).await {
+                    if writer.write_all(reply.as_bytes()).await.is_err() {
+                        return;
+                    }
+                    let _ = writer.write_all(b"\r").await;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+fn format_volume(half_steps: u32) -> String {
+    if half_steps.is_multiple_of(2) {
+        format!("MV{:02}", half_steps / 2)
+    } else {
+        format!("MV{:02}5", half_steps / 2)
+    }
+}
+
+/// Process a command and return the status lines the receiver would send
+async fn process_command(command: &str, state: &Arc<RwLock<MockDenonState>>) -> Vec<String> {
+    let mut state = state.write().await;
+    state.commands.push(command.to_string());
+
+    match command {
+        "PW?" => vec![if state.power { "PWON" } else { "PWSTANDBY" }.to_string()],
+        "PWON" => {
+            state.power = true;
+            vec!["PWON".to_string()]
+        }
+        "PWSTANDBY" => {
+            state.power = false;
+            vec!["PWSTANDBY".to_string()]
+        }
+        // Real receivers report the max volume alongside the current value
+        "MV?" => vec![
+            "MVMAX 98".to_string(),
+            format_volume(state.volume_half_steps),
+        ],
+        "MVUP" => {
+            state.volume_half_steps = (state.volume_half_steps + 1).min(196);
+            vec![format_volume(state.volume_half_steps)]
+        }
+        "MVDOWN" => {
+            state.volume_half_steps = state.volume_half_steps.saturating_sub(1);
+            vec![format_volume(state.volume_half_steps)]
+        }
+        "MU?" => vec![if state.muted { "MUON" } else { "MUOFF" }.to_string()],
+        "MUON" => {
+            state.muted = true;
+            vec!["MUON".to_string()]
+        }
+        "MUOFF" => {
+            state.muted = false;
+            vec!["MUOFF".to_string()]
+        }
+        "SI?" => vec![format!("SI{}", state.input)],
+        _ => {
+            if let Some(value) = command.strip_prefix("MV") {
+                let parsed = match value.len() {
+                    2 => value.parse::<u32>().ok().map(|v| v * 2),
+                    3 => value.parse::<u32>().ok().map(|v| v / 10 * 2 + 1),
+                    _ => None,
+                };
+                if let Some(half_steps) = parsed {
+                    state.volume_half_steps = half_steps;
+                    return vec![format_volume(half_steps)];
+                }
+            } else if let Some(input) = command.strip_prefix("SI") {
+                state.input = input.to_string();
+                return vec![command.to_string()];
+            }
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Mock go-librespot daemon for testing
+//!
+//! Simulates the local API: GET /status and POST /player/*
+
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Mock player state
+#[derive(Debug, Clone)]
+pub struct MockGoLibrespotState {
+    pub paused: bool,
+    pub volume: u32,
+    pub volume_steps: u32,
+    pub position_ms: u64,
+    pub commands: Vec<String>,
+}
+
+impl Default for MockGoLibrespotState {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            volume: 32,
+            volume_steps: 64,
+            position_ms: 30_000,
+            commands: Vec::new(),
+        }
+    }
+}
+
+type SharedState = Arc<RwLock<MockGoLibrespotState>>;
+
+/// Mock go-librespot daemon
+pub struct MockGoLibrespot {
+    addr: SocketAddr,
+    state: SharedState,
+    handle: JoinHandle<()>,
+}
+
+impl MockGoLibrespot {
+    /// Start a mock daemon on a random port
+    pub async fn start() -> Self {
+        let state = Arc::new(RwLock::new(MockGoLibrespotState::default()));
+
+        let app = Router::new()
+            .route("/status", get(get_status))
+            .route("/player/pause", post(pause))
+            .route("/player/resume", post(resume))
+            .route("/player/volume", post(set_volume))
+            .with_state(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            addr,
+            state,
+            handle,
+        }
+    }
+
+    /// Base URL of the local API
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Get a snapshot of the player state
+    pub async fn state(&self) -> MockGoLibrespotState {
+        self.state.read().await.clone()
+    }
+
+    /// Stop the mock server
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+async fn get_status(State(state): State<SharedState>) -> Json<Value> {
+    let state = state.read().await;
+    Json(json!({
+        "username": "test",
+        "device_id": "abc123",
+        "device_type": "computer",
+        "device_name": "Office",
+        "play_origin": "go-librespot",
+        "stopped": false,
+        "paused": state.paused,
+        "buffering": false,
+        "volume": state.volume,
+        "volume_steps": state.volume_steps,
+        "repeat_context": false,
+        "repeat_track": false,
+        "shuffle_context": false,
+        "track": {
+            "uri": "spotify:track:4vLYewWIvqHfKtJDk8c8tq",
+            "name": "Blue in Green",
+            "artist_names": ["Miles Davis", "Bill Evans"],
+            "album_name": "Kind of Blue",
+            "album_cover_url": "https://i.scdn.co/image/ab67616d0000b273",
+            "position": state.position_ms,
+            "duration": 337_000,
+            "release_date": "1959",
+            "track_number": 3,
+            "disc_number": 1
+        }
+    }))
+}
+
+async fn pause(State(state): State<SharedState>) -> Json<Value> {
+    let mut state = state.write().await;
+    state.paused = true;
+    state.commands.push("pause".to_string());
+    Json(json!({}))
+}
+
+async fn resume(State(state): State<SharedState>) -> Json<Value> {
+    let mut state = state.write().await;
+    state.paused = false;
+    state.commands.push("resume".to_string());
+    Json(json!({}))
+}
+
+async fn set_volume(State(state): State<SharedState>, Json(body): Json<Value>) -> Json<Value> {
+    let mut state = state.write().await;
+    if let Some(volume) = body.get("volume").and_then(Value::as_u64) {
+        state.volume = volume as u32;
+    }
+    state.commands.push("volume".to_string());
+    Json(json!({}))
+}
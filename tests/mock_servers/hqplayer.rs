@@ -13,18 +13,50 @@ use tokio::task::JoinHandle;
 #[derive(Debug, Clone)]
 pub struct MockHqpState {
     pub state: u8, // 0=stopped, 1=paused, 2=playing
-    pub mode: u8,  // 0=PCM, 1=SDM
+    pub mode: u8,  // 0=PCM, 1=SDM, 2=[source]
     pub filter: u32,
     pub shaper: u32,
     pub rate: u32,
     pub volume: i32, // dB value
+    /// Toggled by VolumeMute (HQPlayer doesn't report it)
+    pub muted: bool,
     pub track_title: String,
     pub track_artist: String,
     pub track_album: String,
     pub position: u32,
     pub length: u32,
+    /// Source sample rate of the playing track
+    pub samplerate: u32,
+    /// Playlist URIs
+    pub playlist: Vec<String>,
+    /// Playlist entry selected with SelectTrack
+    pub track: u32,
 }
 
+/// Mode list (index order) as (name, value); "[source]" is value -1 as in HQPlayer
+const MODES: [(&str, i32); 3] = [("PCM", 0), ("SDM", 1), ("[source]", -1)];
+
+/// Filter, shaper and rate lists (index order) served by GetFilters etc.
+const FILTERS: [&str; 2] = ["poly-sinc-xtr", "closed-form"];
+const SHAPERS: [&str; 2] = ["NS9", "NS5"];
+const RATES: [u32; 2] = [352800, 705600];
+
+/// Library served by LibraryGet: (path, artist, album, tracks)
+const LIBRARY: [(&str, &str, &str, [&str; 2]); 2] = [
+    (
+        "/music/Bach/Goldberg",
+        "Glenn Gould",
+        "Goldberg Variations",
+        ["01 Aria.flac", "02 Variatio 1.flac"],
+    ),
+    (
+        "/music/Miles Davis/Kind of Blue",
+        "Miles Davis",
+        "Kind of Blue",
+        ["01 So What.flac", "02 Freddie Freeloader.flac"],
+    ),
+];
+
 impl Default for MockHqpState {
     fn default() -> Self {
         Self {
@@ -39,6 +71,10 @@ impl Default for MockHqpState {
             track_album: String::new(),
             position: 0,
             length: 0,
+            samplerate: 44100,
+            playlist: Vec::new(),
+            track: 0,
+            muted: false,
         }
     }
 }
@@ -103,6 +139,25 @@ impl MockHqpServer {
         state.length = length;
     }
 
+    /// Change the pipeline as if from HQPlayer's own UI (list indexes)
+    pub async fn set_pipeline(&self, mode: u8, filter: u32, shaper: u32, rate: u32) {
+        let mut state = self.state.write().await;
+        state.mode = mode;
+        state.filter = filter;
+        state.shaper = shaper;
+        state.rate = rate;
+    }
+
+    /// Set the source sample rate (DSD64 = 2822400)
+    pub async fn set_samplerate(&self, samplerate: u32) {
+        self.state.write().await.samplerate = samplerate;
+    }
+
+    /// Current state (e.g. to check pipeline changes made by the bridge)
+    pub async fn state(&self) -> MockHqpState {
+        self.state.read().await.clone()
+    }
+
     /// Stop the mock server
     pub async fn stop(self) {
         self.handle.abort();
@@ -132,15 +187,76 @@ async fn handle_connection(stream: TcpStream, state: Arc<RwLock<MockHqpState>>)
 
 /// Process an XML command and return a response
 async fn process_command(command: &str, state: &Arc<RwLock<MockHqpState>>) -> String {
-    // Skip XML declaration
-    let command = command.trim();
+    // Skip XML declaration (sent on its own line or in front of the command)
+    let mut command = command.trim();
     if command.starts_with("<?xml") {
-        return String::new(); // Ignore declaration line
+        command = command
+            .split_once("?>")
+            .map(|(_, rest)| rest.trim())
+            .unwrap_or("");
+        if command.is_empty() {
+            return String::new(); // Declaration-only line
+        }
     }
 
     // Parse command name from XML
     let cmd_name = parse_element_name(command);
 
+    // Pipeline setters take effect (values are list indexes here)
+    let value = parse_value(command);
+    match cmd_name.as_str() {
+        "SetMode" => {
+            let value: i32 = parse_attr(command, "value").parse().unwrap_or(0);
+            if let Some(index) = MODES.iter().position(|(_, v)| *v == value) {
+                state.write().await.mode = index as u8;
+            }
+        }
+        "SetFilter" => state.write().await.filter = value,
+        "SetShaping" => state.write().await.shaper = value,
+        "SetRate" => state.write().await.rate = value,
+        "Volume" => {
+            let mut state = state.write().await;
+            state.volume = parse_attr(command, "value").parse().unwrap_or(state.volume);
+        }
+        "VolumeUp" => state.write().await.volume += 1,
+        "VolumeDown" => state.write().await.volume -= 1,
+        "VolumeMute" => {
+            let mut state = state.write().await;
+            state.muted = !state.muted;
+        }
+        "PlaylistAdd" => {
+            let mut state = state.write().await;
+            if parse_attr(command, "clear") == "1" {
+                state.playlist.clear();
+            }
+            state.playlist.push(parse_attr(command, "uri"));
+        }
+        "PlaylistClear" => state.write().await.playlist.clear(),
+        "PlaylistRemove" => {
+            let mut state = state.write().await;
+            let index = parse_attr(command, "index").parse().unwrap_or(usize::MAX);
+            if index < state.playlist.len() {
+                state.playlist.remove(index);
+            }
+        }
+        "SelectTrack" => {
+            state.write().await.track = parse_attr(command, "index").parse().unwrap_or(0)
+        }
+        "LibraryLoad" => {
+            let path = parse_attr(command, "path");
+            if let Some((dir, _, _, files)) = LIBRARY.iter().find(|(p, ..)| *p == path) {
+                let mut state = state.write().await;
+                if parse_attr(command, "queued") != "1" {
+                    state.playlist.clear();
+                }
+                state
+                    .playlist
+                    .extend(files.iter().map(|f| format!("{}/{}", dir, f)));
+            }
+        }
+        _ => {}
+    }
+
     let state = state.read().await;
 
     match cmd_name.as_str() {
@@ -148,19 +264,41 @@ async fn process_command(command: &str, state: &Arc<RwLock<MockHqpState>>) -> St
             "<?xml version=\"1.0\"?>\n<GetInfo name=\"MockHQPlayer\" product=\"HQPlayer\" version=\"5.0.0\" platform=\"mock\" engine=\"mock\"/>\n"
         ),
         "State" => format!(
-            "<?xml version=\"1.0\"?>\n<State state=\"{}\" mode=\"{}\" filter=\"{}\" shaper=\"{}\" rate=\"{}\" volume=\"{}\"/>\n",
-            state.state, state.mode, state.filter, state.shaper, state.rate, state.volume
+            "<?xml version=\"1.0\"?>\n<State state=\"{}\" mode=\"{}\" filter=\"{}\" shaper=\"{}\" rate=\"{}\" volume=\"{}\" active_mode=\"{}\" active_rate=\"{}\"/>\n",
+            state.state, state.mode, state.filter, state.shaper, state.rate, state.volume,
+            state.mode, RATES[state.rate as usize % RATES.len()]
         ),
         "Status" => format!(
-            "<?xml version=\"1.0\"?>\n<Status state=\"{}\" track=\"0\" track_id=\"\" position=\"{}\" length=\"{}\" volume=\"{}\" active_mode=\"PCM\" active_filter=\"poly-sinc-xtr\" active_shaper=\"NS9\" active_rate=\"352800\"/>\n",
-            state.state, state.position, state.length, state.volume
+            "<?xml version=\"1.0\"?>\n<Status state=\"{}\" track=\"0\" track_id=\"\" position=\"{}\" length=\"{}\" volume=\"{}\" active_mode=\"{}\" active_filter=\"{}\" active_shaper=\"{}\" active_rate=\"{}\" samplerate=\"{}\">{}</Status>\n",
+            state.state, state.position, state.length, state.volume,
+            MODES[state.mode as usize % MODES.len()].0,
+            FILTERS[state.filter as usize % FILTERS.len()],
+            SHAPERS[state.shaper as usize % SHAPERS.len()],
+            RATES[state.rate as usize % RATES.len()],
+            state.samplerate,
+            if state.track_title.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "<metadata albumartist=\"Various\" artist=\"{}\" title=\"{}\" album=\"{}\"/>",
+                    state.track_artist, state.track_title, state.track_album
+                )
+            }
         ),
         "VolumeRange" => {
             "<?xml version=\"1.0\"?>\n<VolumeRange min=\"-60\" max=\"0\" step=\"1\" enabled=\"1\" adaptive=\"0\"/>\n".to_string()
         }
-        "GetModes" => {
-            "<?xml version=\"1.0\"?>\n<GetModes><ModesItem index=\"0\" name=\"PCM\" value=\"0\"/><ModesItem index=\"1\" name=\"SDM\" value=\"1\"/></GetModes>\n".to_string()
-        }
+        "GetModes" => format!(
+            "<?xml version=\"1.0\"?>\n<GetModes>{}</GetModes>\n",
+            MODES
+                .iter()
+                .enumerate()
+                .map(|(i, (name, value))| format!(
+                    "<ModesItem index=\"{}\" name=\"{}\" value=\"{}\"/>",
+                    i, name, value
+                ))
+                .collect::<String>()
+        ),
         "GetFilters" => {
             "<?xml version=\"1.0\"?>\n<GetFilters><FiltersItem index=\"0\" name=\"poly-sinc-xtr\" value=\"0\" arg=\"0\"/><FiltersItem index=\"1\" name=\"closed-form\" value=\"1\" arg=\"0\"/></GetFilters>\n".to_string()
         }
@@ -173,13 +311,40 @@ async fn process_command(command: &str, state: &Arc<RwLock<MockHqpState>>) -> St
         "MatrixListProfiles" => {
             "<?xml version=\"1.0\"?>\n<MatrixListProfiles><MatrixProfile index=\"0\" name=\"Default\"/><MatrixProfile index=\"1\" name=\"Night\"/></MatrixListProfiles>\n".to_string()
         }
+        "PlaylistGet" => format!(
+            "<?xml version=\"1.0\"?>\n<PlaylistGet>{}</PlaylistGet>\n",
+            state
+                .playlist
+                .iter()
+                .enumerate()
+                .map(|(i, uri)| format!(
+                    "<PlaylistItem index=\"{}\" uri=\"{}\" length=\"300\"><metadata artist=\"Artist\" title=\"Track {}\" album=\"Album\"/></PlaylistItem>",
+                    i, uri, i + 1
+                ))
+                .collect::<String>()
+        ),
+        "LibraryGet" => format!(
+            "<?xml version=\"1.0\"?>\n<LibraryGet>{}</LibraryGet>\n",
+            LIBRARY
+                .iter()
+                .map(|(path, artist, album, files)| format!(
+                    "<LibraryDirectory path=\"{}\" artist=\"{}\" album=\"{}\" genre=\"\" date=\"\">{}</LibraryDirectory>",
+                    path, artist, album,
+                    files
+                        .iter()
+                        .map(|f| format!("<LibraryFile name=\"{}\" song=\"{}\" length=\"300\"/>", f, &f[3..f.len() - 5]))
+                        .collect::<String>()
+                ))
+                .collect::<String>()
+        ),
         "MatrixGetProfile" => {
             "<?xml version=\"1.0\"?>\n<MatrixGetProfile index=\"0\" value=\"Default\"/>\n".to_string()
         }
         // Control commands - return empty acknowledgment
         "Play" | "Pause" | "Stop" | "Previous" | "Next" | "Seek" |
         "SetMode" | "SetFilter" | "SetShaping" | "SetRate" | "Volume" |
-        "VolumeUp" | "VolumeDown" | "VolumeMute" | "MatrixSetProfile" => {
+        "VolumeUp" | "VolumeDown" | "VolumeMute" | "MatrixSetProfile" |
+        "PlaylistAdd" | "PlaylistClear" | "PlaylistRemove" | "SelectTrack" | "LibraryLoad" => {
             "<?xml version=\"1.0\"?>\n<Ok/>\n".to_string()
         }
         _ => {
@@ -188,6 +353,22 @@ async fn process_command(command: &str, state: &Arc<RwLock<MockHqpState>>) -> St
     }
 }
 
+/// Parse the value attribute from XML like "<SetMode value="1"/>"
+fn parse_value(xml: &str) -> u32 {
+    xml.split_once("value=\"")
+        .and_then(|(_, rest)| rest.split('"').next())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Parse a string attribute from a command (empty when absent)
+fn parse_attr(xml: &str, attr: &str) -> String {
+    xml.split_once(&format!(" {}=\"", attr))
+        .and_then(|(_, rest)| rest.split('"').next())
+        .unwrap_or_default()
+        .to_string()
+}
+
 /// Parse element name from XML like "<GetInfo attr="val"/>"
 fn parse_element_name(xml: &str) -> String {
     let xml = xml.trim();
@@ -27,6 +27,8 @@ pub struct MockPlayer {
     pub album: String,
     pub duration: f64,
     pub time: f64,
+    /// Master of this player's sync group (the master points at itself)
+    pub sync_master: Option<String>,
 }
 
 impl MockPlayer {
@@ -44,6 +46,7 @@ impl MockPlayer {
             album: String::new(),
             duration: 0.0,
             time: 0.0,
+            sync_master: None,
         }
     }
 }
@@ -51,6 +54,8 @@ impl MockPlayer {
 /// Mock LMS server state
 struct MockLmsState {
     players: HashMap<String, MockPlayer>,
+    /// Play/queue commands received, as "<player> <args...>"
+    commands: Vec<String>,
 }
 
 /// Mock LMS Server
@@ -65,6 +70,7 @@ impl MockLmsServer {
     pub async fn start() -> Self {
         let state = Arc::new(RwLock::new(MockLmsState {
             players: HashMap::new(),
+            commands: Vec::new(),
         }));
 
         let app = Router::new()
@@ -124,6 +130,26 @@ impl MockLmsServer {
         }
     }
 
+    /// Get player volume
+    pub async fn volume(&self, playerid: &str) -> Option<i32> {
+        let state = self.state.read().await;
+        state.players.get(playerid).map(|p| p.volume)
+    }
+
+    /// Get the master of a player's sync group
+    pub async fn sync_master(&self, playerid: &str) -> Option<String> {
+        let state = self.state.read().await;
+        state
+            .players
+            .get(playerid)
+            .and_then(|p| p.sync_master.clone())
+    }
+
+    /// Get play/queue commands received so far
+    pub async fn commands(&self) -> Vec<String> {
+        self.state.read().await.commands.clone()
+    }
+
     /// Stop the mock server
     pub async fn stop(self) {
         self.handle.abort();
@@ -167,6 +193,40 @@ async fn handle_jsonrpc(
         .and_then(|v| v.as_str())
         .ok_or(StatusCode::BAD_REQUEST)?;
 
+    // Record play/queue commands: "playlist play|add", "playlistcontrol" and
+    // XMLBrowser "<cmd> playlist play|add"
+    let words: Vec<String> = commands
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(String::from)
+                .unwrap_or_else(|| v.to_string())
+        })
+        .collect();
+    let is_play = match words.as_slice() {
+        [first, second, ..] if first == "playlist" => second == "play" || second == "add",
+        [first, ..] if first == "playlistcontrol" => true,
+        [_, second, ..] => second == "playlist",
+        _ => false,
+    };
+    if is_play {
+        let mut state = state.write().await;
+        state
+            .commands
+            .push(format!("{} {}", player_id, words.join(" ")));
+        return Ok(Json(JsonRpcResponse {
+            id: request.id,
+            result: json!({}),
+        }));
+    }
+
+    if let Some(result) = library_response(&words) {
+        return Ok(Json(JsonRpcResponse {
+            id: request.id,
+            result,
+        }));
+    }
+
     // Handle commands that modify state
     match command {
         "play" => {
@@ -231,6 +291,74 @@ async fn handle_jsonrpc(
                 result: json!({}),
             }));
         }
+        "mixer" => {
+            // mixer volume <0-100|+n|-n>
+            let mut state = state.write().await;
+            if let Some(player) = state.players.get_mut(player_id) {
+                let arg = commands.get(2).cloned().unwrap_or(Value::Null);
+                let value = arg.as_i64().map(|v| (v as i32, false)).or_else(|| {
+                    let s = arg.as_str()?;
+                    let relative = s.starts_with('+') || s.starts_with('-');
+                    s.trim_start_matches('+')
+                        .parse::<i32>()
+                        .ok()
+                        .map(|v| (v, relative))
+                });
+                if let Some((v, relative)) = value {
+                    let volume = if relative { player.volume + v } else { v };
+                    player.volume = volume.clamp(0, 100);
+                }
+            }
+            return Ok(Json(JsonRpcResponse {
+                id: request.id,
+                result: json!({}),
+            }));
+        }
+        "sync" => {
+            // "<master> sync <player>" joins player to master's group,
+            // "<player> sync -" leaves the group
+            let mut state = state.write().await;
+            let other = commands.get(1).and_then(|v| v.as_str()).unwrap_or("-");
+            if other == "-" {
+                let master = state
+                    .players
+                    .get(player_id)
+                    .and_then(|p| p.sync_master.clone());
+                if let Some(player) = state.players.get_mut(player_id) {
+                    player.sync_master = None;
+                }
+                // Dissolve the group when only the master is left
+                if let Some(master) = master {
+                    let remaining = state
+                        .players
+                        .values()
+                        .filter(|p| p.sync_master.as_deref() == Some(master.as_str()))
+                        .count();
+                    if remaining <= 1 {
+                        for p in state.players.values_mut() {
+                            if p.sync_master.as_deref() == Some(master.as_str()) {
+                                p.sync_master = None;
+                            }
+                        }
+                    }
+                }
+            } else if state.players.contains_key(player_id) {
+                let master = state
+                    .players
+                    .get(player_id)
+                    .and_then(|p| p.sync_master.clone())
+                    .unwrap_or_else(|| player_id.to_string());
+                for id in [player_id.to_string(), other.to_string()] {
+                    if let Some(player) = state.players.get_mut(&id) {
+                        player.sync_master = Some(master.clone());
+                    }
+                }
+            }
+            return Ok(Json(JsonRpcResponse {
+                id: request.id,
+                result: json!({}),
+            }));
+        }
         _ => {}
     }
 
@@ -272,7 +400,7 @@ async fn handle_jsonrpc(
                     vec![]
                 };
 
-                json!({
+                let mut status = json!({
                     "mode": player.mode,
                     "power": if player.power { 1 } else { 0 },
                     "mixer volume": player.volume,
@@ -281,15 +409,26 @@ async fn handle_jsonrpc(
                     "playlist_tracks": playlist_loop.len(),
                     "playlist_cur_index": if playlist_loop.is_empty() { Value::Null } else { json!(0) },
                     "playlist_loop": playlist_loop,
-                })
+                });
+                if let Some(ref master) = player.sync_master {
+                    let mut slaves: Vec<&str> = state
+                        .players
+                        .values()
+                        .filter(|p| {
+                            p.sync_master.as_deref() == Some(master.as_str())
+                                && &p.playerid != master
+                        })
+                        .map(|p| p.playerid.as_str())
+                        .collect();
+                    slaves.sort();
+                    status["sync_master"] = json!(master);
+                    status["sync_slaves"] = json!(slaves.join(","));
+                }
+                status
             } else {
                 json!({})
             }
         }
-        "mixer" => {
-            // Volume control - return empty success
-            json!({})
-        }
         "playlist" => {
             // Playlist control (next/prev) - return empty success
             json!({})
@@ -305,6 +444,127 @@ async fn handle_jsonrpc(
     }))
 }
 
+/// Mock LMS discovery responder (UDP, answers 'e' requests with 'E' TLVs)
+pub struct MockLmsDiscovery {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl MockLmsDiscovery {
+    /// Start a responder on a random local port advertising the given name and JSON port
+    pub async fn start(name: &str, json_port: u16) -> Self {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+
+        let mut response = vec![b'E'];
+        for (tag, value) in [
+            (b"NAME", name.to_string()),
+            (b"JSON", json_port.to_string()),
+            (b"UUID", "f2a1c0de-0000-4000-8000-000000000001".to_string()),
+            (b"VERS", "9.0.2".to_string()),
+        ] {
+            response.extend_from_slice(tag);
+            response.push(value.len() as u8);
+            response.extend_from_slice(value.as_bytes());
+        }
+
+        let handle = tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((len, peer)) = socket.recv_from(&mut buf).await {
+                if len > 0 && buf[0] == b'e' {
+                    let _ = socket.send_to(&response, peer).await;
+                }
+            }
+        });
+
+        Self { addr, handle }
+    }
+
+    /// Address to send discovery requests to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop the responder
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+/// Canned library, favorites and radio app responses
+fn library_response(words: &[String]) -> Option<Value> {
+    let has = |arg: &str| words.iter().any(|w| w == arg);
+    let result = match words.first()?.as_str() {
+        "favorites" if words.get(1)? == "items" => {
+            if has("item_id:1") {
+                json!({
+                    "title": "Stations",
+                    "count": 1,
+                    "loop_loop": [
+                        {"id": "1.0", "name": "Radio Paradise", "isaudio": 1, "hasitems": 0}
+                    ]
+                })
+            } else {
+                json!({
+                    "title": "Favorites",
+                    "count": 2,
+                    "loop_loop": [
+                        {"id": "0", "name": "Morning Jazz", "isaudio": 1, "hasitems": 0,
+                         "image": "/imageproxy/fav0/image.png"},
+                        {"id": "1", "name": "Stations", "isaudio": 0, "hasitems": 1}
+                    ]
+                })
+            }
+        }
+        "radios" => json!({
+            "count": 1,
+            "radioss_loop": [
+                {"cmd": "radioparadise", "name": "Radio Paradise", "type": "xmlbrowser",
+                 "icon": "plugins/RadioParadise/html/images/icon.png"}
+            ]
+        }),
+        "artists" => json!({
+            "count": 1,
+            "artists_loop": [{"id": 1, "artist": "Miles Davis"}]
+        }),
+        "albums" => json!({
+            "count": 1,
+            "albums_loop": [
+                {"id": 10, "album": "Kind of Blue", "artist": "Miles Davis",
+                 "artwork_track_id": "abc123"}
+            ]
+        }),
+        "titles" => json!({
+            "count": 2,
+            "titles_loop": [
+                {"id": 100, "title": "So What", "artist": "Miles Davis",
+                 "album": "Kind of Blue", "url": "file:///music/01%20So%20What.flac"},
+                {"id": 101, "title": "Freddie Freeloader", "artist": "Miles Davis",
+                 "album": "Kind of Blue"}
+            ]
+        }),
+        "playlists" if has("tracks") => json!({
+            "count": 1,
+            "playlisttracks_loop": [
+                {"id": 100, "title": "So What", "artist": "Miles Davis",
+                 "url": "file:///music/01%20So%20What.flac"}
+            ]
+        }),
+        "playlists" => json!({
+            "count": 1,
+            "playlists_loop": [{"id": 5, "playlist": "Sunday Morning"}]
+        }),
+        "search" => json!({
+            "count": 3,
+            "contributors_loop": [{"contributor": "Miles Davis", "contributor_id": 1}],
+            "albums_loop": [{"album": "Kind of Blue", "album_id": 10}],
+            "tracks_loop": [{"track": "So What", "track_id": 100}]
+        }),
+        _ => return None,
+    };
+    Some(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
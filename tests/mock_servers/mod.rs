@@ -1,16 +1,28 @@
 //! Mock servers for adapter integration testing
 //!
-//! These mock servers simulate real backend services (Roon, LMS, HQPlayer, UPnP, OpenHome)
-//! allowing full integration testing without real hardware.
+//! These mock servers simulate real backend services (Roon, LMS, HQPlayer, UPnP, OpenHome,
+//! go-librespot, remote bridges)
+//! and network/serial amplifiers (Denon/Marantz, Yamaha, RS-232), allowing full integration testing
+//! without real hardware.
 
+pub mod bridge;
+pub mod denon;
+pub mod go_librespot;
 pub mod hqplayer;
 pub mod lms;
 pub mod openhome;
 pub mod roon;
+pub mod serial;
 pub mod upnp;
+pub mod yamaha;
 
+pub use bridge::MockBridge;
+pub use denon::MockDenonReceiver;
+pub use go_librespot::MockGoLibrespot;
 pub use hqplayer::MockHqpServer;
-pub use lms::MockLmsServer;
+pub use lms::{MockLmsDiscovery, MockLmsServer};
 pub use openhome::MockOpenHomeDevice;
 pub use roon::MockRoonCore;
-pub use upnp::MockUpnpRenderer;
+pub use serial::MockSerialAmp;
+pub use upnp::{MockMediaServer, MockUpnpRenderer};
+pub use yamaha::MockYamahaReceiver;
@@ -31,6 +31,23 @@ pub struct MockOpenHomeState {
     pub track_artist: String,
     pub track_album: String,
     pub track_art_url: String,
+    /// Playlist tracks: (id, uri, metadata)
+    pub playlist: Vec<(u32, String, String)>,
+    pub next_track_id: u32,
+    /// Playlist ID of the current track (0 = none)
+    pub current_track_id: u32,
+    pub shuffle: bool,
+    pub repeat: bool,
+    /// Product sources: (name, type)
+    pub sources: Vec<(String, String)>,
+    pub source_index: u32,
+    pub standby: bool,
+    /// Radio presets: (id, metadata); ID 0 is an empty slot
+    pub radio_channels: Vec<(u32, String)>,
+    /// Radio stream currently tuned (SetId / SetChannel)
+    pub radio_uri: String,
+    pub radio_metadata: String,
+    pub radio_channel_id: u32,
 }
 
 impl Default for MockOpenHomeState {
@@ -47,10 +64,44 @@ impl Default for MockOpenHomeState {
             track_artist: String::new(),
             track_album: String::new(),
             track_art_url: String::new(),
+            playlist: Vec::new(),
+            next_track_id: 1,
+            current_track_id: 0,
+            shuffle: false,
+            repeat: false,
+            sources: vec![
+                ("Playlist".to_string(), "Playlist".to_string()),
+                ("Radio".to_string(), "Radio".to_string()),
+                ("TV".to_string(), "Hdmi".to_string()),
+            ],
+            source_index: 0,
+            standby: false,
+            radio_channels: vec![
+                (
+                    1,
+                    radio_didl("Radio Paradise", "http://stream.radioparadise.com/flac"),
+                ),
+                (0, String::new()),
+                (
+                    2,
+                    radio_didl("FIP", "http://icecast.radiofrance.fr/fip-hifi.aac"),
+                ),
+            ],
+            radio_uri: String::new(),
+            radio_metadata: String::new(),
+            radio_channel_id: 0,
         }
     }
 }
 
+/// DIDL-Lite metadata for a radio preset
+fn radio_didl(title: &str, uri: &str) -> String {
+    format!(
+        r#"<DIDL-Lite xmlns="urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:upnp="urn:schemas-upnp-org:metadata-1-0/upnp/"><item id="" parentID="" restricted="1"><dc:title>{}</dc:title><upnp:class>object.item.audioItem.audioBroadcast</upnp:class><res protocolInfo="http-get:*:*:*">{}</res></item></DIDL-Lite>"#,
+        title, uri
+    )
+}
+
 /// Mock OpenHome device
 pub struct MockOpenHomeDevice {
     addr: SocketAddr,
@@ -73,6 +124,9 @@ impl MockOpenHomeDevice {
             .route("/Transport/control", post(handle_transport))
             .route("/Volume/control", post(handle_volume))
             .route("/Info/control", post(handle_info))
+            .route("/Playlist/control", post(handle_playlist))
+            .route("/Product/control", post(handle_product))
+            .route("/Radio/control", post(handle_radio))
             .with_state(state.clone());
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -128,6 +182,16 @@ impl MockOpenHomeDevice {
         state.track_art_url = art_url.to_string();
     }
 
+    /// Set standby (as if from the device's remote)
+    pub async fn set_standby(&self, standby: bool) {
+        self.state.write().await.standby = standby;
+    }
+
+    /// Get the state (e.g. the Playlist)
+    pub async fn state(&self) -> MockOpenHomeState {
+        self.state.read().await.clone()
+    }
+
     /// Stop the mock server
     pub async fn stop(self) {
         self.handle.abort();
@@ -172,6 +236,27 @@ async fn handle_description(
         <eventSubURL>/Info/event</eventSubURL>
         <SCPDURL>/Info/scpd.xml</SCPDURL>
       </service>
+      <service>
+        <serviceType>urn:av-openhome-org:service:Product:2</serviceType>
+        <serviceId>urn:av-openhome-org:serviceId:Product</serviceId>
+        <controlURL>/Product/control</controlURL>
+        <eventSubURL>/Product/event</eventSubURL>
+        <SCPDURL>/Product/scpd.xml</SCPDURL>
+      </service>
+      <service>
+        <serviceType>urn:av-openhome-org:service:Playlist:1</serviceType>
+        <serviceId>urn:av-openhome-org:serviceId:Playlist</serviceId>
+        <controlURL>/Playlist/control</controlURL>
+        <eventSubURL>/Playlist/event</eventSubURL>
+        <SCPDURL>/Playlist/scpd.xml</SCPDURL>
+      </service>
+      <service>
+        <serviceType>urn:av-openhome-org:service:Radio:1</serviceType>
+        <serviceId>urn:av-openhome-org:serviceId:Radio</serviceId>
+        <controlURL>/Radio/control</controlURL>
+        <eventSubURL>/Radio/event</eventSubURL>
+        <SCPDURL>/Radio/scpd.xml</SCPDURL>
+      </service>
     </serviceList>
   </device>
 </root>"#,
@@ -374,6 +459,350 @@ async fn handle_info(
         .unwrap()
 }
 
+/// Extract and unescape a SOAP argument from a request body
+fn soap_arg(body: &str, name: &str) -> Option<String> {
+    let start = body.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = body[start..].find(&format!("</{}>", name))? + start;
+    quick_xml::escape::unescape(&body[start..end])
+        .ok()
+        .map(|v| v.into_owned())
+}
+
+/// Handle Playlist SOAP requests (on-device queue)
+async fn handle_playlist(
+    State(state): State<Arc<RwLock<MockOpenHomeState>>>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    use base64::Engine;
+
+    let action = headers
+        .get("soapaction")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .rsplit('#')
+        .next()
+        .unwrap_or("")
+        .trim_matches('"')
+        .to_string();
+    let value = soap_arg(&body, "Value").unwrap_or_default();
+
+    let mut state = state.write().await;
+    let content = match action.as_str() {
+        "IdArray" => {
+            let bytes: Vec<u8> = state
+                .playlist
+                .iter()
+                .flat_map(|(id, _, _)| id.to_be_bytes())
+                .collect();
+            format!(
+                "<Token>1</Token><Array>{}</Array>",
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            )
+        }
+        "ReadList" => {
+            let ids: Vec<u32> = soap_arg(&body, "IdList")
+                .unwrap_or_default()
+                .split_whitespace()
+                .filter_map(|id| id.parse().ok())
+                .collect();
+            let entries: String = state
+                .playlist
+                .iter()
+                .filter(|(id, _, _)| ids.contains(id))
+                .map(|(id, uri, metadata)| {
+                    format!(
+                        "<Entry><Id>{}</Id><Uri>{}</Uri><Metadata>{}</Metadata></Entry>",
+                        id,
+                        quick_xml::escape::escape(uri),
+                        quick_xml::escape::escape(metadata)
+                    )
+                })
+                .collect();
+            format!(
+                "<TrackList>{}</TrackList>",
+                quick_xml::escape::escape(format!("<TrackList>{}</TrackList>", entries))
+            )
+        }
+        "Insert" => {
+            let after_id: u32 = soap_arg(&body, "AfterId")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let position = if after_id == 0 {
+                0
+            } else {
+                match state.playlist.iter().position(|(id, _, _)| *id == after_id) {
+                    Some(index) => index + 1,
+                    None => return playlist_fault(),
+                }
+            };
+            let id = state.next_track_id;
+            state.next_track_id += 1;
+            let uri = soap_arg(&body, "Uri").unwrap_or_default();
+            let metadata = soap_arg(&body, "Metadata").unwrap_or_default();
+            state.playlist.insert(position, (id, uri, metadata));
+            format!("<NewId>{}</NewId>", id)
+        }
+        "DeleteId" => {
+            let id: u32 = value.parse().unwrap_or(0);
+            state.playlist.retain(|(track_id, _, _)| *track_id != id);
+            if state.current_track_id == id {
+                state.current_track_id = 0;
+            }
+            String::new()
+        }
+        "DeleteAll" => {
+            state.playlist.clear();
+            state.current_track_id = 0;
+            String::new()
+        }
+        "SeekId" => {
+            let id: u32 = value.parse().unwrap_or(0);
+            if !state
+                .playlist
+                .iter()
+                .any(|(track_id, _, _)| *track_id == id)
+            {
+                return playlist_fault();
+            }
+            state.current_track_id = id;
+            String::new()
+        }
+        "Play" => {
+            state.state = "Playing".to_string();
+            String::new()
+        }
+        "Id" => format!("<Value>{}</Value>", state.current_track_id),
+        "Shuffle" => format!("<Value>{}</Value>", state.shuffle),
+        "Repeat" => format!("<Value>{}</Value>", state.repeat),
+        "SetShuffle" => {
+            state.shuffle = value == "true" || value == "1";
+            String::new()
+        }
+        "SetRepeat" => {
+            state.repeat = value == "true" || value == "1";
+            String::new()
+        }
+        _ => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from("Unknown action"))
+                .unwrap();
+        }
+    };
+
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <u:{action}Response xmlns:u="urn:av-openhome-org:service:Playlist:1">{content}</u:{action}Response>
+  </s:Body>
+</s:Envelope>"#
+    );
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+        .body(Body::from(xml))
+        .unwrap()
+}
+
+/// Handle Product SOAP requests (sources, standby)
+async fn handle_product(
+    State(state): State<Arc<RwLock<MockOpenHomeState>>>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    let action = headers
+        .get("soapaction")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let value = soap_arg(&body, "Value").unwrap_or_default();
+
+    let mut state = state.write().await;
+    let (action_name, content) = if action.contains("#SourceXml") {
+        let sources: String = state
+            .sources
+            .iter()
+            .map(|(name, source_type)| {
+                format!(
+                    "<Source><Name>{}</Name><Type>{}</Type><Visible>true</Visible></Source>",
+                    name, source_type
+                )
+            })
+            .collect();
+        (
+            "SourceXml",
+            format!(
+                "<Value>{}</Value>",
+                quick_xml::escape::escape(format!("<SourceList>{}</SourceList>", sources))
+            ),
+        )
+    } else if action.contains("#SourceIndex") {
+        (
+            "SourceIndex",
+            format!("<Value>{}</Value>", state.source_index),
+        )
+    } else if action.contains("#SetSourceIndex") {
+        let index: u32 = value.parse().unwrap_or(u32::MAX);
+        if index as usize >= state.sources.len() {
+            return playlist_fault();
+        }
+        state.source_index = index;
+        state.standby = false;
+        ("SetSourceIndex", String::new())
+    } else if action.contains("#Standby") {
+        ("Standby", format!("<Value>{}</Value>", state.standby))
+    } else if action.contains("#SetStandby") {
+        state.standby = value == "true" || value == "1";
+        ("SetStandby", String::new())
+    } else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Unknown action"))
+            .unwrap();
+    };
+
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <u:{action_name}Response xmlns:u="urn:av-openhome-org:service:Product:2">{content}</u:{action_name}Response>
+  </s:Body>
+</s:Envelope>"#
+    );
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+        .body(Body::from(xml))
+        .unwrap()
+}
+
+/// Handle Radio SOAP requests (presets, arbitrary streams)
+async fn handle_radio(
+    State(state): State<Arc<RwLock<MockOpenHomeState>>>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    use base64::Engine;
+
+    let action = headers
+        .get("soapaction")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .rsplit('#')
+        .next()
+        .unwrap_or("")
+        .trim_matches('"')
+        .to_string();
+
+    let mut state = state.write().await;
+    let content = match action.as_str() {
+        "IdArray" => {
+            let bytes: Vec<u8> = state
+                .radio_channels
+                .iter()
+                .flat_map(|(id, _)| id.to_be_bytes())
+                .collect();
+            format!(
+                "<Token>1</Token><Array>{}</Array>",
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            )
+        }
+        "ReadList" => {
+            let ids: Vec<u32> = soap_arg(&body, "IdList")
+                .unwrap_or_default()
+                .split_whitespace()
+                .filter_map(|id| id.parse().ok())
+                .collect();
+            let entries: String = state
+                .radio_channels
+                .iter()
+                .filter(|(id, _)| ids.contains(id))
+                .map(|(id, metadata)| {
+                    format!(
+                        "<Entry><Id>{}</Id><Metadata>{}</Metadata></Entry>",
+                        id,
+                        quick_xml::escape::escape(metadata)
+                    )
+                })
+                .collect();
+            format!(
+                "<ChannelList>{}</ChannelList>",
+                quick_xml::escape::escape(format!("<ChannelList>{}</ChannelList>", entries))
+            )
+        }
+        "SetId" => {
+            let id: u32 = soap_arg(&body, "Value")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let Some((_, metadata)) = state
+                .radio_channels
+                .iter()
+                .find(|(c, _)| *c == id && id != 0)
+            else {
+                return playlist_fault();
+            };
+            state.radio_metadata = metadata.clone();
+            state.radio_channel_id = id;
+            state.radio_uri = soap_arg(&body, "Uri").unwrap_or_default();
+            String::new()
+        }
+        "SetChannel" => {
+            state.radio_channel_id = 0;
+            state.radio_uri = soap_arg(&body, "Uri").unwrap_or_default();
+            state.radio_metadata = soap_arg(&body, "Metadata").unwrap_or_default();
+            String::new()
+        }
+        "Play" => {
+            state.state = "Playing".to_string();
+            String::new()
+        }
+        _ => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from("Unknown action"))
+                .unwrap();
+        }
+    };
+
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <u:{action}Response xmlns:u="urn:av-openhome-org:service:Radio:1">{content}</u:{action}Response>
+  </s:Body>
+</s:Envelope>"#
+    );
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+        .body(Body::from(xml))
+        .unwrap()
+}
+
+/// SOAP error response (e.g. an unknown track ID or source index)
+fn playlist_fault() -> Response {
+    Response::builder()
+        .status(StatusCode::INTERNAL_SERVER_ERROR)
+        .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+        .body(Body::from(
+            r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <s:Fault>
+      <faultcode>s:Client</faultcode>
+      <faultstring>UPnPError</faultstring>
+      <detail>
+        <UPnPError xmlns="urn:schemas-upnp-org:control-1-0">
+          <errorCode>800</errorCode>
+          <errorDescription>Id not found</errorDescription>
+        </UPnPError>
+      </detail>
+    </s:Fault>
+  </s:Body>
+</s:Envelope>"#,
+        ))
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
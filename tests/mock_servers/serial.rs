@@ -0,0 +1,143 @@
+//! Mock RS-232 amplifier for testing
+//!
+//! Runs a simple CR-terminated ASCII protocol on the master side of a
+//! pseudo-terminal; the driver under test opens the slave device path.
+//!
+//! Protocol: `VOL?`, `VOL=nnn`, `VOL+`, `VOL-`, `MUTE?`, `MUTE=ON|OFF`,
+//! `PWR?`, `PWR=ON|STBY`, `SRC?`, `SRC=<name>`. Every command is answered
+//! with the resulting status line.
+
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio_serial::{SerialPort, SerialStream};
+
+/// Mock amplifier state
+#[derive(Debug, Clone)]
+pub struct MockSerialState {
+    pub power: bool,
+    pub volume: i32,
+    pub muted: bool,
+    pub input: String,
+    /// Every command received, in order
+    pub commands: Vec<String>,
+}
+
+impl Default for MockSerialState {
+    fn default() -> Self {
+        Self {
+            power: true,
+            volume: 35,
+            muted: false,
+            input: "CD".to_string(),
+            commands: Vec::new(),
+        }
+    }
+}
+
+/// Mock serial amplifier on a pseudo-terminal
+pub struct MockSerialAmp {
+    port: String,
+    state: Arc<RwLock<MockSerialState>>,
+    // Keeps the slave side open (without a lock) so the master stays readable
+    _slave: std::fs::File,
+    handle: JoinHandle<()>,
+}
+
+impl MockSerialAmp {
+    /// Start a mock amplifier on a fresh pty pair
+    pub async fn start() -> Self {
+        let (master, mut slave) = SerialStream::pair().expect("failed to open pty pair");
+        let port = slave.name().expect("pty slave has a name");
+        // Release TIOCEXCL and the flock so the driver can open the device
+        // exclusively, as it would a real port
+        slave.set_exclusive(false).unwrap();
+        let holder = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&port)
+            .expect("failed to reopen pty slave");
+        drop(slave);
+
+        let state = Arc::new(RwLock::new(MockSerialState::default()));
+        let state_clone = state.clone();
+        let handle = tokio::spawn(async move {
+            handle_port(master, state_clone).await;
+        });
+
+        Self {
+            port,
+            state,
+            _slave: holder,
+            handle,
+        }
+    }
+
+    /// Device path of the slave side
+    pub fn port(&self) -> &str {
+        &self.port
+    }
+
+    /// Get a snapshot of the amplifier state
+    pub async fn state(&self) -> MockSerialState {
+        self.state.read().await.clone()
+    }
+
+    /// Stop the mock amplifier
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+async fn handle_port(master: SerialStream, state: Arc<RwLock<MockSerialState>>) {
+    let (reader, mut writer) = tokio::io::split(master);
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+
+    while let Ok(n) = reader.read_until(b'\r', &mut buf).await {
+        if n == 0 {
+            break;
+        }
+        let command = String::from_utf8_lossy(&buf).trim().to_string();
+        buf.clear();
+        if command.is_empty() {
+            continue;
+        }
+        if let Some(reply) = process_command(&command, &state).await {
+            if writer.write_all(reply.as_bytes()).await.is_err() {
+                break;
+            }
+            let _ = writer.write_all(b"\r\n").await;
+        }
+    }
+}
+
+/// Process a command and return the status line the amplifier would send
+async fn process_command(command: &str, state: &Arc<RwLock<MockSerialState>>) -> Option<String> {
+    let mut state = state.write().await;
+    state.commands.push(command.to_string());
+
+    let (key, value) = match command.split_once('=') {
+        Some((key, value)) => (key, Some(value)),
+        None => (command.trim_end_matches(['?', '+', '-']), None),
+    };
+
+    match (key, value) {
+        ("VOL", Some(v)) => state.volume = v.parse::<i32>().ok()?.clamp(0, 99),
+        ("VOL", None) if command.ends_with('+') => state.volume = (state.volume + 1).min(99),
+        ("VOL", None) if command.ends_with('-') => state.volume = (state.volume - 1).max(0),
+        ("MUTE", Some(v)) => state.muted = v == "ON",
+        ("PWR", Some(v)) => state.power = v == "ON",
+        ("SRC", Some(v)) => state.input = v.to_string(),
+        ("VOL" | "MUTE" | "PWR" | "SRC", None) => {}
+        _ => return Some("ERR".to_string()),
+    }
+
+    Some(match key {
+        "VOL" => format!("VOL={:03}", state.volume),
+        "MUTE" => format!("MUTE={}", if state.muted { "ON" } else { "OFF" }),
+        "PWR" => format!("PWR={}", if state.power { "ON" } else { "STBY" }),
+        _ => format!("SRC={}", state.input),
+    })
+}
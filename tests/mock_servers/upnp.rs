@@ -1,4 +1,4 @@
-//! Mock UPnP MediaRenderer for testing
+//! Mock UPnP MediaRenderer and MediaServer for testing
 //!
 //! Provides HTTP endpoints for device description and SOAP control.
 //! Note: Does not implement SSDP discovery - tests should directly configure adapter.
@@ -27,6 +27,14 @@ pub struct MockUpnpState {
     pub state: String, // PLAYING, PAUSED_PLAYBACK, STOPPED
     pub volume: u32,   // 0-100
     pub muted: bool,
+    /// Last SetAVTransportURI URI and metadata (unescaped)
+    pub current_uri: Option<String>,
+    pub current_metadata: Option<String>,
+    /// Last SetNextAVTransportURI URI and metadata (unescaped)
+    pub next_uri: Option<String>,
+    pub next_metadata: Option<String>,
+    /// Answer SetNextAVTransportURI with a fault (no gapless support)
+    pub reject_next: bool,
 }
 
 impl Default for MockUpnpState {
@@ -39,6 +47,11 @@ impl Default for MockUpnpState {
             state: "STOPPED".to_string(),
             volume: 50,
             muted: false,
+            current_uri: None,
+            current_metadata: None,
+            next_uri: None,
+            next_metadata: None,
+            reject_next: false,
         }
     }
 }
@@ -110,6 +123,25 @@ impl MockUpnpRenderer {
         self.state.write().await.muted = muted;
     }
 
+    /// Get the state (e.g. the URI set by SetAVTransportURI)
+    pub async fn state(&self) -> MockUpnpState {
+        self.state.read().await.clone()
+    }
+
+    /// Reject SetNextAVTransportURI, like renderers without gapless support
+    pub async fn set_reject_next(&self, reject: bool) {
+        self.state.write().await.reject_next = reject;
+    }
+
+    /// Move on to the track set with SetNextAVTransportURI, as at the end of a track
+    pub async fn advance_to_next(&self) {
+        let mut state = self.state.write().await;
+        if let Some(uri) = state.next_uri.take() {
+            state.current_uri = Some(uri);
+            state.current_metadata = state.next_metadata.take();
+        }
+    }
+
     /// Stop the mock server
     pub async fn stop(self) {
         self.handle.abort();
@@ -168,6 +200,67 @@ async fn handle_av_transport(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
 
+    if action.contains("SetNextAVTransportURI") {
+        let mut state = state.write().await;
+        if state.reject_next {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+                .body(Body::from(
+                    r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <s:Fault>
+      <faultcode>s:Client</faultcode>
+      <faultstring>UPnPError</faultstring>
+      <detail>
+        <UPnPError xmlns="urn:schemas-upnp-org:control-1-0">
+          <errorCode>401</errorCode>
+          <errorDescription>Invalid Action</errorDescription>
+        </UPnPError>
+      </detail>
+    </s:Fault>
+  </s:Body>
+</s:Envelope>"#,
+                ))
+                .unwrap();
+        }
+        state.next_uri = soap_arg(&body, "NextURI");
+        state.next_metadata = soap_arg(&body, "NextURIMetaData");
+        return soap_response(
+            "urn:schemas-upnp-org:service:AVTransport:1",
+            "SetNextAVTransportURI",
+            "",
+        );
+    }
+
+    if action.contains("GetPositionInfo") {
+        let state = state.read().await;
+        let track_uri = state.current_uri.clone().unwrap_or_default();
+        return soap_response(
+            "urn:schemas-upnp-org:service:AVTransport:1",
+            "GetPositionInfo",
+            &format!(
+                "<Track>1</Track><TrackDuration>0:09:22</TrackDuration><TrackURI>{}</TrackURI>\
+                 <RelTime>0:00:00</RelTime>",
+                quick_xml::escape::escape(&track_uri)
+            ),
+        );
+    }
+
+    if action.contains("SetAVTransportURI") {
+        let mut state = state.write().await;
+        state.current_uri = soap_arg(&body, "CurrentURI");
+        state.current_metadata = soap_arg(&body, "CurrentURIMetaData");
+        state.next_uri = None;
+        state.next_metadata = None;
+        return soap_response(
+            "urn:schemas-upnp-org:service:AVTransport:1",
+            "SetAVTransportURI",
+            "",
+        );
+    }
+
     let state_guard = state.read().await;
 
     let response_body = if action.contains("GetTransportInfo") {
@@ -286,6 +379,292 @@ async fn handle_rendering_control(
         .unwrap()
 }
 
+/// Extract and unescape a SOAP argument from a request body
+fn soap_arg(body: &str, name: &str) -> Option<String> {
+    let start = body.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = body[start..].find(&format!("</{}>", name))? + start;
+    quick_xml::escape::unescape(&body[start..end])
+        .ok()
+        .map(|v| v.into_owned())
+}
+
+/// Build a SOAP response envelope
+fn soap_response(service: &str, action: &str, content: &str) -> Response {
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <u:{action}Response xmlns:u="{service}">{content}</u:{action}Response>
+  </s:Body>
+</s:Envelope>"#
+    );
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+        .body(Body::from(xml))
+        .unwrap()
+}
+
+// =============================================================================
+// Mock MediaServer (ContentDirectory)
+// =============================================================================
+
+/// An object in the mock content tree
+#[derive(Debug, Clone)]
+pub struct MockMediaObject {
+    pub id: String,
+    pub parent_id: String,
+    pub title: String,
+    pub class: String,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// Resource URI (items only)
+    pub uri: Option<String>,
+}
+
+/// Mock MediaServer state
+#[derive(Debug, Clone)]
+pub struct MockMediaServerState {
+    pub uuid: String,
+    pub name: String,
+    pub objects: Vec<MockMediaObject>,
+    /// SearchCriteria of Search requests received
+    pub searches: Vec<String>,
+}
+
+fn container(id: &str, parent_id: &str, title: &str, class: &str) -> MockMediaObject {
+    MockMediaObject {
+        id: id.to_string(),
+        parent_id: parent_id.to_string(),
+        title: title.to_string(),
+        class: class.to_string(),
+        artist: None,
+        album: None,
+        uri: None,
+    }
+}
+
+fn track(id: &str, parent_id: &str, title: &str, port_placeholder: &str) -> MockMediaObject {
+    MockMediaObject {
+        id: id.to_string(),
+        parent_id: parent_id.to_string(),
+        title: title.to_string(),
+        class: "object.item.audioItem.musicTrack".to_string(),
+        artist: Some("Miles Davis".to_string()),
+        album: Some("Kind of Blue".to_string()),
+        uri: Some(format!("http://{}/music/{}.flac", port_placeholder, id)),
+    }
+}
+
+impl Default for MockMediaServerState {
+    fn default() -> Self {
+        Self {
+            uuid: "mock-media-server-uuid".to_string(),
+            name: "Mock MinimServer".to_string(),
+            objects: vec![
+                container("1", "0", "Albums", "object.container"),
+                container(
+                    "1$1",
+                    "1",
+                    "Kind of Blue",
+                    "object.container.album.musicAlbum",
+                ),
+                track("1$1$1", "1$1", "So What", "media.local"),
+                track("1$1$2", "1$1", "Freddie Freeloader", "media.local"),
+            ],
+            searches: Vec::new(),
+        }
+    }
+}
+
+impl MockMediaObject {
+    fn to_didl(&self) -> String {
+        let tag = if self.uri.is_some() {
+            "item"
+        } else {
+            "container"
+        };
+        let mut xml = format!(
+            r#"<{tag} id="{}" parentID="{}" restricted="1"><dc:title>{}</dc:title><upnp:class>{}</upnp:class>"#,
+            self.id, self.parent_id, self.title, self.class
+        );
+        if let Some(ref artist) = self.artist {
+            xml.push_str(&format!("<upnp:artist>{}</upnp:artist>", artist));
+        }
+        if let Some(ref album) = self.album {
+            xml.push_str(&format!("<upnp:album>{}</upnp:album>", album));
+        }
+        if let Some(ref uri) = self.uri {
+            xml.push_str(&format!(
+                r#"<res protocolInfo="http-get:*:audio/flac:*" duration="0:09:22">{}</res>"#,
+                uri
+            ));
+        }
+        xml.push_str(&format!("</{tag}>"));
+        xml
+    }
+}
+
+/// Mock UPnP MediaServer
+pub struct MockMediaServer {
+    addr: SocketAddr,
+    state: Arc<RwLock<MockMediaServerState>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockMediaServer {
+    /// Start a mock media server (one album of two tracks) on a random port
+    pub async fn start() -> Self {
+        let state = Arc::new(RwLock::new(MockMediaServerState::default()));
+
+        let app = Router::new()
+            .route("/server.xml", get(handle_server_description))
+            .route("/ContentDirectory/control", post(handle_content_directory))
+            .with_state(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            addr,
+            state,
+            handle,
+        }
+    }
+
+    /// Get the device description URL
+    pub fn description_url(&self) -> String {
+        format!("http://{}/server.xml", self.addr)
+    }
+
+    /// Get the UUID
+    pub async fn uuid(&self) -> String {
+        self.state.read().await.uuid.clone()
+    }
+
+    /// SearchCriteria of Search requests received
+    pub async fn searches(&self) -> Vec<String> {
+        self.state.read().await.searches.clone()
+    }
+
+    /// Stop the mock server
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+async fn handle_server_description(
+    State(state): State<Arc<RwLock<MockMediaServerState>>>,
+) -> impl IntoResponse {
+    let state = state.read().await;
+
+    // Relative control URL, as some servers report
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <specVersion><major>1</major><minor>0</minor></specVersion>
+  <device>
+    <deviceType>urn:schemas-upnp-org:device:MediaServer:1</deviceType>
+    <friendlyName>{}</friendlyName>
+    <manufacturer>Mock Corp</manufacturer>
+    <modelName>Mock Server</modelName>
+    <UDN>uuid:{}</UDN>
+    <serviceList>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:ContentDirectory:1</serviceType>
+        <serviceId>urn:upnp-org:serviceId:ContentDirectory</serviceId>
+        <controlURL>ContentDirectory/control</controlURL>
+        <eventSubURL>ContentDirectory/event</eventSubURL>
+        <SCPDURL>ContentDirectory/scpd.xml</SCPDURL>
+      </service>
+    </serviceList>
+  </device>
+</root>"#,
+        state.name, state.uuid
+    );
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/xml; charset=utf-8")
+        .body(Body::from(xml))
+        .unwrap()
+}
+
+/// Handle ContentDirectory Browse/Search
+async fn handle_content_directory(
+    State(state): State<Arc<RwLock<MockMediaServerState>>>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    let action = headers
+        .get("soapaction")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+
+    let (action_name, objects): (&str, Vec<MockMediaObject>) = if action.contains("#Browse") {
+        let object_id = soap_arg(&body, "ObjectID").unwrap_or_default();
+        let flag = soap_arg(&body, "BrowseFlag").unwrap_or_default();
+        let state = state.read().await;
+        let objects = if flag == "BrowseMetadata" {
+            state
+                .objects
+                .iter()
+                .filter(|o| o.id == object_id)
+                .cloned()
+                .collect()
+        } else {
+            state
+                .objects
+                .iter()
+                .filter(|o| o.parent_id == object_id)
+                .cloned()
+                .collect()
+        };
+        ("Browse", objects)
+    } else if action.contains("#Search") {
+        let criteria = soap_arg(&body, "SearchCriteria").unwrap_or_default();
+        let mut state = state.write().await;
+        state.searches.push(criteria.clone());
+        // Match the quoted term of the first "contains" clause against titles
+        let term = criteria
+            .split("contains \"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap_or("")
+            .to_lowercase();
+        let objects = state
+            .objects
+            .iter()
+            .filter(|o| o.uri.is_some() && o.title.to_lowercase().contains(&term))
+            .cloned()
+            .collect();
+        ("Search", objects)
+    } else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Unknown action"))
+            .unwrap();
+    };
+
+    let didl = format!(
+        r#"<DIDL-Lite xmlns="urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:upnp="urn:schemas-upnp-org:metadata-1-0/upnp/">{}</DIDL-Lite>"#,
+        objects.iter().map(|o| o.to_didl()).collect::<String>()
+    );
+    soap_response(
+        "urn:schemas-upnp-org:service:ContentDirectory:1",
+        action_name,
+        &format!(
+            "<Result>{}</Result><NumberReturned>{}</NumberReturned><TotalMatches>{}</TotalMatches><UpdateID>1</UpdateID>",
+            quick_xml::escape::escape(&didl),
+            objects.len(),
+            objects.len()
+        ),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,170 @@
+//! Mock Yamaha receiver for testing
+//!
+//! Simulates the YXC HTTP API at /YamahaExtendedControl/v1/main/*
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Mock zone state
+#[derive(Debug, Clone)]
+pub struct MockYamahaState {
+    pub power: String,
+    pub volume: i32,
+    pub max_volume: i32,
+    pub mute: bool,
+    pub input: String,
+}
+
+impl Default for MockYamahaState {
+    fn default() -> Self {
+        Self {
+            power: "on".to_string(),
+            volume: 60,
+            max_volume: 161,
+            mute: false,
+            input: "hdmi1".to_string(),
+        }
+    }
+}
+
+type SharedState = Arc<RwLock<MockYamahaState>>;
+
+/// Mock Yamaha receiver
+pub struct MockYamahaReceiver {
+    addr: SocketAddr,
+    state: SharedState,
+    handle: JoinHandle<()>,
+}
+
+impl MockYamahaReceiver {
+    /// Start a mock receiver on a random port
+    pub async fn start() -> Self {
+        let state = Arc::new(RwLock::new(MockYamahaState::default()));
+
+        let app = Router::new()
+            .route("/YamahaExtendedControl/v1/main/getStatus", get(get_status))
+            .route("/YamahaExtendedControl/v1/main/setVolume", get(set_volume))
+            .route("/YamahaExtendedControl/v1/main/setMute", get(set_mute))
+            .route("/YamahaExtendedControl/v1/main/setInput", get(set_input))
+            .route("/YamahaExtendedControl/v1/main/setPower", get(set_power))
+            .with_state(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            addr,
+            state,
+            handle,
+        }
+    }
+
+    /// Get the server address
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Get a snapshot of the zone state
+    pub async fn state(&self) -> MockYamahaState {
+        self.state.read().await.clone()
+    }
+
+    /// Stop the mock server
+    pub async fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+fn ok() -> Json<Value> {
+    Json(json!({"response_code": 0}))
+}
+
+fn invalid() -> Json<Value> {
+    Json(json!({"response_code": 3}))
+}
+
+async fn get_status(State(state): State<SharedState>) -> Json<Value> {
+    let state = state.read().await;
+    Json(json!({
+        "response_code": 0,
+        "power": state.power,
+        "volume": state.volume,
+        "max_volume": state.max_volume,
+        "mute": state.mute,
+        "input": state.input,
+        "actual_volume": {
+            "mode": "db",
+            "value": -80.5 + state.volume as f64 * 0.5,
+            "unit": "dB"
+        }
+    }))
+}
+
+async fn set_volume(
+    State(state): State<SharedState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let mut state = state.write().await;
+    let step = params
+        .get("step")
+        .and_then(|s| s.parse::<i32>().ok())
+        .unwrap_or(1);
+    match params.get("volume").map(String::as_str) {
+        Some("up") => state.volume = (state.volume + step).min(state.max_volume),
+        Some("down") => state.volume = (state.volume - step).max(0),
+        Some(value) => match value.parse::<i32>() {
+            Ok(v) if (0..=state.max_volume).contains(&v) => state.volume = v,
+            _ => return invalid(),
+        },
+        None => return invalid(),
+    }
+    ok()
+}
+
+async fn set_mute(
+    State(state): State<SharedState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    match params.get("enable").map(String::as_str) {
+        Some("true") => state.write().await.mute = true,
+        Some("false") => state.write().await.mute = false,
+        _ => return invalid(),
+    }
+    ok()
+}
+
+async fn set_input(
+    State(state): State<SharedState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    match params.get("input") {
+        Some(input) => state.write().await.input = input.clone(),
+        None => return invalid(),
+    }
+    ok()
+}
+
+async fn set_power(
+    State(state): State<SharedState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    match params.get("power").map(String::as_str) {
+        Some(power @ ("on" | "standby")) => state.write().await.power = power.to_string(),
+        _ => return invalid(),
+    }
+    ok()
+}
@@ -17,10 +17,14 @@ use std::{sync::Arc, time::Instant};
 use tokio_util::sync::CancellationToken;
 use tower::ServiceExt;
 
+use unified_hifi_control::adapters::airplay::AirPlayAdapter;
+use unified_hifi_control::adapters::external::ExternalAdapterManager;
 use unified_hifi_control::adapters::hqplayer::{HqpInstanceManager, HqpZoneLinkService};
-use unified_hifi_control::adapters::lms::LmsAdapter;
+use unified_hifi_control::adapters::lms::{LmsAdapter, LmsInstanceManager};
 use unified_hifi_control::adapters::openhome::OpenHomeAdapter;
+use unified_hifi_control::adapters::remote::RemoteAdapter;
 use unified_hifi_control::adapters::roon::RoonAdapter;
+use unified_hifi_control::adapters::spotify::SpotifyAdapter;
 use unified_hifi_control::adapters::upnp::UPnPAdapter;
 use unified_hifi_control::adapters::Startable;
 use unified_hifi_control::aggregator::ZoneAggregator;
@@ -29,6 +33,7 @@ use unified_hifi_control::api::AppState;
 use unified_hifi_control::bus::create_bus;
 use unified_hifi_control::coordinator::AdapterCoordinator;
 use unified_hifi_control::knobs::{self, KnobStore};
+use unified_hifi_control::volume::{VolumeDelegationService, VolumeOutputManager};
 
 // Stub HTML handlers for UI route tests (replacing deleted ui module)
 mod ui_stubs {
@@ -54,13 +59,30 @@ async fn create_test_app() -> Router {
     let hqplayer = hqp_instances.get_default().await;
     let hqp_zone_links = Arc::new(HqpZoneLinkService::new(hqp_instances.clone()));
     let lms = Arc::new(LmsAdapter::new(bus.clone()));
+    let lms_instances = Arc::new(LmsInstanceManager::new(bus.clone(), lms.clone()));
     let openhome = Arc::new(OpenHomeAdapter::new(bus.clone()));
     let upnp = Arc::new(UPnPAdapter::new(bus.clone()));
+    let airplay = Arc::new(AirPlayAdapter::new(bus.clone()));
+    let spotify = Arc::new(SpotifyAdapter::new(bus.clone()));
+    let remote = Arc::new(RemoteAdapter::new(bus.clone()));
+    let external_adapters = Arc::new(ExternalAdapterManager::new(coordinator.clone()));
+    let volume_outputs = Arc::new(VolumeOutputManager::new(bus.clone()));
+    let volume_delegates = Arc::new(VolumeDelegationService::new(
+        volume_outputs.clone(),
+        bus.clone(),
+    ));
     let knob_store = KnobStore::new(std::env::temp_dir());
 
     // Build startable adapters list
-    let startable_adapters: Vec<Arc<dyn Startable>> =
-        vec![roon.clone(), lms.clone(), openhome.clone(), upnp.clone()];
+    let startable_adapters: Vec<Arc<dyn Startable>> = vec![
+        roon.clone(),
+        lms_instances.clone(),
+        openhome.clone(),
+        upnp.clone(),
+        airplay.clone(),
+        spotify.clone(),
+        remote.clone(),
+    ];
 
     let aggregator = Arc::new(ZoneAggregator::new(bus.clone()));
     let state = AppState::new(
@@ -69,8 +91,15 @@ async fn create_test_app() -> Router {
         hqp_instances,
         hqp_zone_links,
         lms,
+        lms_instances,
         openhome,
         upnp,
+        airplay,
+        spotify,
+        remote,
+        external_adapters,
+        volume_outputs,
+        volume_delegates,
         knob_store,
         bus,
         aggregator,
@@ -138,6 +167,14 @@ async fn create_test_app() -> Router {
         // UPnP routes
         .route("/upnp/status", get(api::upnp_status_handler))
         .route("/upnp/zones", get(api::upnp_zones_handler))
+        .route("/airplay/status", get(api::airplay_status_handler))
+        .route("/airplay/zones", get(api::airplay_zones_handler))
+        .route("/spotify/status", get(api::spotify_status_handler))
+        .route("/spotify/zones", get(api::spotify_zones_handler))
+        .route("/remote/status", get(api::remote_status_handler))
+        .route("/remote/zones", get(api::remote_zones_handler))
+        .route("/external/adapters", get(api::external_adapters_handler))
+        .route("/external/zones", get(api::external_zones_handler))
         .route(
             "/upnp/zone/{zone_id}/now_playing",
             get(api::upnp_now_playing_handler),
@@ -426,6 +463,38 @@ mod api_endpoints {
         assert_json("/upnp/zones", &body);
     }
 
+    #[tokio::test]
+    async fn airplay_status_returns_json() {
+        let app = create_test_app().await;
+        let (status, body) = get_body(&app, "/airplay/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_json("/airplay/status", &body);
+    }
+
+    #[tokio::test]
+    async fn spotify_status_returns_json() {
+        let app = create_test_app().await;
+        let (status, body) = get_body(&app, "/spotify/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_json("/spotify/status", &body);
+    }
+
+    #[tokio::test]
+    async fn remote_status_returns_json() {
+        let app = create_test_app().await;
+        let (status, body) = get_body(&app, "/remote/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_json("/remote/status", &body);
+    }
+
+    #[tokio::test]
+    async fn external_zones_returns_json() {
+        let app = create_test_app().await;
+        let (status, body) = get_body(&app, "/external/zones").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_json("/external/zones", &body);
+    }
+
     #[tokio::test]
     async fn api_settings_returns_json() {
         let app = create_test_app().await;